@@ -8,49 +8,189 @@ export default {
       "VotePropose",
       "Concern",
       "VoteConcern",
-      "VoteCouncil"
+      "VoteCouncil",
+      "RevealPropose",
+      "RevealConcern"
     ]
   },
   "ProposalCID": "Vec<u8>",
   "ConcernCID": "ProposalCID",
   "DocumentCID": "ProposalCID",
+  "RoleCID": "ProposalCID",
+  "CategoryId": "u32",
+  "RoundIndex": "u32",
+  "ProposalMetadata": {
+    "title": "Vec<u8>",
+    "category": "CategoryId",
+    "budget": "Balance",
+    "duration": "BlockNumber",
+    "roles": "Vec<RoleCID>"
+  },
+  "Salt": "[u8; 32]",
   "Proposal": {
     "proposal": "ProposalCID",
-    "votes": "u32"
+    "votes": "u32",
+    "amends": "Option<ProposalCID>",
+    "metadata": "ProposalMetadata"
   },
   "Concern": {
     "associated_proposal": "ProposalCID",
     "concern": "ConcernCID",
-    "votes": "u32"
+    "votes": "u32",
+    "amends": "Option<ConcernCID>"
   },
   "VecDeque": "Vec<ProposalWinner>",
   "ProposalWinner": {
     "concerns": "Vec<ConcernCID>",
     "proposer": "IdentityId",
     "proposal": "ProposalCID",
-    "vote_ratio": "Permill"
+    "vote_ratio": "Permill",
+    "metadata": "ProposalMetadata"
+  },
+  "RoundSummary": {
+    "winners": "Vec<ProposalWinner>",
+    "concern_winners": "Vec<(ConcernCID, ProposalCID)>",
+    "council_result": "Vec<(ProposalCID, bool)>",
+    "projects": "Vec<ProjectID>",
+    "total_reward": "Balance"
+  },
+  "VoteTopic": {
+    "_enum": [
+      "Proposal",
+      "Concern"
+    ]
+  },
+  "Delegation": {
+    "delegate": "IdentityId",
+    "round": "Option<RoundIndex>"
+  },
+  "Phase": {
+    "state": "States",
+    "round": "RoundIndex",
+    "next_transit": "BlockNumber",
+    "next_transit_estimate": "Moment"
+  },
+  "VoteBudget": {
+    "proposal_votes": "u16",
+    "concern_votes": "u16"
+  },
+  "CycleConfig": {
+    "allow_vote_stacking": "bool",
+    "commit_reveal_voting": "bool",
+    "vote_reveal_duration": "BlockNumber",
+    "max_delegation_depth": "u32",
+    "delegate_reward_share": "Permill",
+    "propose_cap": "u32",
+    "propose_identified_user_cap": "u8",
+    "propose_identity_level": "u8",
+    "propose_max_title_length": "u32",
+    "propose_reward": "Permill",
+    "propose_round_duration": "BlockNumber",
+    "propose_round_budget": "Balance",
+    "propose_vote_acceptance_min": "Permill",
+    "propose_vote_duration": "BlockNumber",
+    "propose_vote_identity_level": "u8",
+    "propose_vote_max_per_identified_user": "u16",
+    "concern_cap": "u32",
+    "concern_identified_user_cap": "u8",
+    "concern_identity_level": "u8",
+    "concern_round_duration": "BlockNumber",
+    "concern_vote_acceptance_min": "Permill",
+    "concern_vote_duration": "BlockNumber",
+    "concern_vote_identity_level": "u8",
+    "concern_vote_max_per_identified_user": "u16",
+    "council_vote_round_duration": "BlockNumber",
+    "council_accept_concern_min_votes": "Permill"
   },
   "IdentityLevel": "u8",
   "ProofType": "[u8; 32]",
   "IdentityId": "AccountId",
   "Ticket": "u64",
+  "ReviewTicket": "AccountId",
+  "Poll": {
+    "ticket": "Ticket",
+    "documents": "Vec<DocumentCID>",
+    "until": "BlockNumber",
+    "votes": "Vec<(IdentityId, bool)>"
+  },
+  "PollTally": {
+    "poll": "Poll",
+    "accept": "u32",
+    "deny": "u32"
+  },
+  "ReviewRequest": {
+    "identity_level": "IdentityLevel",
+    "at": "Moment"
+  },
   "ProjectID": "u64",
   "Worker": {
     "worker": "IdentityId",
     "job_description": "DocumentCID",
     "salary": "Balance",
-    "hired": "BlockNumber"
+    "hired": "BlockNumber",
+    "paid_until": "BlockNumber",
+    "leaves_at": "Option<BlockNumber>"
+  },
+  "Application": {
+    "applicant": "IdentityId",
+    "position": "DocumentCID",
+    "application": "DocumentCID",
+    "votes": "u32"
+  },
+  "Offer": {
+    "applicant": "IdentityId",
+    "position": "DocumentCID",
+    "application": "DocumentCID",
+    "salary": "Balance"
+  },
+  "ProjectState": {
+    "_enum": [
+      "Spawned",
+      "Staffing",
+      "Running",
+      "Halted",
+      "Completed",
+      "Abandoned"
+    ]
   },
   "Project": {
     "id": "ProjectID",
+    "state": "ProjectState",
     "proposal": "ProposalWinner",
     "project_leader": "Option<Worker>",
-    "open_positions": "Vec<DocumentCID>",
-    "workers": "Vec<Worker>",
-    "deadline": "BlockNumber"
+    "worker_count": "u32",
+    "deadline": "BlockNumber",
+    "budget": "Balance",
+    "proposer_reward": "Balance"
   },
+  "PositionId": "DocumentCID",
   "PRJ": "Project",
   "ID": "IdentityId",
   "PW": "ProposalWinner",
-  "Timestamp": "u64"
+  "Timestamp": "u64",
+  "Releases": {
+    "_enum": [
+      "V1_0_0",
+      "V2_0_0",
+      "V3_0_0"
+    ]
+  },
+  "EraIndex": "u32",
+  "RewardCategory": {
+    "_enum": [
+      "ProposalVote",
+      "ConcernSubmit",
+      "ConcernVote"
+    ]
+  },
+  "IssuanceCurve": {
+    "_enum": {
+      "Fixed": "Balance",
+      "Decaying": {
+        "initial": "Balance",
+        "decay": "Permill"
+      },
+      "PerIdentity": "Balance"
+    }
+  }
 }
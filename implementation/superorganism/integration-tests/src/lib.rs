@@ -0,0 +1,105 @@
+//! Execute blocks of the assembled runtime. The genesis is built by testnet_genesis of the node
+//! and extrinsics are signed by the development accounts, like on a development chain.
+
+use node_superorganism::chain_spec::{authority_keys_from_seed, testnet_genesis};
+use node_superorganism_runtime::{
+	BlockNumber, BuildStorage, Call, Event, Executive, Hash, Header, Proposal, Runtime, SignedExtra, SLOT_DURATION,
+	StorageValue, System, TimestampCall, UncheckedExtrinsic, pallet_proposal::ActiveConfig,
+};
+use sp_keyring::AccountKeyring;
+use sp_runtime::{
+	codec::Encode, DispatchOutcome, generic::{Era, SignedPayload}, traits::Header as HeaderT,
+};
+use std::cell::RefCell;
+
+/// Duration of every phase of the proposal cycle in blocks. The runtime configures them to last a week.
+pub const PHASE_DURATION: BlockNumber = 10;
+
+/// Accounts that are endowed and council members at genesis
+pub const ACCOUNTS: [AccountKeyring; 4] = [
+	AccountKeyring::Alice, AccountKeyring::Bob, AccountKeyring::Charlie, AccountKeyring::Dave,
+];
+
+thread_local! {
+	/// Hash of the last executed block
+	static PARENT_HASH: RefCell<Hash> = RefCell::new(Hash::default());
+}
+
+/// Build the genesis storage with testnet_genesis. Alice is the only authority and the sudo key.
+/// The phases of the proposal cycle last PHASE_DURATION blocks.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let genesis = testnet_genesis(
+		// The runtime is executed natively, the wasm code is not required
+		&[],
+		vec![authority_keys_from_seed("Alice")],
+		AccountKeyring::Alice.to_account_id(),
+		ACCOUNTS.iter().map(|account| account.to_account_id()).collect(),
+		true,
+	);
+	let mut ext = sp_io::TestExternalities::new(genesis.build_storage().unwrap());
+	ext.execute_with(|| {
+		PARENT_HASH.with(|hash| *hash.borrow_mut() = System::block_hash(0));
+		let mut config = Proposal::config();
+		config.propose_round_duration = PHASE_DURATION;
+		config.propose_vote_duration = PHASE_DURATION;
+		config.concern_round_duration = PHASE_DURATION;
+		config.concern_vote_duration = PHASE_DURATION;
+		config.council_vote_round_duration = PHASE_DURATION;
+		config.vote_reveal_duration = PHASE_DURATION;
+		<ActiveConfig<Runtime>>::put(config);
+	});
+	ext
+}
+
+/// Sign a call with the next nonce of the account
+pub fn sign(signer: AccountKeyring, call: Call) -> UncheckedExtrinsic {
+	let account = signer.to_account_id();
+	let extra: SignedExtra = (
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(System::account_nonce(&account)),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(0),
+	);
+	let payload = SignedPayload::new(call, extra).expect("Additional signed data is available");
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(call, account, signature.into(), extra)
+}
+
+/// Execute the next block. It contains the timestamp inherent and the calls, signed by the
+/// given accounts in order. Returns the dispatch outcome of every call.
+pub fn execute_block(calls: Vec<(AccountKeyring, Call)>) -> Vec<DispatchOutcome> {
+	let number: BlockNumber = System::block_number() + 1;
+	let parent: Hash = PARENT_HASH.with(|hash| *hash.borrow());
+	Executive::initialize_block(&Header::new(number, Default::default(), Default::default(), parent,
+		Default::default()));
+
+	// Aura requires the slot to increase with every block
+	let now = u64::from(number) * SLOT_DURATION;
+	let timestamp = UncheckedExtrinsic::new_unsigned(Call::Timestamp(TimestampCall::set(now)));
+	assert_eq!(Executive::apply_extrinsic(timestamp), Ok(Ok(())));
+
+	// Sign every call right before it is applied, the nonce is increased by the previous calls
+	let outcomes = calls.into_iter()
+		.map(|(signer, call)| Executive::apply_extrinsic(sign(signer, call)).expect("Extrinsic is invalid"))
+		.collect();
+
+	let header = Executive::finalize_block();
+	PARENT_HASH.with(|hash| *hash.borrow_mut() = header.hash());
+	outcomes
+}
+
+/// Execute empty blocks up to (including) block n
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		execute_block(Vec::new());
+	}
+}
+
+/// Events deposited in the last executed block
+pub fn events() -> Vec<Event> {
+	System::events().into_iter().map(|record| record.event).collect()
+}
@@ -0,0 +1,123 @@
+use node_superorganism_integration_tests::*;
+use node_superorganism_runtime::{
+	Balance, Balances, Call, CommunityTreasury, Event, Project, Proposal, Runtime,
+	pallet_council, pallet_proposal::{self, Error as ProposalError, RawEvent as ProposalEvent},
+	pallet_proposal_types::{ProposalMetadata, States},
+};
+use sp_keyring::AccountKeyring::{Alice, Bob, Charlie};
+
+/// Budget requested by the proposals. The proposer reward fits into the budget of the first era.
+const BUDGET: Balance = 1_000_000_000_000_000;
+
+fn propose_call(proposal: &[u8]) -> Call {
+	let metadata = ProposalMetadata { title: b"Community garden".to_vec(), category: 0, budget: BUDGET,
+		duration: 100, roles: Vec::new() };
+	Call::Proposal(pallet_proposal::Call::propose(proposal.to_vec(), metadata))
+}
+
+fn vote_call(proposal: &[u8]) -> Call {
+	Call::Proposal(pallet_proposal::Call::vote_proposal(proposal.to_vec()))
+}
+
+fn council_vote_call(ticket: u64, accept: bool) -> Call {
+	Call::Council(pallet_council::Call::vote_poll(ticket, accept))
+}
+
+/// Execute the blocks up to the scheduled state transit
+fn run_to_next_transit() {
+	run_to_block(Proposal::next_transit());
+}
+
+#[test]
+fn genesis_schedules_first_state_transit() {
+	new_test_ext().execute_with(|| {
+		let duration = Proposal::config().propose_round_duration;
+		assert_eq!(Proposal::get_phase(), (States::Propose, 0, duration));
+
+		// Without proposals the propose round is repeated, every transit schedules the next one
+		for _ in 0..3 {
+			let next = Proposal::next_transit();
+			run_to_block(next - 1);
+			assert_eq!(Proposal::get_phase(), (States::Propose, 0, next));
+
+			run_to_block(next);
+			let events = events();
+			assert!(events.contains(&Event::pallet_scheduler(
+				pallet_scheduler::RawEvent::Dispatched((next, 0), None, Ok(()))
+			)));
+			assert!(events.contains(&Event::pallet_proposal(ProposalEvent::StateRotated(States::Propose))));
+			assert_eq!(Proposal::next_transit(), next + duration);
+		}
+	});
+}
+
+#[test]
+fn signed_extrinsics_spawn_project() {
+	new_test_ext().execute_with(|| {
+		let proposal = b"QmCommunityGarden";
+		let alice = Alice.to_account_id();
+
+		// Propose
+		assert_eq!(execute_block(vec![(Alice, propose_call(proposal))]), vec![Ok(())]);
+		run_to_next_transit();
+		assert_eq!(Proposal::state(), States::VotePropose);
+
+		// VotePropose
+		assert_eq!(execute_block(vec![(Bob, vote_call(proposal)), (Charlie, vote_call(proposal))]),
+			vec![Ok(()), Ok(())]);
+		run_to_next_transit();
+		assert_eq!(Proposal::state(), States::Concern);
+		assert_eq!(Proposal::proposal_winners(0).len(), 1);
+
+		// Concern: without concerns the council votes right away
+		run_to_next_transit();
+		assert_eq!(Proposal::state(), States::VoteCouncil);
+		let ticket = Proposal::council_vote_tickets()[0];
+		assert_eq!(execute_block(vec![
+			(Alice, council_vote_call(ticket, true)),
+			(Bob, council_vote_call(ticket, true)),
+			(Charlie, council_vote_call(ticket, true)),
+		]), vec![Ok(()), Ok(()), Ok(())]);
+		let balance = Balances::free_balance(&alice);
+
+		// VoteCouncil: the accepted proposal is converted into a project
+		run_to_next_transit();
+		assert_eq!(Proposal::get_phase().0, States::Propose);
+		assert_eq!(Proposal::round(), 1);
+		assert_eq!(Project::project_number(), 1);
+		let project = Project::project(0).expect("Project was not spawned");
+		assert_eq!(project.proposal.proposal, proposal.to_vec());
+		assert_eq!(project.proposal.proposer, alice);
+		assert_eq!(project.budget, BUDGET);
+		assert_eq!(Proposal::round_summary(0).unwrap().projects, vec![0]);
+
+		let reward: Balance = Proposal::config().propose_reward * BUDGET;
+		assert!(events().contains(&Event::pallet_proposal(ProposalEvent::ProposerReward(0, alice.clone(), reward))));
+		assert_eq!(project.proposer_reward, reward);
+
+		// Alice voted in the council poll as well
+		let council_reward: Balance = events().into_iter().find_map(|event| match event {
+			Event::pallet_proposal(ProposalEvent::CouncilVoteReward(member, _, reward)) if member == alice =>
+				Some(reward),
+			_ => None,
+		}).expect("Council vote was not rewarded");
+
+		// The rewards are paid from the budget minted when the first round started
+		assert!(CommunityTreasury::pending_payouts().is_empty());
+		assert_eq!(Balances::free_balance(&alice), balance + reward + council_reward);
+	});
+}
+
+#[test]
+fn signed_extrinsic_reports_dispatch_error() {
+	new_test_ext().execute_with(|| {
+		// Every identified user can submit one proposal per round
+		assert_eq!(execute_block(vec![(Alice, propose_call(b"QmFirst")), (Alice, propose_call(b"QmSecond"))]),
+			vec![Ok(()), Err(ProposalError::<Runtime>::UserProposalLimitReached.into())]);
+		assert_eq!(Proposal::proposal_count(), 1);
+
+		// Votes are only accepted in VotePropose
+		assert_eq!(execute_block(vec![(Bob, vote_call(b"QmFirst"))]),
+			vec![Err(ProposalError::<Runtime>::WrongState.into())]);
+	});
+}
@@ -1,7 +1,9 @@
 use sp_core::{Pair, Public, sr25519};
 use node_superorganism_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	ProposalConfig, SudoConfig, SystemConfig, WASM_BINARY, Signature
+	AccountId, AuraConfig, BalancesConfig, CommunityTreasuryConfig, CouncilConfig, DocumentAvailabilityConfig,
+	GenesisConfig, GrandpaConfig, Permill, ProjectConfig, ProposalConfig, SudoConfig, SystemConfig, WASM_BINARY,
+	Signature,
+	pallet_community_treasury::issuance::{IssuanceCurve, RewardCategory},
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -126,7 +128,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 }
 
 /// Configure initial storage state for FRAME modules.
-fn testnet_genesis(
+pub fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
@@ -153,8 +155,25 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		pallet_community_treasury: Some(CommunityTreasuryConfig {
+			issuance_curve: IssuanceCurve::Fixed(1_000_000_000_000_000),
+			reward_split: vec![
+				(RewardCategory::ProposalVote, Permill::from_percent(40)),
+				(RewardCategory::ConcernSubmit, Permill::from_percent(25)),
+				(RewardCategory::ConcernVote, Permill::from_percent(25)),
+				(RewardCategory::CouncilVote, Permill::from_percent(10)),
+			],
+		}),
+		pallet_council: Some(CouncilConfig {
+			members: endowed_accounts.clone(),
+		}),
+		pallet_project: Some(ProjectConfig {}),
 		pallet_proposal: Some(ProposalConfig {
-			state: Default::default()
+			state: Default::default(),
+			categories: vec![(0, b"General".to_vec())],
+		}),
+		pallet_document_availability: Some(DocumentAvailabilityConfig {
+			reporters: endowed_accounts,
 		}),
 	}
 }
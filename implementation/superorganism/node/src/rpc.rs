@@ -5,15 +5,55 @@
 
 #![warn(missing_docs)]
 
-use std::sync::Arc;
+use std::{fmt::Debug, sync::Arc};
 
-use node_superorganism_runtime::{opaque::Block, AccountId, Balance, Index};
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use node_superorganism_runtime::{
+	api::{
+		CouncilApi as CouncilRuntimeApi, IdentityApi as IdentityRuntimeApi,
+		ProjectApi as ProjectRuntimeApi, ProposalApi as ProposalRuntimeApi,
+	},
+	opaque::Block, AccountId, Balance, Index,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
 pub use sc_rpc_api::DenyUnsafe;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use sp_transaction_pool::TransactionPool;
 
+pub mod council;
+pub mod identity;
+pub mod project;
+pub mod proposal;
+
+/// Error code for failed calls into the runtime
+const RUNTIME_ERROR: i64 = 1;
+/// Error code for queries the runtime rejected
+const QUERY_ERROR: i64 = 2;
+
+/// Convert an error of a runtime API call into an RPC error
+fn runtime_error<E: Debug>(err: E) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime API call failed".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+/// Convert an error the runtime returned for a query into an RPC error
+fn query_error<E: Debug>(err: E) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(QUERY_ERROR),
+		message: "Query was rejected by the runtime".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+/// Query at the given block or at the best block
+fn block_id<C: HeaderBackend<Block>>(client: &C, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+	BlockId::hash(at.unwrap_or_else(|| client.info().best_hash))
+}
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -35,10 +75,18 @@ pub fn create_full<C, P>(
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: ProposalRuntimeApi<Block>,
+	C::Api: ProjectRuntimeApi<Block>,
+	C::Api: CouncilRuntimeApi<Block>,
+	C::Api: IdentityRuntimeApi<Block>,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use proposal::{ProposalApi, Proposals};
+	use project::{ProjectApi, Projects};
+	use council::{Council, CouncilApi};
+	use identity::{Identity, IdentityApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
@@ -55,6 +103,22 @@ pub fn create_full<C, P>(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
 
+	io.extend_with(
+		ProposalApi::to_delegate(Proposals::new(client.clone()))
+	);
+
+	io.extend_with(
+		ProjectApi::to_delegate(Projects::new(client.clone()))
+	);
+
+	io.extend_with(
+		CouncilApi::to_delegate(Council::new(client.clone()))
+	);
+
+	io.extend_with(
+		IdentityApi::to_delegate(Identity::new(client.clone()))
+	);
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -0,0 +1,56 @@
+//! `superorganism_*` RPC methods for the council.
+
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use node_superorganism_runtime::{
+	api::CouncilApi as CouncilRuntimeApi, opaque::Block, AccountId, BlockNumber,
+	pallet_council::PollTally,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use super::{block_id, runtime_error};
+
+/// Queries for the council
+#[rpc]
+pub trait CouncilApi<BlockHash> {
+	/// Get the current council members
+	#[rpc(name = "superorganism_councilMembers")]
+	fn members(&self, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// Get up to limit polls (including the tally) that still accept votes, skipping the first start polls
+	#[rpc(name = "superorganism_openPolls")]
+	fn open_polls(&self, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<PollTally<AccountId, BlockNumber>>>;
+}
+
+/// Implements the council RPC methods
+pub struct Council<C> {
+	client: Arc<C>,
+}
+
+impl<C> Council<C> {
+	/// Create new `Council` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Council { client }
+	}
+}
+
+impl<C> CouncilApi<<Block as BlockT>::Hash> for Council<C> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CouncilRuntimeApi<Block>,
+{
+	fn members(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<AccountId>> {
+		self.client.runtime_api().members(&block_id(&*self.client, at)).map_err(runtime_error)
+	}
+
+	fn open_polls(&self, start: u32, limit: u32, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<PollTally<AccountId, BlockNumber>>>
+	{
+		self.client.runtime_api().open_polls(&block_id(&*self.client, at), start, limit)
+			.map_err(runtime_error)
+	}
+}
@@ -0,0 +1,68 @@
+//! `superorganism_*` RPC methods for identities.
+
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use node_superorganism_runtime::{
+	api::IdentityApi as IdentityRuntimeApi, opaque::Block, AccountId, Moment,
+	pallet_community_identity::{IdentityLevel, ReviewRequest},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use super::{block_id, runtime_error};
+
+/// Queries for identities
+#[rpc]
+pub trait IdentityApi<BlockHash> {
+	/// Get the identity level of an identity
+	#[rpc(name = "superorganism_identityLevel")]
+	fn identity_level(&self, identity: AccountId, at: Option<BlockHash>) -> Result<IdentityLevel>;
+
+	/// Get the tickets of the peer reviews an identity requested that are still pending
+	#[rpc(name = "superorganism_pendingReviews")]
+	fn pending_reviews(&self, identity: AccountId, at: Option<BlockHash>)
+		-> Result<Vec<(AccountId, ReviewRequest<Moment>)>>;
+
+	/// Get the appointments of an identity (when the identity has to participate in a review)
+	#[rpc(name = "superorganism_appointments")]
+	fn appointments(&self, identity: AccountId, at: Option<BlockHash>)
+		-> Result<Vec<(Moment, Vec<AccountId>)>>;
+}
+
+/// Implements the identity RPC methods
+pub struct Identity<C> {
+	client: Arc<C>,
+}
+
+impl<C> Identity<C> {
+	/// Create new `Identity` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Identity { client }
+	}
+}
+
+impl<C> IdentityApi<<Block as BlockT>::Hash> for Identity<C> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: IdentityRuntimeApi<Block>,
+{
+	fn identity_level(&self, identity: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<IdentityLevel> {
+		self.client.runtime_api().identity_level(&block_id(&*self.client, at), identity).map_err(runtime_error)
+	}
+
+	fn pending_reviews(&self, identity: AccountId, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<(AccountId, ReviewRequest<Moment>)>>
+	{
+		self.client.runtime_api().pending_reviews(&block_id(&*self.client, at), identity)
+			.map_err(runtime_error)
+	}
+
+	fn appointments(&self, identity: AccountId, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<(Moment, Vec<AccountId>)>>
+	{
+		self.client.runtime_api().appointments(&block_id(&*self.client, at), identity)
+			.map_err(runtime_error)
+	}
+}
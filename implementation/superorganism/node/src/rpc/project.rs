@@ -0,0 +1,86 @@
+//! `superorganism_*` RPC methods for projects.
+
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use node_superorganism_runtime::{
+	api::ProjectApi as ProjectRuntimeApi, opaque::Block, AccountId, Balance, BlockNumber,
+	pallet_project::types::{Project, ProjectID, ProjectState, Worker},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use super::{block_id, runtime_error};
+
+/// Queries for projects
+#[rpc]
+pub trait ProjectApi<BlockHash> {
+	/// Get a live or archived project
+	#[rpc(name = "superorganism_project")]
+	fn project(&self, project: ProjectID, at: Option<BlockHash>)
+		-> Result<Option<Project<Balance, BlockNumber, AccountId>>>;
+
+	/// Get the projects in a state with an id within [start, start + limit) and the id to continue
+	/// with (null after the last project). Without a state, get the live projects.
+	#[rpc(name = "superorganism_projects")]
+	fn projects(&self, state: Option<ProjectState>, start: ProjectID, limit: u32, at: Option<BlockHash>)
+		-> Result<(Vec<Project<Balance, BlockNumber, AccountId>>, Option<ProjectID>)>;
+
+	/// Get up to limit live projects an identity works on (as worker or project leader),
+	/// skipping the first start projects
+	#[rpc(name = "superorganism_workerProjects")]
+	fn worker_projects(&self, identity: AccountId, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<Project<Balance, BlockNumber, AccountId>>>;
+
+	/// Get up to limit workers of a project, skipping the first start workers
+	#[rpc(name = "superorganism_workers")]
+	fn workers(&self, project: ProjectID, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<Worker<Balance, BlockNumber, AccountId>>>;
+}
+
+/// Implements the project RPC methods
+pub struct Projects<C> {
+	client: Arc<C>,
+}
+
+impl<C> Projects<C> {
+	/// Create new `Projects` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Projects { client }
+	}
+}
+
+impl<C> ProjectApi<<Block as BlockT>::Hash> for Projects<C> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ProjectRuntimeApi<Block>,
+{
+	fn project(&self, project: ProjectID, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Option<Project<Balance, BlockNumber, AccountId>>>
+	{
+		self.client.runtime_api().project(&block_id(&*self.client, at), project).map_err(runtime_error)
+	}
+
+	fn projects(&self, state: Option<ProjectState>, start: ProjectID, limit: u32,
+		at: Option<<Block as BlockT>::Hash>)
+		-> Result<(Vec<Project<Balance, BlockNumber, AccountId>>, Option<ProjectID>)>
+	{
+		self.client.runtime_api().projects(&block_id(&*self.client, at), state, start, limit)
+			.map_err(runtime_error)
+	}
+
+	fn worker_projects(&self, identity: AccountId, start: u32, limit: u32, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<Project<Balance, BlockNumber, AccountId>>>
+	{
+		self.client.runtime_api().worker_projects(&block_id(&*self.client, at), identity, start, limit)
+			.map_err(runtime_error)
+	}
+
+	fn workers(&self, project: ProjectID, start: u32, limit: u32, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<Worker<Balance, BlockNumber, AccountId>>>
+	{
+		self.client.runtime_api().workers(&block_id(&*self.client, at), project, start, limit)
+			.map_err(runtime_error)
+	}
+}
@@ -0,0 +1,135 @@
+//! `superorganism_*` RPC methods for the proposal cycle.
+
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use node_superorganism_runtime::{
+	api::ProposalApi as ProposalRuntimeApi, opaque::Block, AccountId, Balance, BlockNumber, Moment,
+	pallet_project::types::ProjectID,
+	pallet_proposal_types::{CategoryId, Concern, Phase, Proposal, ProposalWinner, RoundIndex, RoundSummary, VoteBudget},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use super::{block_id, query_error, runtime_error};
+
+/// Queries for the proposal cycle
+#[rpc]
+pub trait ProposalApi<BlockHash> {
+	/// Get the current state, round and (estimated) time of the next state transit
+	#[rpc(name = "superorganism_phase")]
+	fn phase(&self, at: Option<BlockHash>) -> Result<Phase<BlockNumber, Moment>>;
+
+	/// Get up to limit proposals of the current round (including votes), skipping the first start proposals.
+	/// Fails for other rounds, proposals of earlier rounds can be queried at a block within that round.
+	#[rpc(name = "superorganism_proposals")]
+	fn proposals(&self, round: RoundIndex, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<(AccountId, Proposal<Balance, BlockNumber>)>>;
+
+	/// Get up to limit proposals of the current round in a category, skipping the first start proposals
+	#[rpc(name = "superorganism_proposalsByCategory")]
+	fn proposals_by_category(&self, category: CategoryId, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<(AccountId, Proposal<Balance, BlockNumber>)>>;
+
+	/// Get up to limit concerns of the current round (including votes), skipping the first start concerns.
+	/// Fails for other rounds, concerns of earlier rounds can be queried at a block within that round.
+	#[rpc(name = "superorganism_concerns")]
+	fn concerns(&self, round: RoundIndex, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<(AccountId, Concern)>>;
+
+	/// Get up to limit winners of a round, skipping the first start winners
+	#[rpc(name = "superorganism_winners")]
+	fn winners(&self, round: RoundIndex, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<ProposalWinner<AccountId, Balance, BlockNumber>>>;
+
+	/// Get up to limit winners of a round in a category, skipping the first start winners
+	#[rpc(name = "superorganism_winnersByCategory")]
+	fn winners_by_category(&self, round: RoundIndex, category: CategoryId, start: u32, limit: u32, at: Option<BlockHash>)
+		-> Result<Vec<ProposalWinner<AccountId, Balance, BlockNumber>>>;
+
+	/// Get the summary of a round. The summary of the current round is incomplete.
+	#[rpc(name = "superorganism_roundSummary")]
+	fn round_summary(&self, round: RoundIndex, at: Option<BlockHash>)
+		-> Result<Option<RoundSummary<AccountId, Balance, BlockNumber, ProjectID>>>;
+
+	/// Get all proposal categories (id, name)
+	#[rpc(name = "superorganism_categories")]
+	fn categories(&self, at: Option<BlockHash>) -> Result<Vec<(CategoryId, Vec<u8>)>>;
+
+	/// Get the number of votes an identity can still submit in the current round
+	#[rpc(name = "superorganism_voteBudget")]
+	fn vote_budget(&self, identity: AccountId, at: Option<BlockHash>) -> Result<VoteBudget>;
+}
+
+/// Implements the proposal cycle RPC methods
+pub struct Proposals<C> {
+	client: Arc<C>,
+}
+
+impl<C> Proposals<C> {
+	/// Create new `Proposals` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Proposals { client }
+	}
+}
+
+impl<C> ProposalApi<<Block as BlockT>::Hash> for Proposals<C> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ProposalRuntimeApi<Block>,
+{
+	fn phase(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Phase<BlockNumber, Moment>> {
+		self.client.runtime_api().phase(&block_id(&*self.client, at)).map_err(runtime_error)
+	}
+
+	fn proposals(&self, round: RoundIndex, start: u32, limit: u32, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<(AccountId, Proposal<Balance, BlockNumber>)>>
+	{
+		self.client.runtime_api().proposals(&block_id(&*self.client, at), round, start, limit)
+			.map_err(runtime_error)?
+			.map_err(query_error)
+	}
+
+	fn proposals_by_category(&self, category: CategoryId, start: u32, limit: u32,
+		at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(AccountId, Proposal<Balance, BlockNumber>)>>
+	{
+		self.client.runtime_api().proposals_by_category(&block_id(&*self.client, at), category, start, limit)
+			.map_err(runtime_error)
+	}
+
+	fn concerns(&self, round: RoundIndex, start: u32, limit: u32, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<(AccountId, Concern)>>
+	{
+		self.client.runtime_api().concerns(&block_id(&*self.client, at), round, start, limit)
+			.map_err(runtime_error)?
+			.map_err(query_error)
+	}
+
+	fn winners(&self, round: RoundIndex, start: u32, limit: u32, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Vec<ProposalWinner<AccountId, Balance, BlockNumber>>>
+	{
+		self.client.runtime_api().winners(&block_id(&*self.client, at), round, start, limit).map_err(runtime_error)
+	}
+
+	fn winners_by_category(&self, round: RoundIndex, category: CategoryId, start: u32, limit: u32,
+		at: Option<<Block as BlockT>::Hash>) -> Result<Vec<ProposalWinner<AccountId, Balance, BlockNumber>>>
+	{
+		self.client.runtime_api().winners_by_category(&block_id(&*self.client, at), round, category, start, limit)
+			.map_err(runtime_error)
+	}
+
+	fn round_summary(&self, round: RoundIndex, at: Option<<Block as BlockT>::Hash>)
+		-> Result<Option<RoundSummary<AccountId, Balance, BlockNumber, ProjectID>>>
+	{
+		self.client.runtime_api().round_summary(&block_id(&*self.client, at), round).map_err(runtime_error)
+	}
+
+	fn categories(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(CategoryId, Vec<u8>)>> {
+		self.client.runtime_api().categories(&block_id(&*self.client, at)).map_err(runtime_error)
+	}
+
+	fn vote_budget(&self, identity: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<VoteBudget> {
+		self.client.runtime_api().vote_budget(&block_id(&*self.client, at), identity).map_err(runtime_error)
+	}
+}
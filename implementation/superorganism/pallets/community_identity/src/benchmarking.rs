@@ -0,0 +1,48 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet_community_identity
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	_ { }
+
+	request_peer_review {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), 1, Default::default())
+	verify {
+		assert!(<PendingReviews<T>>::contains_key(&caller));
+	}
+
+	approve_identity {
+		let caller: T::AccountId = whitelisted_caller();
+		let ticket = Module::<T>::do_request_peer_review(account("identity", 0, SEED), 1, Default::default())?;
+	}: _(RawOrigin::Signed(caller), ticket, Default::default())
+
+	reject_identity {
+		let caller: T::AccountId = whitelisted_caller();
+		let ticket = Module::<T>::do_request_peer_review(account("identity", 0, SEED), 1, Default::default())?;
+	}: _(RawOrigin::Signed(caller), ticket.clone())
+	verify {
+		assert!(!<PendingReviews<T>>::contains_key(&ticket));
+	}
+
+	// report_missing is not implemented yet and always fails, it is benchmarked once it stores
+	// the missing participants.
+}
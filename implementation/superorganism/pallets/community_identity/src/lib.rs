@@ -14,7 +14,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 use frame_support::{
-	decl_module,
+	decl_error, decl_event, decl_module, decl_storage,
 	dispatch::{DispatchError, fmt::Debug, Vec},
 	Parameter,
 	sp_runtime::traits::{AtLeast32Bit, Scale},
@@ -27,8 +27,14 @@ use frame_support::serde::{Deserialize, Serialize};
 mod mock;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 /// Public interface to PhysicalIdentity
 pub mod traits;
+/// Weights of the extrinsics
+pub mod weights;
+
+pub use weights::WeightInfo;
 
 
 pub type IdentityLevel = u8;
@@ -60,17 +66,73 @@ pub struct PhysicalIdentityData<Timestamp, AccountId, ProofData> where
 	proof: PhysicalProof<Timestamp, ProofData>,
 }
 
+/// Structure that contains the requested identity level and the date of the review
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReviewRequest<Timestamp> where
+	Timestamp: AtLeast32Bit + Parameter + Default + Debug + Copy,
+{
+	pub identity_level: IdentityLevel,
+	pub at: Timestamp,
+}
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
-	// type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
 	type Timestamp: Parameter + Default + AtLeast32Bit
 		+ Scale<Self::BlockNumber, Output = Self::Timestamp> + Copy;
+
+	/// Weight information for extrinsics in this pallet
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as CommunityIdentity {
+		/// Ticket -> Requested peer review that was not approved or rejected yet
+		pub PendingReviews get(fn pending_review): map hasher(identity)
+			Ticket<T> => Option<ReviewRequest<T::Timestamp>> = None;
+		/// Identity -> Identity level granted by the last approved peer review (not granted yet, because
+		/// peer reviews are not implemented)
+		pub IdentityLevels get(fn identity_level): map hasher(identity)
+			IdentityId<T> => Option<IdentityLevel> = None;
+		/// Number of identities that were granted an identity level
+		pub IdentityCount get(fn identity_count): u32;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where ID = IdentityId<T>,
+							ReviewTicket = Ticket<T>,
+							Timestamp = <T as Trait>::Timestamp {
+		/// A peer review was requested \[Ticket, IdentityLevel, At\]
+		PeerReviewRequested(ReviewTicket, IdentityLevel, Timestamp),
+		/// A reviewer approved an identity \[Ticket\]
+		IdentityApproved(ReviewTicket),
+		/// A reviewer rejected an identity \[Ticket\]
+		IdentityRejected(ReviewTicket),
+		/// A participant reported missing participants \[Ticket, Missing\]
+		MissingReported(ReviewTicket, Vec<ID>),
+		/// The identity level of an identity changed \[Identity, IdentityLevel\]
+		IdentityLevelChanged(ID, IdentityLevel),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The operation is not implemented yet
+		NotImplemented,
+	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
 		/// Request a peer review to gain a specific IdentityLev
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::request_peer_review()]
 		fn request_peer_review(origin, identity_level: IdentityLevel, at: T::Timestamp) {
 			let caller = ensure_signed(origin)?;
 			Self::do_request_peer_review(caller, identity_level, at)?;
@@ -78,21 +140,21 @@ decl_module! {
 		}
 
 		/// As a reviewer, approve a reviewed PhysicalIdentity by supplying a proof
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::approve_identity()]
 		pub fn approve_identity(origin, review_process: Ticket<T>, proof_data: ProofType) {
 			let _ = ensure_signed(origin)?;
 			Self::do_approve_identity(review_process, proof_data)?;
 		}
 		
 		/// As a reviewer, reject a reviewed PhysicalIdentity
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::reject_identity()]
 		pub fn reject_identity(origin, review_process: Ticket<T>) {
 			let _ = ensure_signed(origin)?;
 			Self::do_reject_identity(review_process)?;
 		}
 
 		/// As a participant, report a missing participant
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::report_missing(missing.len() as u32)]
 		pub fn report_missing(origin, review_process: Ticket<T>, missing: Vec<IdentityId<T>>) {
 			let _ = ensure_signed(origin)?;
 			Self::do_report_missing(review_process, missing)?;
@@ -101,37 +163,49 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
-	fn do_request_peer_review(user: T::AccountId, _identity_level: IdentityLevel, _at: T::Timestamp)
+	fn do_request_peer_review(user: T::AccountId, identity_level: IdentityLevel, at: T::Timestamp)
 		-> Result<T::AccountId, DispatchError>
 	{
-		// TODO implement
+		// TODO implement (select reviewers)
+		<PendingReviews<T>>::insert(&user, ReviewRequest { identity_level, at });
+		Self::deposit_event(Event::<T>::PeerReviewRequested(user.clone(), identity_level, at));
 		Ok(user)
 	}
 
 	fn do_approve_identity(_review_process: Ticket<T>, _proof_data: ProofType)
 		-> Result<(), DispatchError>
 	{
-		// TODO implement
+		// TODO implement (select reviewers, only they can approve the identity and grant the
+		// requested identity level in IdentityLevels)
 		Ok(())
 	}
 
-	fn do_reject_identity(_review_process: Ticket<T>) -> Result<(), DispatchError> {
+	fn do_reject_identity(review_process: Ticket<T>) -> Result<(), DispatchError> {
 		// TODO implement
+		<PendingReviews<T>>::remove(&review_process);
+		Self::deposit_event(Event::<T>::IdentityRejected(review_process));
 		Ok(())
 	}
 
 	fn do_report_missing(_review_process: Ticket<T>, _missing: Vec<IdentityId<T>>)
 		-> Result<(), DispatchError>
 	{
-		Ok(())
+		// TODO implement (store the missing participants and emit MissingReported)
+		Err(Error::<T>::NotImplemented.into())
 	}
 
 	fn do_get_appointments(_identity: &IdentityId<T>) -> Vec<(T::Timestamp, Vec<IdentityId<T>>)> {
 		Default::default()
 	}
 
-	fn do_get_identity_level(_identity: &IdentityId<T>) -> IdentityLevel {
-		5
+	/// Get the tickets of the peer reviews an identity requested that are still pending
+	pub fn get_pending_reviews(identity: &IdentityId<T>) -> Vec<(Ticket<T>, ReviewRequest<T::Timestamp>)> {
+		<PendingReviews<T>>::get(identity).map(|r| (identity.clone(), r)).into_iter().collect()
+	}
+
+	fn do_get_identity_level(identity: &IdentityId<T>) -> IdentityLevel {
+		// TODO: Remove the default level once peer reviews are implemented
+		<IdentityLevels<T>>::get(identity).unwrap_or(5)
 	}
 
 	fn do_get_identity_id(address: &T::AccountId) -> IdentityId<T> {
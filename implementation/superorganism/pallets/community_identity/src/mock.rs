@@ -1,13 +1,24 @@
-/*use crate::{Module, Trait};
+use crate::{Module, Trait};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
 use frame_system as system;
 
 impl_outer_origin! {
-	pub enum Origin for Test {}
+	pub enum Origin for Test where system = frame_system {}
+}
+
+mod identity {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		frame_system<T>,
+		identity<T>,
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -32,7 +43,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -50,12 +61,24 @@ impl system::Trait for Test {
 }
 
 impl Trait for Test {
-	type Event = ();
+	type Event = Event;
+	type Timestamp = u64;
+	type WeightInfo = ();
 }
 
-pub type TemplateModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Identity = Module<Test>;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
-}*/
+	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not stored in the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// The last event that was deposited by the pallet
+pub fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
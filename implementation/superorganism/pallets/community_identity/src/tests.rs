@@ -1,25 +1,93 @@
-/*
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Error, mock::*, IdentityLevels, PendingReviews, ProofType, RawEvent, ReviewRequest, traits::PeerReviewedPhysicalIdentity};
+use frame_support::{assert_noop, assert_ok, StorageMap};
 
 #[test]
-fn it_works_for_default_value() {
+fn request_peer_review_stores_request() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(Identity::request_peer_review(Origin::signed(1), 3, 42));
+		assert_eq!(Identity::pending_review(1), Some(ReviewRequest { identity_level: 3, at: 42 }));
+		assert_eq!(last_event(), Event::identity(RawEvent::PeerReviewRequested(1, 3, 42)));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn request_peer_review_replaces_pending_request() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
-		);
+		assert_ok!(Identity::request_peer_review(Origin::signed(1), 3, 42));
+		assert_ok!(Identity::request_peer_review(Origin::signed(1), 4, 43));
+		assert_eq!(Identity::get_pending_reviews(&1), vec![(1, ReviewRequest { identity_level: 4, at: 43 })]);
+	});
+}
+
+#[test]
+fn approve_identity_does_not_grant_level() {
+	new_test_ext().execute_with(|| {
+		// The ticket is the identity of the requester, reviewers are not selected yet
+		assert_ok!(Identity::request_peer_review(Origin::signed(1), 255, 42));
+		let events = System::events().len();
+		assert_ok!(Identity::approve_identity(Origin::signed(1), 1, [0u8; 32]));
+		assert_eq!(Identity::identity_level(1), None);
+		assert_eq!(Identity::identity_count(), 0);
+		assert!(PendingReviews::<Test>::contains_key(1));
+		assert_eq!(System::events().len(), events);
+		assert_eq!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_identity_level(&1), 5);
+	});
+}
+
+#[test]
+fn reject_identity_removes_request() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::request_peer_review(Origin::signed(1), 3, 42));
+		assert_ok!(Identity::reject_identity(Origin::signed(2), 1));
+		assert_eq!(Identity::pending_review(1), None);
+		assert_eq!(Identity::identity_level(1), None);
+		assert_eq!(last_event(), Event::identity(RawEvent::IdentityRejected(1)));
+	});
+}
+
+#[test]
+fn report_missing_is_not_implemented() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Identity::report_missing(Origin::signed(2), 1, vec![3, 4]), Error::<Test>::NotImplemented);
+		assert_noop!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::report_missing(1, vec![3]),
+			Error::<Test>::NotImplemented);
+	});
+}
+
+#[test]
+fn extrinsics_require_signed_origin() {
+	new_test_ext().execute_with(|| {
+		assert!(Identity::request_peer_review(Origin::none(), 3, 42).is_err());
+		assert!(Identity::approve_identity(Origin::root(), 1, [0u8; 32]).is_err());
+		assert!(Identity::reject_identity(Origin::none(), 1).is_err());
+		assert!(Identity::report_missing(Origin::root(), 1, vec![]).is_err());
+	});
+}
+
+#[test]
+fn trait_uses_default_level_until_reviewed() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_identity_level(&1), 5);
+		<IdentityLevels<Test>>::insert(1, 2);
+		assert_eq!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_identity_level(&1), 2);
+	});
+}
+
+#[test]
+fn trait_maps_addresses_to_identities() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_identity_id(&7), 7);
+		assert_eq!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_address(&7), 7);
+		assert!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_appointments(&7).is_empty());
+	});
+}
+
+#[test]
+fn trait_ticket_is_requesting_identity() {
+	new_test_ext().execute_with(|| {
+		let ticket = <Identity as PeerReviewedPhysicalIdentity<ProofType>>::request_peer_review(1, 2, 42).unwrap();
+		assert_eq!(ticket, 1);
+		assert_ok!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::approve_identity(ticket, [0u8; 32]));
+		assert_eq!(<Identity as PeerReviewedPhysicalIdentity<ProofType>>::get_identity_level(&1), 5);
 	});
 }
-*/
@@ -0,0 +1,49 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet_community_treasury
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::sp_std::vec;
+
+/// Every reward category, a split lists each of them once at most
+const CATEGORIES: [RewardCategory; 4] = [RewardCategory::ProposalVote, RewardCategory::ConcernSubmit,
+	RewardCategory::ConcernVote, RewardCategory::CouncilVote];
+const MAX_CATEGORIES: u32 = 4;
+
+benchmarks! {
+	_ { }
+
+	set_issuance_curve {
+		let origin = T::ConfigOrigin::successful_origin();
+		let curve = IssuanceCurve::Decaying { initial: 1_000u32.into(), decay: Permill::from_percent(10) };
+	}: { Module::<T>::set_issuance_curve(origin, curve.clone())? }
+	verify {
+		assert_eq!(<Curve<T>>::get(), curve);
+	}
+
+	// The validation compares every category with the preceding ones
+	set_reward_split {
+		let s in 0 .. MAX_CATEGORIES;
+		let split: Vec<(RewardCategory, Permill)> = CATEGORIES.iter()
+			.take(s as usize)
+			.map(|c| (*c, Permill::from_percent(25)))
+			.collect();
+		let origin = T::ConfigOrigin::successful_origin();
+	}: { Module::<T>::set_reward_split(origin, split.clone())? }
+	verify {
+		assert_eq!(<RewardSplit>::get(), split);
+	}
+}
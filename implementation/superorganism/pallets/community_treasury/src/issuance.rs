@@ -0,0 +1,69 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::fmt::Debug,
+	sp_runtime::{Permill, PerThing, traits::{AtLeast32BitUnsigned, Saturating}},
+};
+#[cfg(feature = "std")]
+use frame_support::serde::{Deserialize, Serialize};
+
+/// Index of an issuance era
+pub type EraIndex = u32;
+
+/// Participation that is rewarded from the era budget
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RewardCategory {
+	/// Votes for proposals that passed
+	ProposalVote,
+	/// Concerns that passed
+	ConcernSubmit,
+	/// Votes for concerns that passed
+	ConcernVote,
+	/// Votes of council members in the council polls on proposals
+	CouncilVote,
+}
+
+/// Curve that determines how many funds are minted every era. Chosen by governance.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum IssuanceCurve<Balance> {
+	/// Mint the same amount every era
+	Fixed(Balance),
+	/// Mint initial in the first era and reduce the amount by decay every following era
+	Decaying { initial: Balance, decay: Permill },
+	/// Mint the amount for every active identity
+	PerIdentity(Balance),
+}
+
+impl<Balance: Default> Default for IssuanceCurve<Balance> {
+	fn default() -> Self {
+		IssuanceCurve::Fixed(Default::default())
+	}
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> IssuanceCurve<Balance> {
+	/// Budget of an era. era counts the eras since the curve took effect (starting at 0).
+	pub fn era_budget(&self, era: EraIndex, active_identities: u32) -> Balance {
+		match self {
+			IssuanceCurve::Fixed(amount) => *amount,
+			IssuanceCurve::Decaying { initial, decay } => {
+				(Permill::one() - *decay).saturating_pow(era as usize) * *initial
+			},
+			IssuanceCurve::PerIdentity(amount) => amount.saturating_mul(active_identities.into()),
+		}
+	}
+}
@@ -0,0 +1,342 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # pallet-community_treasury
+//! Holds the community funds in a pallet owned account. The treasury is funded by
+//! transaction fees and slashes (as `OnUnbalanced` handler) and by the issuance.
+//! Payouts the treasury cannot cover are queued and paid first in, first out.
+//!
+//! ## Issuance
+//! Every era a budget is minted into the treasury. Its amount follows the `IssuanceCurve`
+//! chosen by governance. The budget is split across the reward categories according to
+//! `RewardSplit` and every category budget is shared evenly among its participants.
+//! Eras either start every `EraDuration` blocks or, if it is zero, whenever the pallet that
+//! distributes the rewards calls `Treasury::new_era` (e.g. when a new proposal round starts).
+
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure,
+	dispatch::DispatchResult,
+	traits::{Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get, Imbalance, OnUnbalanced},
+	sp_runtime::{ModuleId, Permill, PerThing, traits::{AccountIdConversion, Saturating, Zero}},
+	sp_std::prelude::*,
+	weights::Weight,
+};
+use crate::{issuance::{EraIndex, IssuanceCurve, RewardCategory}, traits::Treasury};
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+/// Issuance curves and reward categories
+pub mod issuance;
+/// Public interface to the treasury
+pub mod traits;
+/// Weights of the extrinsics
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: frame_system::Trait {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Type that manages balances
+	type Currency: Currency<Self::AccountId>;
+
+	// Parameters
+	/// Id the treasury account is derived from
+	type ModuleId: Get<ModuleId>;
+
+	/// How long is an era? Every era a budget is minted into the treasury. Value in blocks.
+	/// Zero disables the timer, eras are started by Treasury::new_era then.
+	type EraDuration: Get<Self::BlockNumber>;
+
+	/// How many identities are currently active? Used by IssuanceCurve::PerIdentity.
+	type ActiveIdentities: Get<u32>;
+
+	/// How many queued payouts are paid per block at most? (required for weight calculation)
+	type MaxPayoutsPerBlock: Get<u32>;
+
+	/// Origin that can change the issuance curve and the reward split
+	type ConfigOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Weight information for extrinsics in this pallet
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as CommunityTreasury {
+		/// Queue index -> Payout the treasury could not cover yet (first in, first out)
+		pub PendingPayouts get(fn pending_payout): map hasher(twox_64_concat)
+			u32 => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Queue index of the oldest pending payout
+		pub PayoutHead get(fn payout_head): u32;
+		/// Queue index of the next queued payout. The queue is empty if it equals PayoutHead.
+		pub PayoutTail get(fn payout_tail): u32;
+		/// Sum of all pending payouts
+		pub PendingTotal get(fn pending_total): BalanceOf<T>;
+		/// Curve that determines the budget of every era
+		pub Curve get(fn issuance_curve) config(issuance_curve): IssuanceCurve<BalanceOf<T>>;
+		/// First era that uses the current curve
+		pub CurveStart get(fn curve_start) build(|_| 1): EraIndex;
+		/// Current era
+		pub Era get(fn era): EraIndex;
+		/// Share of the era budget every reward category receives
+		pub RewardSplit get(fn reward_split) config(): Vec<(RewardCategory, Permill)>;
+		/// Reward category -> Budget of the current era that was not shared yet
+		pub RewardBudgets get(fn reward_budget): map hasher(twox_64_concat) RewardCategory => BalanceOf<T>;
+	}
+	add_extra_genesis {
+		build(|config: &GenesisConfig<T>| {
+			assert!(<Module<T>>::is_valid_split(&config.reward_split), "Invalid reward split");
+
+			// Create the treasury account
+			let account = <Module<T>>::account_id();
+
+			if T::Currency::free_balance(&account).is_zero() {
+				let _ = T::Currency::make_free_balance_be(&account, T::Currency::minimum_balance());
+			}
+		});
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId,
+							Balance = BalanceOf<T> {
+		/// The treasury received funds (fees, slashes) \[Amount\]
+		Deposit(Balance),
+		/// A new era started and its budget was minted into the treasury \[Era, Amount\]
+		Minted(EraIndex, Balance),
+		/// Governance chose a new issuance curve \[Curve\]
+		IssuanceCurveSet(IssuanceCurve<Balance>),
+		/// Governance changed how the era budget is split \[Split\]
+		RewardSplitSet(Vec<(RewardCategory, Permill)>),
+		/// The budget of a reward category was shared \[Category, Participants, RewardPerParticipant\]
+		RewardShared(RewardCategory, u32, Balance),
+		/// The treasury paid an account \[Recipient, Amount\]
+		Paid(AccountId, Balance),
+		/// The treasury could not cover a payout and queued it \[Recipient, Amount\]
+		PayoutQueued(AccountId, Balance),
+		/// A queued payout could not be transferred (e.g. below the existential deposit) and was
+		/// dropped, the amount stays in the treasury \[Recipient, Amount\]
+		PayoutFailed(AccountId, Balance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The shares exceed 100% or a reward category is listed twice
+		InvalidRewardSplit,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Id the treasury account is derived from
+		const ModuleId: ModuleId = T::ModuleId::get();
+
+		/// How long is an era? Every era a budget is minted into the treasury. Value in blocks.
+		/// Zero disables the timer, eras are started by Treasury::new_era then.
+		const EraDuration: T::BlockNumber = T::EraDuration::get();
+
+		/// How many queued payouts are paid per block at most? (required for weight calculation)
+		const MaxPayoutsPerBlock: u32 = T::MaxPayoutsPerBlock::get();
+
+		/// As ConfigOrigin, choose the curve that determines the budget of the following eras
+		#[weight = T::WeightInfo::set_issuance_curve()]
+		fn set_issuance_curve(origin, curve: IssuanceCurve<BalanceOf<T>>) {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			<Curve<T>>::put(&curve);
+			<CurveStart>::put(<Era>::get().saturating_add(1));
+			Self::deposit_event(RawEvent::IssuanceCurveSet(curve));
+		}
+
+		/// As ConfigOrigin, set how the budget of the following eras is split across the reward categories
+		#[weight = T::WeightInfo::set_reward_split(split.len() as u32)]
+		fn set_reward_split(origin, split: Vec<(RewardCategory, Permill)>) {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_valid_split(&split), Error::<T>::InvalidRewardSplit);
+			<RewardSplit>::put(&split);
+			Self::deposit_event(RawEvent::RewardSplitSet(split));
+		}
+
+		/// Start a new era every EraDuration blocks and pay queued payouts
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight: Weight = T::DbWeight::get().reads(1);
+			let duration = T::EraDuration::get();
+
+			if !duration.is_zero() && (now % duration).is_zero() {
+				weight = weight.saturating_add(Self::start_era());
+			}
+
+			weight.saturating_add(Self::pay_pending())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Mint the budget of the new era and split it across the reward categories.
+	/// Category budgets of the previous era that were not shared stay in the treasury.
+	fn start_era() -> Weight {
+		let era: EraIndex = <Era>::mutate(|era| { *era = era.saturating_add(1); *era });
+		let since: EraIndex = era.saturating_sub(<CurveStart>::get());
+		let budget: BalanceOf<T> = <Curve<T>>::get().era_budget(since, T::ActiveIdentities::get());
+		let split = <RewardSplit>::get();
+
+		// Avoid collecting the iterator to avoid creating a new Vector
+		<RewardBudgets<T>>::drain().nth(usize::MAX);
+
+		for (category, share) in split.iter() {
+			<RewardBudgets<T>>::insert(category, *share * budget);
+		}
+
+		if !budget.is_zero() {
+			let _ = T::Currency::deposit_creating(&Self::account_id(), budget);
+		}
+
+		Self::deposit_event(RawEvent::Minted(era, budget));
+		T::DbWeight::get().reads_writes(5, 3 + 2 * split.len() as u64)
+	}
+
+	/// Shares must not exceed 100% and every category must be listed once at most
+	fn is_valid_split(split: &[(RewardCategory, Permill)]) -> bool {
+		let mut total: u32 = 0;
+
+		for (i, (category, share)) in split.iter().enumerate() {
+			if split[..i].iter().any(|(c, _)| c == category) {
+				return false;
+			}
+
+			total = total.saturating_add(share.deconstruct());
+		}
+
+		total <= Permill::one().deconstruct()
+	}
+
+	/// Pay queued payouts in order as long as the treasury can cover them
+	fn pay_pending() -> Weight {
+		let tail: u32 = <PayoutTail>::get();
+		let mut head: u32 = <PayoutHead>::get();
+		let mut paid: u64 = 0;
+
+		while head != tail && paid < T::MaxPayoutsPerBlock::get().into() {
+			if let Some((to, amount)) = <PendingPayouts<T>>::get(head) {
+				if amount > Self::available() {
+					break;
+				}
+
+				// A failed payout is dropped instead of blocking the queue
+				if T::Currency::transfer(&Self::account_id(), &to, amount, KeepAlive).is_ok() {
+					Self::deposit_event(RawEvent::Paid(to, amount));
+				} else {
+					Self::deposit_event(RawEvent::PayoutFailed(to, amount));
+				}
+
+				<PendingPayouts<T>>::remove(head);
+				<PendingTotal<T>>::mutate(|total| *total = total.saturating_sub(amount));
+			}
+
+			head = head.wrapping_add(1);
+			paid += 1;
+		}
+
+		if paid > 0 {
+			<PayoutHead>::put(head);
+		}
+
+		T::DbWeight::get().reads_writes(2 + 2 * paid, 3 * paid)
+	}
+
+	/// Pending payouts in the order they are paid
+	pub fn pending_payouts() -> Vec<(T::AccountId, BalanceOf<T>)> {
+		let tail: u32 = <PayoutTail>::get();
+		let mut head: u32 = <PayoutHead>::get();
+		let mut pending = Vec::new();
+
+		while head != tail {
+			pending.extend(<PendingPayouts<T>>::get(head));
+			head = head.wrapping_add(1);
+		}
+
+		pending
+	}
+}
+
+impl<T: Trait> Treasury for Module<T> {
+	type AccountId = T::AccountId;
+	type Balance = BalanceOf<T>;
+
+	fn account_id() -> Self::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	fn available() -> Self::Balance {
+		// Keep the treasury account alive
+		T::Currency::free_balance(&Self::account_id()).saturating_sub(T::Currency::minimum_balance())
+	}
+
+	fn pay(to: &Self::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let tail: u32 = <PayoutTail>::get();
+
+		// Queued payouts are paid first
+		if <PayoutHead>::get() == tail && amount <= Self::available() {
+			T::Currency::transfer(&Self::account_id(), to, amount, KeepAlive)?;
+			Self::deposit_event(RawEvent::Paid(to.clone(), amount));
+		} else {
+			<PendingPayouts<T>>::insert(tail, (to.clone(), amount));
+			<PayoutTail>::put(tail.wrapping_add(1));
+			<PendingTotal<T>>::mutate(|total| *total = total.saturating_add(amount));
+			Self::deposit_event(RawEvent::PayoutQueued(to.clone(), amount));
+		}
+
+		Ok(())
+	}
+
+	fn new_era() {
+		Self::start_era();
+	}
+
+	fn reward_share(category: RewardCategory, participants: u32) -> Self::Balance {
+		if participants == 0 {
+			return Zero::zero();
+		}
+
+		let share: BalanceOf<T> = <RewardBudgets<T>>::take(category) / participants.into();
+		Self::deposit_event(RawEvent::RewardShared(category, participants, share));
+		share
+	}
+}
+
+/// Transaction fees and slashes are transferred into the treasury
+impl<T: Trait> OnUnbalanced<NegativeImbalanceOf<T>> for Module<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let numeric_amount = amount.peek();
+		T::Currency::resolve_creating(&Self::account_id(), amount);
+		Self::deposit_event(RawEvent::Deposit(numeric_amount));
+	}
+}
@@ -0,0 +1,131 @@
+use crate::{Module, Trait, issuance::{IssuanceCurve, RewardCategory}};
+use sp_core::H256;
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, traits::OnInitialize, weights::Weight};
+use sp_runtime::{
+	ModuleId, traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill, Permill,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+mod community_treasury {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		frame_system<T>,
+		pallet_balances<T>,
+		community_treasury<T>,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const EraDuration: u64 = 10;
+	pub const ActiveIdentities: u32 = 4;
+	pub const MaxPayoutsPerBlock: u32 = 2;
+}
+
+impl Trait for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type ModuleId = TreasuryModuleId;
+	type EraDuration = EraDuration;
+	type ActiveIdentities = ActiveIdentities;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type ConfigOrigin = system::EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type CommunityTreasury = Module<Test>;
+
+/// Budget minted into the treasury every era
+pub const ERA_BUDGET: u64 = 1_000;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		issuance_curve: IssuanceCurve::Fixed(ERA_BUDGET),
+		reward_split: vec![
+			(RewardCategory::ProposalVote, Permill::from_percent(50)),
+			(RewardCategory::ConcernSubmit, Permill::from_percent(20)),
+			(RewardCategory::ConcernVote, Permill::from_percent(20)),
+		],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not stored in the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Initialize the blocks up to (including) block n
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		CommunityTreasury::on_initialize(System::block_number());
+	}
+}
+
+/// Was the event deposited (in any block)?
+pub fn has_event(event: crate::Event<Test>) -> bool {
+	System::events().iter().any(|r| r.event == Event::community_treasury(event.clone()))
+}
@@ -0,0 +1,211 @@
+use crate::{Error, mock::*, RawEvent, issuance::{IssuanceCurve, RewardCategory}, traits::Treasury};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError,
+	traits::{Currency, LockableCurrency, OnUnbalanced, WithdrawReasons}};
+use sp_runtime::Permill;
+
+/// Transfer amount into the treasury like a transaction fee
+fn deposit(amount: u64) {
+	CommunityTreasury::on_unbalanced(Balances::issue(amount));
+}
+
+#[test]
+fn genesis_creates_treasury_account() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(CommunityTreasury::account_id()), 1);
+		assert_eq!(CommunityTreasury::available(), 0);
+	});
+}
+
+#[test]
+fn fees_are_deposited() {
+	new_test_ext().execute_with(|| {
+		deposit(500);
+		assert!(has_event(RawEvent::Deposit(500)));
+		assert_eq!(CommunityTreasury::available(), 500);
+	});
+}
+
+#[test]
+fn era_mints_budget_and_splits_it() {
+	new_test_ext().execute_with(|| {
+		run_to_block(9);
+		assert_eq!(CommunityTreasury::era(), 0);
+		assert_eq!(CommunityTreasury::available(), 0);
+
+		run_to_block(10);
+		assert_eq!(CommunityTreasury::era(), 1);
+		assert!(has_event(RawEvent::Minted(1, ERA_BUDGET)));
+		assert_eq!(CommunityTreasury::available(), ERA_BUDGET);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ProposalVote), 500);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ConcernSubmit), 200);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ConcernVote), 200);
+	});
+}
+
+#[test]
+fn unspent_budgets_are_drained_in_next_era() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		assert_eq!(CommunityTreasury::reward_share(RewardCategory::ProposalVote, 2), 250);
+
+		// The budgets are reset instead of accumulated, the unspent funds stay in the treasury
+		run_to_block(20);
+		assert!(has_event(RawEvent::Minted(2, ERA_BUDGET)));
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ProposalVote), 500);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ConcernSubmit), 200);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ConcernVote), 200);
+		assert_eq!(CommunityTreasury::available(), 2 * ERA_BUDGET);
+	});
+}
+
+#[test]
+fn reward_share_splits_category_budget() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		assert_eq!(CommunityTreasury::reward_share(RewardCategory::ConcernVote, 0), 0);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ConcernVote), 200);
+
+		assert_eq!(CommunityTreasury::reward_share(RewardCategory::ConcernVote, 3), 66);
+		assert!(has_event(RawEvent::RewardShared(RewardCategory::ConcernVote, 3, 66)));
+		// Sharing does not transfer funds
+		assert_eq!(CommunityTreasury::available(), ERA_BUDGET);
+	});
+}
+
+#[test]
+fn decaying_curve_starts_with_initial_budget() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		let curve = IssuanceCurve::Decaying { initial: ERA_BUDGET, decay: Permill::from_percent(10) };
+		assert_ok!(CommunityTreasury::set_issuance_curve(Origin::root(), curve));
+
+		run_to_block(20);
+		assert!(has_event(RawEvent::Minted(2, ERA_BUDGET)));
+		run_to_block(30);
+		assert!(has_event(RawEvent::Minted(3, 900)));
+		assert_eq!(CommunityTreasury::available(), 2 * ERA_BUDGET + 900);
+	});
+}
+
+#[test]
+fn new_era_mints_budget() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		assert_eq!(CommunityTreasury::reward_share(RewardCategory::ProposalVote, 1), 500);
+		assert_eq!(CommunityTreasury::reward_share(RewardCategory::ProposalVote, 1), 0);
+
+		// Started by the pallet that distributes the rewards, e.g. with a new proposal round
+		CommunityTreasury::new_era();
+		assert_eq!(CommunityTreasury::era(), 2);
+		assert!(has_event(RawEvent::Minted(2, ERA_BUDGET)));
+		assert_eq!(CommunityTreasury::reward_share(RewardCategory::ProposalVote, 1), 500);
+	});
+}
+
+#[test]
+fn per_identity_curve_scales_with_identities() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(CommunityTreasury::set_issuance_curve(Origin::signed(1), IssuanceCurve::PerIdentity(100)),
+			DispatchError::BadOrigin);
+		assert_ok!(CommunityTreasury::set_issuance_curve(Origin::root(), IssuanceCurve::PerIdentity(100)));
+		assert!(has_event(RawEvent::IssuanceCurveSet(IssuanceCurve::PerIdentity(100))));
+
+		run_to_block(10);
+		assert!(has_event(RawEvent::Minted(1, 400)));
+	});
+}
+
+#[test]
+fn set_reward_split_applies_in_next_era() {
+	new_test_ext().execute_with(|| {
+		let split = vec![(RewardCategory::ProposalVote, Permill::from_percent(100))];
+		assert_noop!(CommunityTreasury::set_reward_split(Origin::signed(1), split.clone()), DispatchError::BadOrigin);
+		assert_ok!(CommunityTreasury::set_reward_split(Origin::root(), split.clone()));
+		assert!(has_event(RawEvent::RewardSplitSet(split)));
+
+		run_to_block(10);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ProposalVote), ERA_BUDGET);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ConcernVote), 0);
+	});
+}
+
+#[test]
+fn error_invalid_reward_split() {
+	new_test_ext().execute_with(|| {
+		let exceeding = vec![
+			(RewardCategory::ProposalVote, Permill::from_percent(60)),
+			(RewardCategory::ConcernVote, Permill::from_percent(50)),
+		];
+		assert_noop!(CommunityTreasury::set_reward_split(Origin::root(), exceeding), Error::<Test>::InvalidRewardSplit);
+		let duplicate = vec![
+			(RewardCategory::ProposalVote, Permill::from_percent(10)),
+			(RewardCategory::ProposalVote, Permill::from_percent(10)),
+		];
+		assert_noop!(CommunityTreasury::set_reward_split(Origin::root(), duplicate), Error::<Test>::InvalidRewardSplit);
+	});
+}
+
+#[test]
+fn pay_covered_payout_immediately() {
+	new_test_ext().execute_with(|| {
+		deposit(500);
+		assert_ok!(CommunityTreasury::pay(&7, 200));
+		assert!(has_event(RawEvent::Paid(7, 200)));
+		assert_eq!(Balances::free_balance(7), 200);
+		assert_eq!(CommunityTreasury::available(), 300);
+		assert!(CommunityTreasury::pending_payouts().is_empty());
+	});
+}
+
+#[test]
+fn pending_payouts_are_paid_first_in_first_out() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CommunityTreasury::pay(&7, 800));
+		assert!(has_event(RawEvent::PayoutQueued(7, 800)));
+		assert_ok!(CommunityTreasury::pay(&8, 100));
+
+		// Covered payouts are queued as well while older payouts are pending
+		deposit(500);
+		assert_ok!(CommunityTreasury::pay(&9, 50));
+		assert!(has_event(RawEvent::PayoutQueued(9, 50)));
+		assert_eq!(CommunityTreasury::pending_total(), 950);
+
+		// The oldest payout blocks the queue until it can be covered
+		run_to_block(2);
+		assert_eq!(Balances::free_balance(8), 0);
+		assert_eq!(CommunityTreasury::pending_payouts().len(), 3);
+
+		// The era budget covers all payouts, but only MaxPayoutsPerBlock are paid per block
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(7), 800);
+		assert_eq!(Balances::free_balance(8), 100);
+		assert_eq!(Balances::free_balance(9), 0);
+		assert_eq!(CommunityTreasury::pending_payouts(), vec![(9, 50)]);
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(9), 50);
+		assert!(CommunityTreasury::pending_payouts().is_empty());
+		assert_eq!((CommunityTreasury::payout_head(), CommunityTreasury::payout_tail()), (3, 3));
+		assert_eq!(CommunityTreasury::pending_total(), 0);
+		assert_eq!(CommunityTreasury::available(), 500 + ERA_BUDGET - 950);
+	});
+}
+
+#[test]
+fn failed_pending_payout_is_reported() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CommunityTreasury::pay(&7, 800));
+		assert_ok!(CommunityTreasury::pay(&8, 100));
+		deposit(1_000);
+		// The funds are covered, but locked
+		Balances::set_lock(*b"testlock", &CommunityTreasury::account_id(), 1_000, WithdrawReasons::all());
+
+		run_to_block(2);
+		assert!(has_event(RawEvent::PayoutFailed(7, 800)));
+		assert!(has_event(RawEvent::PayoutFailed(8, 100)));
+		assert_eq!(Balances::free_balance(7), 0);
+		assert!(CommunityTreasury::pending_payouts().is_empty());
+		assert_eq!(CommunityTreasury::pending_total(), 0);
+		assert_eq!(CommunityTreasury::available(), 1_000);
+	});
+}
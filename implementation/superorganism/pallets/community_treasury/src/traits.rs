@@ -0,0 +1,37 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::dispatch::DispatchResult;
+use crate::issuance::RewardCategory;
+
+/// Public interface of the community treasury.
+/// Other pallets pay rewards, salaries and budgets with it instead of minting new funds.
+pub trait Treasury {
+	type AccountId;
+	type Balance;
+
+	/// Account that holds the funds of the treasury
+	fn account_id() -> Self::AccountId;
+	/// Funds the treasury can spend right now
+	fn available() -> Self::Balance;
+	/// Pay amount to an account. If the treasury cannot cover it, the payout is queued
+	/// and paid as soon as the treasury received enough funds.
+	fn pay(to: &Self::AccountId, amount: Self::Balance) -> DispatchResult;
+	/// Start a new era: Mint its budget and refill the reward budgets
+	fn new_era();
+	/// Take the budget of a reward category for the current era and return the share of
+	/// every participant. The remainder stays in the treasury. Every category is shared once
+	/// per era, later calls in the same era return zero.
+	fn reward_share(category: RewardCategory, participants: u32) -> Self::Balance;
+}
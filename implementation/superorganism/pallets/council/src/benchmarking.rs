@@ -0,0 +1,104 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet_council
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{sp_std::vec, traits::OnInitialize};
+use frame_system::RawOrigin;
+
+const MAX_DOCUMENTS: u32 = 100;
+const MAX_MEMBERS: u32 = 100;
+const MAX_POLLS: u32 = 100;
+const SEED: u32 = 0;
+
+/// Document CID of the length of a CIDv1 (sha2-256, base32)
+fn document(i: u32) -> DocumentCID {
+	let mut cid = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd".to_vec();
+	cid.extend_from_slice(&i.to_le_bytes());
+	cid
+}
+
+/// Set m council members, the caller becomes the last member
+fn set_members<T: Trait>(m: u32, caller: &T::AccountId) {
+	let mut members: Vec<IdentityId<T>> = (1..m).map(|i| account("member", i, SEED)).collect();
+	members.push(T::Identity::get_identity_id(caller));
+	<Members<T>>::put(members);
+}
+
+/// Add a poll that closes at until and let voters council members vote for it
+fn add_voted_poll<T: Trait>(until: BlockNumber<T>, voters: u32) -> Result<Ticket, &'static str> {
+	let ticket = Module::<T>::do_add_poll(vec![document(0)], until)?;
+
+	for i in 1..=voters {
+		Module::<T>::do_vote_poll(account("member", i, SEED), ticket, i % 2 == 0)?;
+	}
+
+	Ok(ticket)
+}
+
+benchmarks! {
+	_ { }
+
+	// vote_council_member, vote_reelect_council and vote_reelect are not implemented yet and
+	// always fail, they are benchmarked once they store the votes.
+
+	add_poll {
+		let d in 1 .. MAX_DOCUMENTS;
+		let documents: Vec<DocumentCID> = (0..d).map(document).collect();
+		let until = frame_system::Module::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Root, documents, until)
+	verify {
+		assert!(<Polls<T>>::contains_key(0));
+	}
+
+	propose_motion {
+		let caller: T::AccountId = whitelisted_caller();
+		set_members::<T>(1, &caller);
+		let call: <T as Trait>::Proposal = frame_system::Call::<T>::remark(vec![0; 32]).into();
+		let until = frame_system::Module::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller), Box::new(call), until)
+	verify {
+		assert!(<Motions<T>>::contains_key(0));
+	}
+
+	// Worst case: Every other council member already voted
+	vote_poll {
+		let m in 2 .. MAX_MEMBERS;
+		let caller: T::AccountId = whitelisted_caller();
+		set_members::<T>(m, &caller);
+		let ticket = add_voted_poll::<T>(frame_system::Module::<T>::block_number() + 10u32.into(), m - 1)?;
+	}: _(RawOrigin::Signed(caller), ticket, true)
+	verify {
+		assert_eq!(<Polls<T>>::get(ticket).map(|p| p.votes.len() as u32), Some(m));
+	}
+
+	on_initialize {
+		let t in 0 .. MAX_POLLS;
+		let m in 1 .. MAX_MEMBERS;
+		let caller: T::AccountId = whitelisted_caller();
+		set_members::<T>(m + 1, &caller);
+		let until = frame_system::Module::<T>::block_number() + 10u32.into();
+
+		for _ in 0..t {
+			add_voted_poll::<T>(until, m)?;
+		}
+	}: {
+		Module::<T>::on_initialize(until);
+	}
+	verify {
+		assert!(!<ClosingPolls<T>>::contains_key(until));
+	}
+}
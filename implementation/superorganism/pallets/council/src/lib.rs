@@ -13,68 +13,246 @@
 // limitations under the License.
 
 #![cfg_attr(not(feature = "std"), no_std)]
-use frame_support::{decl_module, decl_storage, dispatch::{DispatchError, Vec}};
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, IterableStorageMap, Parameter,
+	RuntimeDebug, dispatch::{DispatchError, DispatchResult, Dispatchable, fmt::Debug, Vec},
+	sp_runtime::traits::Hash, traits::{EnsureOrigin, Get}, weights::{GetDispatchInfo, Weight}};
+use frame_support::sp_std::{boxed::Box, vec};
 use frame_system::{ensure_signed, ensure_root};
+use codec::{Codec, Decode, Encode, EncodeLike};
+#[cfg(feature = "std")]
+use frame_support::serde::{Deserialize, Serialize};
 use pallet_community_identity::{ProofType, IdentityId, IdentityLevel, traits::PeerReviewedPhysicalIdentity};
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+/// Storage migrations
+pub mod migrations;
 /// Public interface to Council
 pub mod traits;
+/// Weights of the extrinsics and hooks
+pub mod weights;
+use migrations::Releases;
+
+pub use weights::WeightInfo;
 
 pub type Ticket = u64;
 pub type BlockNumber<T> = <T as frame_system::Trait>::BlockNumber;
 // TODO: Change from Vec<u8> to fixed length type
 pub type DocumentCID = Vec<u8>;
 
+/// Structure that contains the documents, the deadline and the votes of a poll
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Poll<IdentityId, BlockNumber> where
+	IdentityId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub ticket: Ticket,
+	pub documents: Vec<DocumentCID>,
+	/// Council members can vote until (excluding) this block
+	pub until: BlockNumber,
+	pub votes: Vec<(IdentityId, bool)>,
+}
+
+impl<IdentityId, BlockNumber> Poll<IdentityId, BlockNumber> where
+	IdentityId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub fn new(ticket: Ticket, documents: Vec<DocumentCID>, until: BlockNumber) -> Self {
+		Poll{ticket, documents, until, votes: Vec::new()}
+	}
+
+	/// Count the votes
+	pub fn tally(self) -> PollTally<IdentityId, BlockNumber> {
+		let accept = self.votes.iter().filter(|v| v.1).count() as u32;
+		let deny = self.votes.len() as u32 - accept;
+		PollTally{poll: self, accept, deny}
+	}
+}
+
+/// Structure that contains a poll and the number of accepting and denying votes
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PollTally<IdentityId, BlockNumber> where
+	IdentityId: Codec + Clone + Debug + EncodeLike + Eq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub poll: Poll<IdentityId, BlockNumber>,
+	pub accept: u32,
+	pub deny: u32,
+}
+
+/// Origin of the calls that the council dispatches
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub enum RawOrigin {
+	/// The council accepted the motion of the poll \[Ticket\]
+	AcceptedPoll(Ticket),
+}
+
+/// Origin of the calls that the council dispatches
+pub type Origin = RawOrigin;
+
+/// Ensure that the council accepted the call in a poll, yields the ticket of the poll
+pub struct EnsureCouncil;
+
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> EnsureOrigin<O> for EnsureCouncil {
+	type Success = Ticket;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|o| match o {
+			RawOrigin::AcceptedPoll(ticket) => ticket,
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::AcceptedPoll(0))
+	}
+}
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
-	// type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The outer origin, must contain the origin of this pallet
+	type Origin: From<RawOrigin>;
+
+	/// The outer call that the council can dispatch after accepting a motion
+	type Proposal: Parameter + Dispatchable<Origin = <Self as Trait>::Origin> + GetDispatchInfo
+		+ From<frame_system::Call<Self>>;
+
 	/// Define Identity type. Must implement PeerReviewedPhysicalIdentity trait
 	type Identity: PeerReviewedPhysicalIdentity<ProofType, IdentityId = IdentityId<Self>,
 						IdentityLevel = IdentityLevel, Address = Self::AccountId>;
+
+	/// Weight information for extrinsics in this pallet
+	type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Council {
 		pub TicketNumber get(fn ticket): Ticket = 0;
+		/// Current council members
+		pub Members get(fn members) config(): Vec<IdentityId<T>>;
+		/// Ticket -> Poll
+		pub Polls get(fn poll): map hasher(identity) Ticket => Option<Poll<IdentityId<T>, BlockNumber<T>>>;
+		/// Ticket -> Call that is dispatched if the council accepts the poll
+		pub Motions get(fn motion): map hasher(identity) Ticket => Option<<T as Trait>::Proposal>;
+		/// Block -> Polls that close at the block
+		pub ClosingPolls get(fn closing_polls): map hasher(identity) BlockNumber<T> => Vec<Ticket>;
+		/// Layout of the storage, used to migrate it during runtime upgrades
+		pub StorageVersion get(fn storage_version): Releases;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where ID = IdentityId<T>,
+							BlockNumber = <T as frame_system::Trait>::BlockNumber {
+		/// An identified user voted for a council member \[Voter, Candidate\]
+		CouncilMemberVoted(ID, ID),
+		/// An identified user voted to reelect the council \[Voter\]
+		ReelectCouncilVoted(ID),
+		/// An identified user voted to reelect a council member \[Voter, Member\]
+		ReelectMemberVoted(ID, ID),
+		/// A poll was created \[Ticket, Documents, Until\]
+		PollCreated(Ticket, Vec<DocumentCID>, BlockNumber),
+		/// A council member voted for a poll \[Ticket, Member, Accept\]
+		PollVoted(Ticket, ID, bool),
+		/// A poll does not accept votes anymore \[Ticket, Accept, Deny\]
+		PollClosed(Ticket, u32, u32),
+		/// The council accepted a motion and dispatched its call \[Ticket, Result\]
+		MotionExecuted(Ticket, DispatchResult),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Council member already voted
+		AlreadyVoted,
+		/// The poll must end in the future
+		InvalidDeadline,
+		/// Identity is not a council member
+		NotACouncilMember,
+		/// The operation is not implemented yet
+		NotImplemented,
+		/// The poll does not accept votes anymore
+		PollClosed,
+		/// Poll does not exist
+		PollNotExistant,
 	}
 }
 
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait> for enum Call where origin: <T as frame_system::Trait>::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Migrate the storage to the current layout
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		/// Announce the result of the polls that close at this block
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let tickets = <ClosingPolls<T>>::take(now);
+			let mut weight = T::WeightInfo::on_initialize(tickets.len() as u32, Self::member_count());
+
+			for ticket in tickets.iter() {
+				if let Some(poll) = <Polls<T>>::get(ticket) {
+					let tally = poll.tally();
+					Self::deposit_event(Event::<T>::PollClosed(*ticket, tally.accept, tally.deny));
+
+					if let Some(call) = <Motions<T>>::take(ticket) {
+						weight = weight.saturating_add(Self::execute_motion(*ticket, call, tally.accept));
+					}
+				}
+			}
+
+			weight
+		}
+
 		/// As an identified user, vote for a council member
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::vote_council_member()]
 		fn vote_council_member(origin, candidate: IdentityId<T>) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_council_member(T::Identity::get_identity_id(&caller), candidate)?;
 		}
 
 		/// As an identified user, vote to reelect council
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::vote_reelect_council()]
 		fn vote_reelect_council(origin) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_reelect_council(T::Identity::get_identity_id(&caller))?;
 		}
 
 		/// As an identified user, vote to reelect a specific council member
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::vote_reelect()]
 		fn vote_reelect(origin, member: IdentityId<T>) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_reelect(T::Identity::get_identity_id(&caller), member)?;
 		}
 
 		/// As root, queue a poll
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::add_poll(documents.len() as u32)]
 		fn add_poll(origin, documents: Vec<DocumentCID>, until: BlockNumber<T>) {
 			ensure_root(origin)?;
 			Self::do_add_poll(documents, until)?;
 		}
 
+		/// As a council member, propose a call that is dispatched with the origin of the council
+		/// if the majority of the council members accepts it in a poll
+		#[weight = T::WeightInfo::propose_motion()]
+		fn propose_motion(origin, call: Box<<T as Trait>::Proposal>, until: BlockNumber<T>) {
+			let caller = ensure_signed(origin)?;
+			Self::do_propose_motion(T::Identity::get_identity_id(&caller), *call, until)?;
+		}
+
 		/// As a council member, vote for a poll
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::vote_poll(<Module<T>>::member_count())]
 		fn vote_poll(origin, poll: Ticket, accept: bool) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_poll(T::Identity::get_identity_id(&caller), poll, accept)?;
@@ -86,33 +264,87 @@ impl<T: Trait> Module<T> {
 	fn do_vote_council_member(_voter: IdentityId<T>, _candidate: IdentityId<T>)
 		-> Result<(), DispatchError>
 	{
-		// TODO implement
-		Ok(())
+		// TODO implement (store the vote and emit CouncilMemberVoted)
+		Err(Error::<T>::NotImplemented.into())
 	}
 
 	fn do_vote_reelect_council(_voter: IdentityId<T>) -> Result<(), DispatchError> {
-		// TODO implement
-		Ok(())
+		// TODO implement (store the vote and emit ReelectCouncilVoted)
+		Err(Error::<T>::NotImplemented.into())
 	}
 
 	fn do_vote_reelect(_voter: IdentityId<T>, _member: IdentityId<T>) -> Result<(), DispatchError> {
-		// TODO implement
-		Ok(())
+		// TODO implement (store the vote and emit ReelectMemberVoted)
+		Err(Error::<T>::NotImplemented.into())
 	}
 
-	fn do_add_poll(_document: Vec<DocumentCID>, _until: BlockNumber<T>) -> Result<Ticket, DispatchError> {
+	fn do_add_poll(documents: Vec<DocumentCID>, until: BlockNumber<T>) -> Result<Ticket, DispatchError> {
+		ensure!(until > frame_system::Module::<T>::block_number(), Error::<T>::InvalidDeadline);
 		let ticket: Ticket = <TicketNumber>::get();
+		<Polls<T>>::insert(ticket, Poll::new(ticket, documents.clone(), until));
+		<ClosingPolls<T>>::append(until, ticket);
 		TicketNumber::put(ticket + 1);
+		Self::deposit_event(Event::<T>::PollCreated(ticket, documents, until));
+		Ok(ticket)
+	}
+
+	fn do_propose_motion(member: IdentityId<T>, call: <T as Trait>::Proposal, until: BlockNumber<T>)
+		-> Result<Ticket, DispatchError>
+	{
+		ensure!(<Members<T>>::get().contains(&member), Error::<T>::NotACouncilMember);
+		let ticket = Self::do_add_poll(vec![T::Hashing::hash_of(&call).encode()], until)?;
+		<Motions<T>>::insert(ticket, call);
 		Ok(ticket)
 	}
 
-	fn do_vote_poll(_member: IdentityId<T>, _poll: Ticket, _accept: bool) -> Result<(), DispatchError> {
+	/// Dispatch the call of a closed poll if the majority of the council members accepted it
+	fn execute_motion(ticket: Ticket, call: <T as Trait>::Proposal, accept: u32) -> Weight {
+		if accept.saturating_mul(2) <= Self::member_count() {
+			return 0;
+		}
+
+		let weight = call.get_dispatch_info().weight;
+		let result = call.dispatch(RawOrigin::AcceptedPoll(ticket).into());
+		Self::deposit_event(Event::<T>::MotionExecuted(ticket, result.map(|_| ()).map_err(|e| e.error)));
+		weight
+	}
+
+	fn do_vote_poll(member: IdentityId<T>, poll: Ticket, accept: bool) -> Result<(), DispatchError> {
+		ensure!(<Members<T>>::get().contains(&member), Error::<T>::NotACouncilMember);
+		let mut p = <Polls<T>>::get(poll).ok_or(Error::<T>::PollNotExistant)?;
+		ensure!(frame_system::Module::<T>::block_number() < p.until, Error::<T>::PollClosed);
+		ensure!(!p.votes.iter().any(|v| v.0 == member), Error::<T>::AlreadyVoted);
+		p.votes.push((member.clone(), accept));
+		<Polls<T>>::insert(poll, p);
+		Self::deposit_event(Event::<T>::PollVoted(poll, member, accept));
 		Ok(())
 	}
 
-	fn do_get_result(_poll: &Ticket) -> Option<Vec<(IdentityId<T>, bool)>> {
-		Some(Vec::from([(Default::default(), true), (Default::default(), true), (Default::default(), true),
-			(Default::default(), true), (Default::default(), true), (Default::default(), true)]))
+	/// Number of council members (the upper bound of votes per poll)
+	fn member_count() -> u32 {
+		<Members<T>>::decode_len().unwrap_or(0) as u32
+	}
+
+	fn do_get_result(poll: &Ticket) -> Option<Vec<(IdentityId<T>, bool)>> {
+		<Polls<T>>::get(poll).map(|p| p.votes)
+	}
+
+	/// Get up to limit polls (including the tally) that still accept votes, skipping the first start open polls
+	pub fn get_open_polls(start: u32, limit: u32) -> Vec<PollTally<IdentityId<T>, BlockNumber<T>>> {
+		let now = frame_system::Module::<T>::block_number();
+		// Polls that closed were removed from ClosingPolls, only open polls are read
+		let mut tickets: Vec<Ticket> = <ClosingPolls<T>>::iter()
+			.filter(|(until, _)| now < *until)
+			.flat_map(|(_, tickets)| tickets.into_iter())
+			.collect();
+		tickets.sort_unstable();
+
+		tickets.into_iter()
+			.filter_map(|ticket| <Polls<T>>::get(ticket))
+			.skip(start as usize)
+			.take(limit as usize)
+			.map(|p| p.tally())
+			.collect()
 	}
 }
 
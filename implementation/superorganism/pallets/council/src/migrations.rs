@@ -0,0 +1,39 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use frame_support::{storage::StorageValue, traits::Get, weights::Weight};
+use crate::{StorageVersion, Trait};
+
+/// Storage layouts of the pallet
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum Releases {
+	/// First versioned layout (equals the storage without a version)
+	V1_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Migrate the storage from any previous layout to the current layout.
+// A release that changes the layout adds a variant to Releases, a migration from the
+// previous variant below and sets the new variant at genesis.
+pub fn migrate<T: Trait>() -> Weight {
+	match <StorageVersion>::get() {
+		Releases::V1_0_0 => T::DbWeight::get().reads(1),
+	}
+}
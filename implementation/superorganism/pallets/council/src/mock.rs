@@ -1,13 +1,34 @@
-/*use crate::{Module, Trait};
+use crate::{GenesisConfig, Module, Trait};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types, traits::OnInitialize, weights::Weight};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
 use frame_system as system;
 
 impl_outer_origin! {
-	pub enum Origin for Test {}
+	pub enum Origin for Test where system = frame_system {
+		council,
+	}
+}
+
+mod council {
+	pub use crate::{Event, Origin};
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		council::Council,
+	}
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		frame_system<T>,
+		pallet_community_identity<T>,
+		council<T>,
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -24,7 +45,7 @@ parameter_types! {
 impl system::Trait for Test {
 	type BaseCallFilter = ();
 	type Origin = Origin;
-	type Call = ();
+	type Call = Call;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
@@ -32,7 +53,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -49,13 +70,47 @@ impl system::Trait for Test {
 	type SystemWeightInfo = ();
 }
 
+impl pallet_community_identity::Trait for Test {
+	type Event = Event;
+	type Timestamp = u64;
+	type WeightInfo = ();
+}
+
 impl Trait for Test {
-	type Event = ();
+	type Event = Event;
+	type Origin = Origin;
+	type Proposal = Call;
+	type Identity = pallet_community_identity::Module<Test>;
+	type WeightInfo = ();
 }
 
-pub type TemplateModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Council = Module<Test>;
+
+/// Council members of the mock runtime
+pub const MEMBERS: [u64; 3] = [1, 2, 3];
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
-}*/
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisConfig::<Test> {
+		members: MEMBERS.to_vec(),
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not stored in the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Initialize the blocks up to (including) block n
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Council::on_initialize(System::block_number());
+	}
+}
+
+/// The last event that was deposited by the pallet
+pub fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
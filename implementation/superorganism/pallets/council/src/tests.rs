@@ -1,25 +1,195 @@
-/*
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{EnsureCouncil, Error, mock::*, Poll, RawEvent, RawOrigin, traits::Council as CouncilTrait};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, traits::EnsureOrigin};
+
+fn documents() -> Vec<Vec<u8>> {
+	vec![b"proposal".to_vec(), b"concern".to_vec()]
+}
+
+#[test]
+fn add_poll_creates_ticket() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_eq!(Council::ticket(), 2);
+		assert_eq!(Council::poll(0), Some(Poll::new(0, documents(), 5)));
+		assert_eq!(Council::closing_polls(5), vec![0, 1]);
+		assert_eq!(last_event(), Event::council(RawEvent::PollCreated(1, documents(), 5)));
+	});
+}
+
+#[test]
+fn add_poll_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert!(Council::add_poll(Origin::signed(1), documents(), 5).is_err());
+		assert_eq!(Council::ticket(), 0);
+	});
+}
+
+#[test]
+fn add_poll_rejects_past_deadline() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Council::add_poll(Origin::root(), documents(), 1), Error::<Test>::InvalidDeadline);
+		assert_noop!(<Council as CouncilTrait>::add_poll(documents(), 0), Error::<Test>::InvalidDeadline);
+	});
+}
+
+#[test]
+fn vote_poll_records_vote() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_ok!(Council::vote_poll(Origin::signed(1), 0, true));
+		assert_ok!(Council::vote_poll(Origin::signed(2), 0, false));
+		assert_eq!(<Council as CouncilTrait>::get_result(&0), Some(vec![(1, true), (2, false)]));
+		assert_eq!(last_event(), Event::council(RawEvent::PollVoted(0, 2, false)));
+	});
+}
+
+#[test]
+fn vote_poll_requires_council_member() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_noop!(Council::vote_poll(Origin::signed(4), 0, true), Error::<Test>::NotACouncilMember);
+	});
+}
+
+#[test]
+fn vote_poll_requires_existing_poll() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Council::vote_poll(Origin::signed(1), 0, true), Error::<Test>::PollNotExistant);
+	});
+}
+
+#[test]
+fn vote_poll_rejects_closed_poll() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		run_to_block(5);
+		assert_noop!(Council::vote_poll(Origin::signed(1), 0, true), Error::<Test>::PollClosed);
+	});
+}
+
+#[test]
+fn vote_poll_rejects_second_vote() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_ok!(Council::vote_poll(Origin::signed(1), 0, true));
+		assert_noop!(Council::vote_poll(Origin::signed(1), 0, false), Error::<Test>::AlreadyVoted);
+	});
+}
+
+#[test]
+fn on_initialize_announces_closed_polls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 3));
+		assert_ok!(Council::vote_poll(Origin::signed(1), 0, true));
+		assert_ok!(Council::vote_poll(Origin::signed(2), 0, true));
+		assert_ok!(Council::vote_poll(Origin::signed(3), 0, false));
+		run_to_block(2);
+		assert_eq!(Council::closing_polls(3), vec![0]);
+		run_to_block(3);
+		assert!(Council::closing_polls(3).is_empty());
+		assert_eq!(last_event(), Event::council(RawEvent::PollClosed(0, 2, 1)));
+		// The result stays available after the poll was closed
+		assert_eq!(<Council as CouncilTrait>::get_result(&0).map(|r| r.len()), Some(3));
+	});
+}
+
+#[test]
+fn get_open_polls_skips_closed_polls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 3));
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_ok!(Council::add_poll(Origin::root(), documents(), 5));
+		assert_ok!(Council::vote_poll(Origin::signed(1), 1, false));
+		assert_eq!(Council::get_open_polls(0, 10).len(), 3);
+		run_to_block(3);
+		let open = Council::get_open_polls(0, 10);
+		assert_eq!(open.iter().map(|p| p.poll.ticket).collect::<Vec<_>>(), vec![1, 2]);
+		assert_eq!((open[0].accept, open[0].deny), (0, 1));
+		assert_eq!(Council::get_open_polls(1, 10).len(), 1);
+		assert_eq!(Council::get_open_polls(0, 1).len(), 1);
+	});
+}
+
+#[test]
+fn get_result_of_unknown_poll_is_none() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<Council as CouncilTrait>::get_result(&0), None);
+	});
+}
+
+#[test]
+fn council_elections_are_not_implemented() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Council::vote_council_member(Origin::signed(4), 5), Error::<Test>::NotImplemented);
+		assert_noop!(Council::vote_reelect_council(Origin::signed(4)), Error::<Test>::NotImplemented);
+		assert_noop!(Council::vote_reelect(Origin::signed(4), 1), Error::<Test>::NotImplemented);
+		assert_noop!(<Council as CouncilTrait>::vote_reelect(4, 1), Error::<Test>::NotImplemented);
+	});
+}
+
+#[test]
+fn trait_votes_as_identity() {
+	new_test_ext().execute_with(|| {
+		let ticket = <Council as CouncilTrait>::add_poll(documents(), 5).unwrap();
+		assert_ok!(<Council as CouncilTrait>::vote_poll(3, ticket, true));
+		assert_noop!(<Council as CouncilTrait>::vote_poll(4, ticket, true), Error::<Test>::NotACouncilMember);
+		assert_eq!(<Council as CouncilTrait>::get_result(&ticket), Some(vec![(3, true)]));
+	});
+}
+
+fn motion() -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::set_heap_pages(1)))
+}
+
+#[test]
+fn propose_motion_creates_poll() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::propose_motion(Origin::signed(1), motion(), 5));
+		assert_eq!(Council::motion(0), Some(*motion()));
+		assert_eq!(Council::closing_polls(5), vec![0]);
+		assert_eq!(Council::poll(0).map(|p| p.documents.len()), Some(1));
+	});
+}
+
+#[test]
+fn propose_motion_requires_council_member() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Council::propose_motion(Origin::signed(4), motion(), 5), Error::<Test>::NotACouncilMember);
+	});
+}
+
+#[test]
+fn accepted_motion_is_dispatched_with_council_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Council::propose_motion(Origin::signed(1), motion(), 3));
+		assert_ok!(Council::vote_poll(Origin::signed(1), 0, true));
+		assert_ok!(Council::vote_poll(Origin::signed(2), 0, true));
+		assert_ok!(Council::vote_poll(Origin::signed(3), 0, false));
+		run_to_block(3);
+		assert_eq!(Council::motion(0), None);
+		// The council origin is not root
+		assert_eq!(last_event(), Event::council(RawEvent::MotionExecuted(0, Err(DispatchError::BadOrigin))));
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn rejected_motion_is_not_dispatched() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(Council::propose_motion(Origin::signed(1), motion(), 3));
+		assert_ok!(Council::vote_poll(Origin::signed(1), 0, true));
+		assert_ok!(Council::vote_poll(Origin::signed(2), 0, false));
+		run_to_block(3);
+		assert_eq!(Council::motion(0), None);
+		assert_eq!(last_event(), Event::council(RawEvent::PollClosed(0, 1, 1)));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn ensure_council_accepts_only_council_origin() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
-		);
+		assert_eq!(EnsureCouncil::try_origin(Origin::from(RawOrigin::AcceptedPoll(4))).ok(), Some(4));
+		assert!(<EnsureCouncil as EnsureOrigin<Origin>>::ensure_origin(Origin::root()).is_err());
+		assert!(<EnsureCouncil as EnsureOrigin<Origin>>::ensure_origin(Origin::signed(1)).is_err());
 	});
 }
-*/
@@ -0,0 +1,115 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_council
+//!
+//! Estimated from the storage accesses of the extrinsics, the values were not measured yet.
+//! SubstrateWeight prices the storage accesses with the database weights of the runtime.
+//! Replace them with the results of the benchmarks in benchmarking.rs on reference hardware:
+//! ./target/release/node-superorganism benchmark --chain dev --execution wasm --wasm-execution compiled
+//!   --pallet pallet_council --extrinsic '*' --steps 50 --repeat 20
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{sp_std::marker::PhantomData, traits::Get,
+	weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+/// Weight functions needed for pallet_council
+pub trait WeightInfo {
+	fn vote_council_member() -> Weight;
+	fn vote_reelect_council() -> Weight;
+	fn vote_reelect() -> Weight;
+	fn add_poll(d: u32, ) -> Weight;
+	fn propose_motion() -> Weight;
+	fn vote_poll(m: u32, ) -> Weight;
+	fn on_initialize(t: u32, m: u32, ) -> Weight;
+}
+
+/// Weights for pallet_council using the database weights of the runtime
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn vote_council_member() -> Weight {
+		(15_000_000 as Weight)
+	}
+	fn vote_reelect_council() -> Weight {
+		(13_000_000 as Weight)
+	}
+	fn vote_reelect() -> Weight {
+		(15_000_000 as Weight)
+	}
+	fn add_poll(d: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((1_120_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn propose_motion() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn vote_poll(m: u32, ) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((510_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn on_initialize(t: u32, m: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((21_000_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((140_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+/// Weights for tests and runtimes without own database weights
+impl WeightInfo for () {
+	fn vote_council_member() -> Weight {
+		(15_000_000 as Weight)
+	}
+	fn vote_reelect_council() -> Weight {
+		(13_000_000 as Weight)
+	}
+	fn vote_reelect() -> Weight {
+		(15_000_000 as Weight)
+	}
+	fn add_poll(d: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((1_120_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn propose_motion() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn vote_poll(m: u32, ) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((510_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn on_initialize(t: u32, m: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((21_000_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((140_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,74 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet_document_availability
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const MAX_REPORTERS: u32 = 100;
+const SEED: u32 = 0;
+
+/// Document CID of the length of a CIDv1 (sha2-256, base32)
+fn document() -> DocumentCID {
+	b"bafkreibjk3a4gnis37z76ovao6v5ngavqipgrj42kvtrozwyxjrsy7aohu".to_vec()
+}
+
+/// Set r reporters. If a caller is given, it becomes the last reporter.
+fn set_reporters<T: Trait>(r: u32, caller: Option<T::AccountId>) {
+	let mut reporters: Vec<T::AccountId> = (0..r).map(|i| account("reporter", i, SEED)).collect();
+
+	if let Some(caller) = caller {
+		reporters.pop();
+		reporters.push(caller);
+	}
+
+	<Reporters<T>>::put(reporters);
+}
+
+benchmarks! {
+	_ { }
+
+	report {
+		let r in 1 .. MAX_REPORTERS;
+		let caller: T::AccountId = whitelisted_caller();
+		set_reporters::<T>(r, Some(caller.clone()));
+		<Module<T> as DocumentAvailability>::register(&document());
+	}: _(RawOrigin::Signed(caller), document(), Availability::Available)
+	verify {
+		assert_eq!(<Documents>::get(document()), Some(Availability::Available));
+	}
+
+	add_reporter {
+		let r in 0 .. MAX_REPORTERS;
+		set_reporters::<T>(r, None);
+		let origin = T::ReporterOrigin::successful_origin();
+		let reporter: T::AccountId = account("candidate", 0, SEED);
+	}: { Module::<T>::add_reporter(origin, reporter.clone())? }
+	verify {
+		assert!(Module::<T>::reporters().contains(&reporter));
+	}
+
+	// Worst case: The reporter is the last reporter
+	remove_reporter {
+		let r in 1 .. MAX_REPORTERS;
+		let reporter: T::AccountId = account("candidate", 0, SEED);
+		set_reporters::<T>(r, Some(reporter.clone()));
+		let origin = T::ReporterOrigin::successful_origin();
+	}: { Module::<T>::remove_reporter(origin, reporter.clone())? }
+	verify {
+		assert!(!Module::<T>::reporters().contains(&reporter));
+	}
+}
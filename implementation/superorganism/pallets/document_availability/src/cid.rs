@@ -0,0 +1,123 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decode the sha2-256 digest from a textual CID. Supported are CIDv0 (base58btc) and
+//! CIDv1 in base32 (prefix b) or base58btc (prefix z).
+
+use sp_std::vec::Vec;
+
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// Multihash code of sha2-256
+const SHA2_256: u64 = 0x12;
+/// Length of a textual CIDv0
+const CIDV0_LENGTH: usize = 46;
+
+/// Get the sha2-256 digest the CID refers to. Returns None if the CID is malformed or
+/// uses another hash function.
+pub fn sha2_256_digest(cid: &[u8]) -> Option<[u8; 32]> {
+	let multihash: Vec<u8> = if cid.len() == CIDV0_LENGTH && cid.starts_with(b"Qm") {
+		// A CIDv0 is a bare multihash
+		decode_base58(cid)?
+	} else {
+		let bytes = match cid.split_first()? {
+			(b'b', encoded) => decode_base32(encoded)?,
+			(b'z', encoded) => decode_base58(encoded)?,
+			_ => return None,
+		};
+		let mut input: &[u8] = &bytes;
+
+		if read_varint(&mut input)? != 1 {
+			return None;
+		}
+
+		// The content codec does not matter, the gateway returns the raw block
+		read_varint(&mut input)?;
+		input.to_vec()
+	};
+
+	let mut input: &[u8] = &multihash;
+
+	if read_varint(&mut input)? != SHA2_256 || read_varint(&mut input)? != 32 || input.len() != 32 {
+		return None;
+	}
+
+	let mut digest = [0u8; 32];
+	digest.copy_from_slice(input);
+	Some(digest)
+}
+
+/// Decode lowercase base32 (RFC 4648) without padding
+fn decode_base32(input: &[u8]) -> Option<Vec<u8>> {
+	let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 5 / 8);
+	let mut buffer: u32 = 0;
+	let mut bits: u32 = 0;
+
+	for c in input.iter() {
+		let value = BASE32_ALPHABET.iter().position(|a| a == c)? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+
+	Some(bytes)
+}
+
+/// Decode base58 with the bitcoin alphabet
+fn decode_base58(input: &[u8]) -> Option<Vec<u8>> {
+	// Little endian big number, multiplied by 58 for every character
+	let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+
+	for c in input.iter() {
+		let mut carry = BASE58_ALPHABET.iter().position(|a| a == c)? as u32;
+
+		for byte in bytes.iter_mut() {
+			carry += u32::from(*byte) * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+
+	// Every leading '1' encodes a leading zero byte
+	bytes.extend(input.iter().take_while(|c| **c == b'1').map(|_| 0u8));
+	bytes.reverse();
+	Some(bytes)
+}
+
+/// Read an unsigned varint (multiformats) and advance the input
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+	let mut value: u64 = 0;
+
+	for i in 0..9 {
+		let (byte, rest) = input.split_first()?;
+		*input = rest;
+		value |= u64::from(byte & 0x7f) << (7 * i);
+
+		if byte & 0x80 == 0 {
+			return Some(value);
+		}
+	}
+
+	None
+}
@@ -0,0 +1,353 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # pallet-document_availability
+//! Checks that the documents the governance process refers to by CID can be fetched from IPFS.
+//!
+//! Other pallets register new documents. The off-chain workers of reporter nodes fetch the raw
+//! (root) block of every pending document from an HTTP IPFS gateway, compare its hash to the CID
+//! and report the document as available or unavailable with a signed transaction. Documents that
+//! were reported as unavailable stay pending and are checked again, until a reporter reports
+//! them as available.
+//!
+//! A reporter node requires a key of type `docs` (author_insertKey) for an account in Reporters.
+//! The gateway defaults to DEFAULT_GATEWAY and is configured in the persistent local storage of
+//! the node under GATEWAY_KEY (offchain_localStorageSet), e.g. "http://127.0.0.1:8080".
+
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, debug, ensure, IterableStorageMap,
+	dispatch::{DispatchResultWithPostInfo, Vec}, traits::{EnsureOrigin, Get}, weights::Pays};
+use frame_system::{ensure_signed, offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer}};
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use frame_support::serde::{Deserialize, Serialize};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{offchain::{http, Duration, StorageKind, storage::StorageValueRef}, traits::Saturating};
+use traits::DocumentAvailability;
+/// Decode the digest of a CID
+pub mod cid;
+/// Public interface to DocumentAvailability
+pub mod traits;
+/// Weights of the extrinsics
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+// TODO: Change from Vec<u8> to fixed length type
+pub type DocumentCID = Vec<u8>;
+
+/// Key type of the reporter keys
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"docs");
+/// Gateway that is used if none is configured
+pub const DEFAULT_GATEWAY: &[u8] = b"http://127.0.0.1:8080";
+/// Key of the gateway in the persistent local storage of the node
+pub const GATEWAY_KEY: &[u8] = b"document_availability::gateway";
+/// Prefix of the keys that store when a document was checked by this node
+const CHECKED_PREFIX: &[u8] = b"document_availability::checked::";
+/// How long does the off-chain worker wait for the gateway? Value in milliseconds.
+const FETCH_TIMEOUT: u64 = 3_000;
+
+/// Keys the off-chain workers sign the reports with
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{app_crypto::{app_crypto, sr25519}, traits::Verify, MultiSignature, MultiSigner};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct ReporterId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for ReporterId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	// Runtimes that sign with sr25519 directly (e.g. the mock runtime)
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature> for ReporterId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Result of an availability check
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Availability {
+	/// The gateway returned content that matches the CID
+	Available,
+	/// The gateway did not find the document, returned other content or the CID cannot be verified.
+	/// The document is checked again.
+	Unavailable,
+}
+
+/// Configure the pallet by specifying the parameters and types on which it depends.
+pub trait Trait: CreateSignedTransaction<Call<Self>> {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Crypto of the keys the off-chain workers sign the reports with
+	type ReporterId: AppCrypto<Self::Public, Self::Signature>;
+
+	/// Origin that can add and remove reporters
+	type ReporterOrigin: EnsureOrigin<Self::Origin>;
+
+	// Parameters
+	/// How many documents does an off-chain worker check per block at most?
+	type ChecksPerBlock: Get<u32>;
+
+	/// How long does an off-chain worker wait before it checks a pending document again?
+	/// Covers reports that are not included yet and unreachable gateways. Value in blocks.
+	type RecheckInterval: Get<Self::BlockNumber>;
+
+	/// Weight information for extrinsics in this pallet
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as DocumentAvailability {
+		/// Accounts whose off-chain workers report the availability of documents
+		pub Reporters get(fn reporters) config(): Vec<T::AccountId>;
+		/// Document -> Block the document was registered at. Checked by the off-chain workers until
+		/// it is reported as available.
+		pub PendingDocuments get(fn pending): map hasher(blake2_128_concat) DocumentCID => Option<T::BlockNumber>;
+		/// Document -> Reported availability
+		pub Documents get(fn availability): map hasher(blake2_128_concat) DocumentCID => Option<Availability>;
+		/// Document -> How often the document was registered and not forgotten yet
+		pub References get(fn references): map hasher(blake2_128_concat) DocumentCID => u32;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+		/// A document awaits the availability check \[Document\]
+		DocumentRegistered(DocumentCID),
+		/// A reporter checked the availability of a document \[Reporter, Document, Availability\]
+		AvailabilityReported(AccountId, DocumentCID, Availability),
+		/// Governance added a reporter \[Reporter\]
+		ReporterAdded(AccountId),
+		/// Governance removed a reporter \[Reporter\]
+		ReporterRemoved(AccountId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The document was already reported with the same availability
+		AlreadyReported,
+		/// Account is already a reporter
+		AlreadyReporter,
+		/// Document is not registered or was already reported as available
+		DocumentNotPending,
+		/// Account is not a reporter
+		NotReporter,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// As a reporter, report whether a pending document can be fetched and matches its CID.
+		/// Documents that are reported as available are final, unavailable documents stay pending.
+		/// Accepted reports are free.
+		#[weight = T::WeightInfo::report(<Reporters<T>>::decode_len().unwrap_or(0) as u32)]
+		fn report(origin, document: DocumentCID, availability: Availability) -> DispatchResultWithPostInfo {
+			let reporter = ensure_signed(origin)?;
+			ensure!(Self::reporters().contains(&reporter), Error::<T>::NotReporter);
+			ensure!(<PendingDocuments<T>>::contains_key(&document), Error::<T>::DocumentNotPending);
+			ensure!(Self::availability(&document) != Some(availability), Error::<T>::AlreadyReported);
+
+			if availability == Availability::Available {
+				<PendingDocuments<T>>::remove(&document);
+			}
+
+			<Documents>::insert(&document, availability);
+			Self::deposit_event(Event::<T>::AvailabilityReported(reporter, document, availability));
+			Ok(Pays::No.into())
+		}
+
+		/// As ReporterOrigin, allow the off-chain worker of an account to report
+		#[weight = T::WeightInfo::add_reporter(<Reporters<T>>::decode_len().unwrap_or(0) as u32)]
+		fn add_reporter(origin, reporter: T::AccountId) {
+			T::ReporterOrigin::ensure_origin(origin)?;
+			let mut reporters = Self::reporters();
+			ensure!(!reporters.contains(&reporter), Error::<T>::AlreadyReporter);
+			reporters.push(reporter.clone());
+			<Reporters<T>>::put(reporters);
+			Self::deposit_event(Event::<T>::ReporterAdded(reporter));
+		}
+
+		/// As ReporterOrigin, revoke the permission of an account to report
+		#[weight = T::WeightInfo::remove_reporter(<Reporters<T>>::decode_len().unwrap_or(0) as u32)]
+		fn remove_reporter(origin, reporter: T::AccountId) {
+			T::ReporterOrigin::ensure_origin(origin)?;
+			let mut reporters = Self::reporters();
+			let idx = reporters.iter().position(|r| *r == reporter).ok_or(Error::<T>::NotReporter)?;
+			reporters.remove(idx);
+			<Reporters<T>>::put(reporters);
+			Self::deposit_event(Event::<T>::ReporterRemoved(reporter));
+		}
+
+		/// Check pending documents and report their availability
+		fn offchain_worker(now: T::BlockNumber) {
+			Self::check_pending_documents(now);
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Check up to ChecksPerBlock pending documents that were not checked within RecheckInterval
+	fn check_pending_documents(now: T::BlockNumber) {
+		let signer = Signer::<T, T::ReporterId>::any_account();
+
+		// Only nodes that hold a reporter key check documents
+		if !signer.can_sign() {
+			return;
+		}
+
+		let gateway: Vec<u8> = Self::gateway();
+		let documents: Vec<DocumentCID> = <PendingDocuments<T>>::iter()
+			.map(|(document, _)| document)
+			.filter(|document| Self::acquire_check(document, now))
+			.take(T::ChecksPerBlock::get() as usize)
+			.collect();
+
+		for document in documents.into_iter() {
+			let availability = match Self::check_document(&gateway, &document) {
+				Ok(availability) => availability,
+				Err(e) => {
+					debug::warn!("Unable to fetch document {:?} from the gateway: {:?}", document, e);
+					continue;
+				}
+			};
+
+			// Documents that are still unavailable do not change
+			if Self::availability(&document) == Some(availability) {
+				continue;
+			}
+
+			match signer.send_signed_transaction(|_| Call::report(document.clone(), availability)) {
+				Some((_, Ok(()))) => {},
+				_ => debug::warn!("Unable to submit the availability of document {:?}", document),
+			}
+		}
+	}
+
+	/// Base URL of the IPFS gateway
+	fn gateway() -> Vec<u8> {
+		sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, GATEWAY_KEY)
+			.unwrap_or_else(|| DEFAULT_GATEWAY.to_vec())
+	}
+
+	/// Remember that this node checks the document now. Returns false if the node checked it
+	/// within RecheckInterval.
+	fn acquire_check(document: &DocumentCID, now: T::BlockNumber) -> bool {
+		let mut key: Vec<u8> = CHECKED_PREFIX.to_vec();
+		key.extend_from_slice(document);
+		let checked = StorageValueRef::persistent(&key);
+		let result = checked.mutate(|last: Option<Option<T::BlockNumber>>| match last {
+			Some(Some(at)) if now < at.saturating_add(T::RecheckInterval::get()) => Err(()),
+			_ => Ok(now),
+		});
+
+		matches!(result, Ok(Ok(_)))
+	}
+
+	/// Fetch the raw block of the document and compare its hash to the CID.
+	/// Returns an error if the gateway is unreachable, fails or refuses to serve the document,
+	/// the document is checked again later.
+	fn check_document(gateway: &[u8], document: &DocumentCID) -> Result<Availability, http::Error> {
+		// Documents with a malformed CID or an unsupported hash function cannot be verified
+		let digest = match cid::sha2_256_digest(document) {
+			Some(digest) => digest,
+			None => return Ok(Availability::Unavailable),
+		};
+
+		let mut url: Vec<u8> = gateway.to_vec();
+		url.extend_from_slice(b"/ipfs/");
+		url.extend_from_slice(document);
+		url.extend_from_slice(b"?format=raw");
+		let url = sp_std::str::from_utf8(&url).map_err(|_| http::Error::Unknown)?;
+
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT));
+		let request = http::Request::get(url)
+			.add_header("Accept", "application/vnd.ipld.raw")
+			.deadline(deadline)
+			.send()
+			.map_err(|_| http::Error::IoError)?;
+		let response = request.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+
+		match response.code {
+			200 => {
+				// Blocks are limited in size, the body fits into memory
+				let content: Vec<u8> = response.body().collect();
+
+				if sp_io::hashing::sha2_256(&content) == digest {
+					Ok(Availability::Available)
+				} else {
+					Ok(Availability::Unavailable)
+				}
+			},
+			// The gateway does not know the document
+			404 => Ok(Availability::Unavailable),
+			// Server errors (e.g. 504 if the gateway could not retrieve the document in time) are
+			// temporary, 410 only states that the gateway does not serve the document
+			_ => Err(http::Error::Unknown),
+		}
+	}
+}
+
+impl<T: Trait> DocumentAvailability for Module<T> {
+	type DocumentCID = DocumentCID;
+
+	fn register(document: &DocumentCID) {
+		<References>::mutate(document, |references| *references = references.saturating_add(1));
+
+		if <PendingDocuments<T>>::contains_key(document) || <Documents>::contains_key(document) {
+			return;
+		}
+
+		<PendingDocuments<T>>::insert(document, frame_system::Module::<T>::block_number());
+		Self::deposit_event(Event::<T>::DocumentRegistered(document.clone()));
+	}
+
+	fn is_unavailable(document: &DocumentCID) -> bool {
+		<Documents>::get(document) == Some(Availability::Unavailable)
+	}
+
+	fn forget(document: &DocumentCID) {
+		let references = <References>::get(document).saturating_sub(1);
+
+		// Another registration still refers to the document
+		if references > 0 {
+			<References>::insert(document, references);
+			return;
+		}
+
+		<References>::remove(document);
+		<PendingDocuments<T>>::remove(document);
+		<Documents>::remove(document);
+	}
+}
@@ -0,0 +1,192 @@
+use crate::{crypto::ReporterId, Call, GenesisConfig, Module, Trait, KEY_TYPE};
+use std::sync::Arc;
+use codec::Decode;
+use parking_lot::RwLock;
+use sp_core::{
+	H256, sr25519, offchain::{OffchainExt, TransactionPoolExt, testing::{self, OffchainState, PoolState}},
+	testing::KeyStore, traits::KeystoreExt,
+};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_runtime::{
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup, Verify}, testing::{Header, TestXt}, Perbill,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+mod document_availability {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		frame_system<T>,
+		document_availability<T>,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+pub type AccountId = sr25519::Public;
+pub type Extrinsic = TestXt<Call<Test>, ()>;
+type Signature = sr25519::Signature;
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test where
+	Call<Test>: From<LocalCall>,
+{
+	type OverarchingCall = Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test where
+	Call<Test>: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call<Test>,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call<Test>, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub const ChecksPerBlock: u32 = 2;
+	pub const RecheckInterval: u64 = 5;
+}
+
+impl Trait for Test {
+	type Event = Event;
+	type ReporterId = ReporterId;
+	type ReporterOrigin = EnsureRoot<AccountId>;
+	type ChecksPerBlock = ChecksPerBlock;
+	type RecheckInterval = RecheckInterval;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type DocumentAvailability = Module<Test>;
+
+/// Content that is referenced by the CIDs below
+pub const CONTENT: &[u8] = b"superorganism";
+/// CIDv0 of CONTENT
+pub const CID_V0: &[u8] = b"QmR829rCv9o7b5B5nZ7T2M91VEQFHJxaSkAEZLqFBx9ybz";
+/// CIDv1 (raw, base32) of CONTENT
+pub const CID_V1: &[u8] = b"bafkreibjk3a4gnis37z76ovao6v5ngavqipgrj42kvtrozwyxjrsy7aohu";
+/// CIDv1 (raw, base58btc) of CONTENT
+pub const CID_V1_BASE58: &[u8] = b"zb2rhZReduRA148YG2dmesjiFXUFW7jzoHKMWxJs1ipxE89oi";
+/// CIDv1 (raw, base32) of CONTENT with a blake2b-256 multihash
+pub const CID_BLAKE2B: &[u8] = b"bafk2bzaceauvnqodgujn7477hkqhpk6wtakyehtiu6nfkzyxm3mluyzmpqhd2";
+/// sha2-256 digest of CONTENT
+pub const DIGEST: [u8; 32] = [
+	0x29, 0x56, 0xc1, 0xc3, 0x35, 0x12, 0xdf, 0xf3, 0xff, 0x3a, 0xa0, 0x77, 0xab, 0xd6, 0x98, 0x15,
+	0x82, 0x1e, 0x68, 0xa7, 0x9a, 0x55, 0x67, 0x17, 0x66, 0xd8, 0xba, 0x63, 0x2c, 0x7c, 0x0e, 0x3d,
+];
+
+const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+/// Account derived from the seed
+pub fn account(seed: u8) -> AccountId {
+	sr25519::Public::from_raw([seed; 32])
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext(reporters: Vec<AccountId>) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisConfig::<Test> {
+		reporters,
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not stored in the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Build genesis storage with the offchain extensions. The keystore holds the key of the
+/// returned reporter, which is the only reporter.
+pub fn new_offchain_test_ext()
+	-> (sp_io::TestExternalities, Arc<RwLock<OffchainState>>, Arc<RwLock<PoolState>>, AccountId)
+{
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	let reporter = keystore.write().sr25519_generate_new(KEY_TYPE, Some(&format!("{}/reporter", PHRASE)))
+		.unwrap();
+
+	let mut ext = new_test_ext(vec![reporter]);
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(keystore));
+	(ext, offchain_state, pool_state, reporter)
+}
+
+/// Let the gateway answer the next request for the document with the content
+pub fn expect_fetch(state: &Arc<RwLock<OffchainState>>, gateway: &str, document: &[u8], content: &[u8]) {
+	state.write().expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: format!("{}/ipfs/{}?format=raw", gateway, std::str::from_utf8(document).unwrap()),
+		headers: vec![("Accept".into(), "application/vnd.ipld.raw".into())],
+		response: Some(content.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+/// Calls of the transactions the off-chain worker submitted, with the nonce they were signed with
+pub fn submitted_reports(state: &Arc<RwLock<PoolState>>) -> Vec<(u64, Call<Test>)> {
+	state.write().transactions.drain(..).map(|tx| {
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		(tx.signature.unwrap().0, tx.call)
+	}).collect()
+}
+
+/// The last event that was deposited by the pallet
+pub fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
@@ -0,0 +1,272 @@
+use crate::{Availability, Call, cid, Error, GATEWAY_KEY, RawEvent, traits::DocumentAvailability as _};
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::OffchainWorker, weights::Pays};
+use sp_runtime::offchain::StorageKind;
+
+const DEFAULT_GATEWAY: &str = "http://127.0.0.1:8080";
+
+#[test]
+fn cid_digest_is_decoded() {
+	assert_eq!(cid::sha2_256_digest(CID_V0), Some(DIGEST));
+	assert_eq!(cid::sha2_256_digest(CID_V1), Some(DIGEST));
+	assert_eq!(cid::sha2_256_digest(CID_V1_BASE58), Some(DIGEST));
+}
+
+#[test]
+fn cid_without_sha2_256_digest_is_rejected() {
+	assert_eq!(cid::sha2_256_digest(CID_BLAKE2B), None);
+	assert_eq!(cid::sha2_256_digest(b""), None);
+	assert_eq!(cid::sha2_256_digest(b"QmCommunityGarden"), None);
+	assert_eq!(cid::sha2_256_digest(&CID_V1[..CID_V1.len() - 4]), None);
+	// Not in the base32 alphabet
+	let mut uppercase = CID_V1.to_ascii_uppercase();
+	uppercase[0] = b'b';
+	assert_eq!(cid::sha2_256_digest(&uppercase), None);
+}
+
+#[test]
+fn register_queues_document_once() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_eq!(DocumentAvailability::pending(CID_V1.to_vec()), Some(1));
+		assert_eq!(last_event(), Event::document_availability(RawEvent::DocumentRegistered(CID_V1.to_vec())));
+
+		let events = System::events().len();
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_eq!(System::events().len(), events);
+		assert!(!DocumentAvailability::is_unavailable(&CID_V1.to_vec()));
+	});
+}
+
+#[test]
+fn report_stores_availability() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		DocumentAvailability::register(&CID_V1.to_vec());
+		let result = DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(),
+			Availability::Unavailable);
+		assert_eq!(result.map(|info| info.pays_fee), Ok(Pays::No));
+		// Unavailable documents are checked again
+		assert_eq!(DocumentAvailability::pending(CID_V1.to_vec()), Some(1));
+		assert_eq!(DocumentAvailability::availability(CID_V1.to_vec()), Some(Availability::Unavailable));
+		assert!(DocumentAvailability::is_unavailable(&CID_V1.to_vec()));
+		assert_eq!(last_event(), Event::document_availability(
+			RawEvent::AvailabilityReported(account(1), CID_V1.to_vec(), Availability::Unavailable)
+		));
+
+		// Reported documents are not registered again
+		let events = System::events().len();
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn unavailable_document_can_be_reported_as_available() {
+	new_test_ext(vec![account(1), account(2)]).execute_with(|| {
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_ok!(DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(),
+			Availability::Unavailable));
+		assert_noop!(
+			DocumentAvailability::report(Origin::signed(account(2)), CID_V1.to_vec(), Availability::Unavailable),
+			Error::<Test>::AlreadyReported
+		);
+
+		assert_ok!(DocumentAvailability::report(Origin::signed(account(2)), CID_V1.to_vec(),
+			Availability::Available));
+		assert_eq!(DocumentAvailability::pending(CID_V1.to_vec()), None);
+		assert_eq!(DocumentAvailability::availability(CID_V1.to_vec()), Some(Availability::Available));
+		assert!(!DocumentAvailability::is_unavailable(&CID_V1.to_vec()));
+	});
+}
+
+#[test]
+fn report_requires_reporter() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_noop!(
+			DocumentAvailability::report(Origin::signed(account(2)), CID_V1.to_vec(), Availability::Unavailable),
+			Error::<Test>::NotReporter
+		);
+	});
+}
+
+#[test]
+fn report_requires_pending_document() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		assert_noop!(
+			DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(), Availability::Available),
+			Error::<Test>::DocumentNotPending
+		);
+
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_ok!(DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(),
+			Availability::Available));
+		assert_noop!(
+			DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(), Availability::Unavailable),
+			Error::<Test>::DocumentNotPending
+		);
+	});
+}
+
+#[test]
+fn forget_removes_document() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		DocumentAvailability::register(&CID_V0.to_vec());
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_ok!(DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(),
+			Availability::Unavailable));
+
+		DocumentAvailability::forget(&CID_V0.to_vec());
+		DocumentAvailability::forget(&CID_V1.to_vec());
+		assert_eq!(DocumentAvailability::pending(CID_V0.to_vec()), None);
+		assert_eq!(DocumentAvailability::availability(CID_V1.to_vec()), None);
+		assert!(!DocumentAvailability::is_unavailable(&CID_V1.to_vec()));
+	});
+}
+
+#[test]
+fn forget_keeps_document_of_other_registrations() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		// E.g. the same concern about two proposals
+		DocumentAvailability::register(&CID_V1.to_vec());
+		DocumentAvailability::register(&CID_V1.to_vec());
+		assert_ok!(DocumentAvailability::report(Origin::signed(account(1)), CID_V1.to_vec(),
+			Availability::Unavailable));
+
+		DocumentAvailability::forget(&CID_V1.to_vec());
+		assert_eq!(DocumentAvailability::references(CID_V1.to_vec()), 1);
+		assert_eq!(DocumentAvailability::pending(CID_V1.to_vec()), Some(1));
+		assert!(DocumentAvailability::is_unavailable(&CID_V1.to_vec()));
+
+		DocumentAvailability::forget(&CID_V1.to_vec());
+		assert_eq!(DocumentAvailability::references(CID_V1.to_vec()), 0);
+		assert_eq!(DocumentAvailability::pending(CID_V1.to_vec()), None);
+		assert!(!DocumentAvailability::is_unavailable(&CID_V1.to_vec()));
+	});
+}
+
+#[test]
+fn reporters_are_managed_by_reporter_origin() {
+	new_test_ext(vec![account(1)]).execute_with(|| {
+		assert!(DocumentAvailability::add_reporter(Origin::signed(account(1)), account(2)).is_err());
+		assert_ok!(DocumentAvailability::add_reporter(Origin::root(), account(2)));
+		assert_eq!(last_event(), Event::document_availability(RawEvent::ReporterAdded(account(2))));
+		assert_noop!(DocumentAvailability::add_reporter(Origin::root(), account(2)), Error::<Test>::AlreadyReporter);
+		assert_eq!(DocumentAvailability::reporters(), vec![account(1), account(2)]);
+
+		assert!(DocumentAvailability::remove_reporter(Origin::signed(account(1)), account(1)).is_err());
+		assert_ok!(DocumentAvailability::remove_reporter(Origin::root(), account(1)));
+		assert_eq!(last_event(), Event::document_availability(RawEvent::ReporterRemoved(account(1))));
+		assert_noop!(DocumentAvailability::remove_reporter(Origin::root(), account(1)), Error::<Test>::NotReporter);
+		assert_eq!(DocumentAvailability::reporters(), vec![account(2)]);
+	});
+}
+
+#[test]
+fn offchain_worker_reports_matching_content_as_available() {
+	let (mut ext, offchain, pool, _) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		DocumentAvailability::register(&CID_V1.to_vec());
+		expect_fetch(&offchain, DEFAULT_GATEWAY, CID_V1, CONTENT);
+		DocumentAvailability::offchain_worker(1);
+		assert_eq!(submitted_reports(&pool), vec![(0, Call::report(CID_V1.to_vec(), Availability::Available))]);
+	});
+}
+
+#[test]
+fn offchain_worker_reports_other_content_as_unavailable() {
+	let (mut ext, offchain, pool, _) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		DocumentAvailability::register(&CID_V0.to_vec());
+		expect_fetch(&offchain, DEFAULT_GATEWAY, CID_V0, b"tampered");
+		DocumentAvailability::offchain_worker(1);
+		assert_eq!(submitted_reports(&pool), vec![(0, Call::report(CID_V0.to_vec(), Availability::Unavailable))]);
+	});
+}
+
+#[test]
+fn offchain_worker_reports_unverifiable_cid_without_fetching() {
+	let (mut ext, _, pool, _) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		// The test gateway panics on unexpected requests
+		DocumentAvailability::register(&CID_BLAKE2B.to_vec());
+		DocumentAvailability::offchain_worker(1);
+		assert_eq!(submitted_reports(&pool), vec![(0, Call::report(CID_BLAKE2B.to_vec(), Availability::Unavailable))]);
+	});
+}
+
+#[test]
+fn offchain_worker_does_not_report_unchanged_availability() {
+	let (mut ext, _, pool, reporter) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		DocumentAvailability::register(&CID_BLAKE2B.to_vec());
+		assert_ok!(DocumentAvailability::report(Origin::signed(reporter), CID_BLAKE2B.to_vec(),
+			Availability::Unavailable));
+		DocumentAvailability::offchain_worker(1);
+		assert!(submitted_reports(&pool).is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_uses_configured_gateway() {
+	let (mut ext, offchain, pool, _) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		let gateway = "https://ipfs.superorganism.example";
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, GATEWAY_KEY, gateway.as_bytes());
+		DocumentAvailability::register(&CID_V1.to_vec());
+		expect_fetch(&offchain, gateway, CID_V1, CONTENT);
+		DocumentAvailability::offchain_worker(1);
+		assert_eq!(submitted_reports(&pool), vec![(0, Call::report(CID_V1.to_vec(), Availability::Available))]);
+	});
+}
+
+#[test]
+fn offchain_worker_rechecks_after_interval() {
+	let (mut ext, offchain, pool, _) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		DocumentAvailability::register(&CID_V1.to_vec());
+		expect_fetch(&offchain, DEFAULT_GATEWAY, CID_V1, CONTENT);
+		DocumentAvailability::offchain_worker(1);
+		assert_eq!(submitted_reports(&pool).len(), 1);
+
+		// The report was not included yet
+		DocumentAvailability::offchain_worker(1 + RecheckInterval::get() - 1);
+		assert!(submitted_reports(&pool).is_empty());
+
+		expect_fetch(&offchain, DEFAULT_GATEWAY, CID_V1, CONTENT);
+		DocumentAvailability::offchain_worker(1 + RecheckInterval::get());
+		assert_eq!(submitted_reports(&pool).len(), 1);
+	});
+}
+
+#[test]
+fn offchain_worker_limits_checks_per_block() {
+	let (mut ext, _, pool, _) = new_offchain_test_ext();
+	ext.execute_with(|| {
+		let documents = [CID_BLAKE2B.to_vec(), b"QmInvalid".to_vec(), b"bInvalid".to_vec()];
+
+		for document in documents.iter() {
+			DocumentAvailability::register(document);
+		}
+
+		DocumentAvailability::offchain_worker(1);
+		assert_eq!(submitted_reports(&pool).len(), ChecksPerBlock::get() as usize);
+		DocumentAvailability::offchain_worker(2);
+		assert_eq!(submitted_reports(&pool).len(), documents.len() - ChecksPerBlock::get() as usize);
+	});
+}
+
+#[test]
+fn offchain_worker_requires_reporter_key() {
+	let (offchain, _) = sp_core::offchain::testing::TestOffchainExt::new();
+	let (pool, pool_state) = sp_core::offchain::testing::TestTransactionPoolExt::new();
+	let mut ext = new_test_ext(vec![account(1)]);
+	ext.register_extension(sp_core::offchain::OffchainExt::new(offchain));
+	ext.register_extension(sp_core::offchain::TransactionPoolExt::new(pool));
+	ext.register_extension(sp_core::traits::KeystoreExt(sp_core::testing::KeyStore::new()));
+	ext.execute_with(|| {
+		DocumentAvailability::register(&CID_BLAKE2B.to_vec());
+		DocumentAvailability::offchain_worker(1);
+		assert!(submitted_reports(&pool_state).is_empty());
+	});
+}
@@ -0,0 +1,43 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::dispatch::{Codec, fmt::Debug, Vec};
+
+/// Availability checks of documents that are referenced by CID.
+/// Documents that were not checked yet count as available.
+pub trait DocumentAvailability {
+	type DocumentCID: Codec + Clone + Debug + Eq + PartialEq;
+
+	/// Queue the document for the availability check of the off-chain workers.
+	/// Every registration must be balanced by forget.
+	fn register(document: &Self::DocumentCID);
+	/// Was the document reported as unavailable?
+	fn is_unavailable(document: &Self::DocumentCID) -> bool;
+	/// Drop the availability of the document and a pending check once every registration
+	/// of the document was forgotten
+	fn forget(document: &Self::DocumentCID);
+}
+
+/// No availability checks, every document is available
+impl DocumentAvailability for () {
+	type DocumentCID = Vec<u8>;
+
+	fn register(_document: &Vec<u8>) {}
+
+	fn is_unavailable(_document: &Vec<u8>) -> bool {
+		false
+	}
+
+	fn forget(_document: &Vec<u8>) {}
+}
@@ -0,0 +1,79 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_document_availability
+//!
+//! Estimated from the storage accesses of the extrinsics, the values were not measured yet.
+//! SubstrateWeight prices the storage accesses with the database weights of the runtime.
+//! Replace them with the results of the benchmarks in benchmarking.rs on reference hardware:
+//! ./target/release/node-superorganism benchmark --chain dev --execution wasm --wasm-execution compiled
+//!   --pallet pallet_document_availability --extrinsic '*' --steps 50 --repeat 20
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{sp_std::marker::PhantomData, traits::Get,
+	weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+/// Weight functions needed for pallet_document_availability
+pub trait WeightInfo {
+	fn report(r: u32, ) -> Weight;
+	fn add_reporter(r: u32, ) -> Weight;
+	fn remove_reporter(r: u32, ) -> Weight;
+}
+
+/// Weights for pallet_document_availability using the database weights of the runtime
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn report(r: u32, ) -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add((160_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn add_reporter(r: u32, ) -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add((180_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_reporter(r: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+/// Weights for tests and runtimes without own database weights
+impl WeightInfo for () {
+	fn report(r: u32, ) -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add((160_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn add_reporter(r: u32, ) -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add((180_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_reporter(r: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
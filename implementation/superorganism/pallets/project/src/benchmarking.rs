@@ -0,0 +1,283 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet_project
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use pallet_proposal_types::ProposalMetadata;
+
+const MAX_APPLICATIONS: u32 = 100;
+const MAX_CANDIDATES: u32 = 100;
+const MAX_ROLES: u32 = 32;
+const MAX_WORKERS: u32 = 100;
+const SEED: u32 = 0;
+
+/// Document CID of the length of a CIDv1 (sha2-256, base32)
+fn document(i: u32) -> DocumentCID {
+	let mut cid = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd".to_vec();
+	cid.extend_from_slice(&i.to_le_bytes());
+	cid
+}
+
+fn salary<T: Trait>() -> BalanceOf<T> {
+	100u32.into()
+}
+
+/// Winning proposal that requires r roles
+fn proposal<T: Trait>(r: u32) -> ProposalWinnerOf<T> {
+	let metadata = ProposalMetadata {
+		title: b"Benchmark".to_vec(),
+		category: 0,
+		budget: 1_000_000u32.into(),
+		duration: 1_000u32.into(),
+		roles: (0..r).map(document).collect(),
+	};
+	ProposalWinner::new(Vec::new(), account("proposer", 0, SEED), document(0), Permill::one(), metadata)
+}
+
+/// Spawn a project that requires no roles
+fn spawn<T: Trait>() -> Result<ProjectID, &'static str> {
+	Ok(Module::<T>::do_spawn_project(proposal::<T>(0))?.id)
+}
+
+/// Let c candidates apply as project leader. Every candidate receives one vote.
+fn add_candidates<T: Trait>(project: ProjectID, c: u32) -> Result<(), &'static str> {
+	for i in 0..c {
+		let candidate: IdentityId<T> = account("candidate", i, SEED);
+		Module::<T>::do_application_project_leader(candidate.clone(), project, document(i))?;
+		Module::<T>::do_vote_project_leader(account("voter", i, SEED), candidate, project)?;
+	}
+
+	Ok(())
+}
+
+/// Spawn a project and elect leader as project leader
+fn spawn_with_leader<T: Trait>(leader: &IdentityId<T>) -> Result<ProjectID, &'static str> {
+	let project = spawn::<T>()?;
+	Module::<T>::do_application_project_leader(leader.clone(), project, document(0))?;
+	Module::<T>::do_vote_project_leader(account("voter", 0, SEED), leader.clone(), project)?;
+	Module::<T>::do_conclude_leader_election(project)?;
+	Ok(project)
+}
+
+/// Hire every worker for a position of its own
+fn hire<T: Trait>(project: ProjectID, leader: &IdentityId<T>, workers: &[IdentityId<T>])
+	-> Result<(), &'static str>
+{
+	for (i, worker) in workers.iter().enumerate() {
+		let position = document(i as u32);
+		Module::<T>::do_open_position(leader.clone(), project, position.clone())?;
+		Module::<T>::do_apply(worker.clone(), project, position.clone(), document(0))?;
+		Module::<T>::do_offer_applicant(leader.clone(), worker.clone(), project, position.clone(),
+			document(0), salary::<T>())?;
+		Module::<T>::do_accept_offer(worker.clone(), project, position, salary::<T>())?;
+	}
+
+	Ok(())
+}
+
+fn workers<T: Trait>(w: u32) -> Vec<IdentityId<T>> {
+	(0..w).map(|i| account("worker", i, SEED)).collect()
+}
+
+benchmarks! {
+	_ { }
+
+	spawn_project {
+		let r in 0 .. MAX_ROLES;
+		let proposal = proposal::<T>(r);
+	}: _(RawOrigin::Root, proposal)
+	verify {
+		assert!(<ProjectStorage<T>>::contains_key(0));
+	}
+
+	application_project_leader {
+		let c in 0 .. MAX_CANDIDATES;
+		let caller: T::AccountId = whitelisted_caller();
+		let project = spawn::<T>()?;
+		add_candidates::<T>(project, c)?;
+	}: _(RawOrigin::Signed(caller), project, document(c))
+	verify {
+		assert_eq!(<LeaderCandidates<T>>::decode_len(project), Some(c as usize + 1));
+	}
+
+	// Worst case: The last candidate receives the vote
+	vote_project_leader {
+		let c in 1 .. MAX_CANDIDATES;
+		let caller: T::AccountId = whitelisted_caller();
+		let project = spawn::<T>()?;
+		add_candidates::<T>(project, c)?;
+		let candidate: IdentityId<T> = account("candidate", c - 1, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), candidate, project)
+	verify {
+		assert!(<LeaderVotes<T>>::contains_key(project, T::Identity::get_identity_id(&caller)));
+	}
+
+	open_position {
+		let caller: T::AccountId = whitelisted_caller();
+		let project = spawn_with_leader::<T>(&T::Identity::get_identity_id(&caller))?;
+	}: _(RawOrigin::Signed(caller), project, document(0))
+	verify {
+		assert!(<OpenPositions<T>>::contains_key(project, document(0)));
+	}
+
+	apply {
+		let a in 0 .. MAX_APPLICATIONS;
+		let caller: T::AccountId = whitelisted_caller();
+		let leader: IdentityId<T> = account("leader", 0, SEED);
+		let project = spawn_with_leader::<T>(&leader)?;
+		Module::<T>::do_open_position(leader, project, document(0))?;
+
+		for i in 0..a {
+			Module::<T>::do_apply(account("applicant", i, SEED), project, document(0), document(i))?;
+		}
+	}: _(RawOrigin::Signed(caller), project, document(0), document(a))
+	verify {
+		assert_eq!(<Applications<T>>::decode_len(project, document(0)), Some(a as usize + 1));
+	}
+
+	// Worst case: The offered application replaces the last offer
+	offer_applicant {
+		let a in 0 .. MAX_APPLICATIONS;
+		let o in 1 .. MAX_APPLICATIONS;
+		let caller: T::AccountId = whitelisted_caller();
+		let leader = T::Identity::get_identity_id(&caller);
+		let project = spawn_with_leader::<T>(&leader)?;
+		Module::<T>::do_open_position(leader.clone(), project, document(0))?;
+		// The first o applicants already received an offer
+		let applicants: Vec<IdentityId<T>> = (0..o + a).map(|i| account("applicant", i, SEED)).collect();
+
+		for (i, applicant) in applicants.iter().enumerate() {
+			Module::<T>::do_apply(applicant.clone(), project, document(0), document(i as u32))?;
+
+			if (i as u32) < o {
+				Module::<T>::do_offer_applicant(leader.clone(), applicant.clone(), project, document(0),
+					document(i as u32), salary::<T>())?;
+			}
+		}
+
+		let applicant = applicants[o as usize - 1].clone();
+	}: _(RawOrigin::Signed(caller), applicant, project, document(0), document(o - 1), salary::<T>() + salary::<T>())
+	verify {
+		assert_eq!(<Offers<T>>::decode_len(project, document(0)), Some(o as usize));
+	}
+
+	// Worst case: The accepted offer is the last one
+	accept_offer {
+		let o in 1 .. MAX_APPLICATIONS;
+		let caller: T::AccountId = whitelisted_caller();
+		let leader: IdentityId<T> = account("leader", 0, SEED);
+		let project = spawn_with_leader::<T>(&leader)?;
+		Module::<T>::do_open_position(leader.clone(), project, document(0))?;
+		let mut applicants: Vec<IdentityId<T>> = (1..o).map(|i| account("applicant", i, SEED)).collect();
+		applicants.push(T::Identity::get_identity_id(&caller));
+
+		for (i, applicant) in applicants.iter().enumerate() {
+			Module::<T>::do_apply(applicant.clone(), project, document(0), document(i as u32))?;
+			Module::<T>::do_offer_applicant(leader.clone(), applicant.clone(), project, document(0),
+				document(i as u32), salary::<T>())?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), project, document(0), salary::<T>())
+	verify {
+		assert!(<Workers<T>>::contains_key(project, T::Identity::get_identity_id(&caller)));
+	}
+
+	start_project {
+		let caller: T::AccountId = whitelisted_caller();
+		let project = spawn_with_leader::<T>(&T::Identity::get_identity_id(&caller))?;
+	}: _(RawOrigin::Signed(caller), project)
+	verify {
+		assert_eq!(<ProjectStorage<T>>::get(project).map(|p| p.state), Some(ProjectState::Running));
+	}
+
+	complete_project {
+		let w in 0 .. MAX_WORKERS;
+		let caller: T::AccountId = whitelisted_caller();
+		let leader = T::Identity::get_identity_id(&caller);
+		let project = spawn_with_leader::<T>(&leader)?;
+		hire::<T>(project, &leader, &workers::<T>(w))?;
+		Module::<T>::do_start_project(leader, project)?;
+	}: _(RawOrigin::Signed(caller), project)
+	verify {
+		assert!(<ArchivedProjects<T>>::contains_key(project));
+	}
+
+	abandon_project {
+		let w in 0 .. MAX_WORKERS;
+		let leader: IdentityId<T> = account("leader", 0, SEED);
+		let project = spawn_with_leader::<T>(&leader)?;
+		hire::<T>(project, &leader, &workers::<T>(w))?;
+	}: _(RawOrigin::Root, project)
+	verify {
+		assert!(<ArchivedProjects<T>>::contains_key(project));
+	}
+
+	// Worst case: The project leader resigns and an election is scheduled
+	resign {
+		let caller: T::AccountId = whitelisted_caller();
+		let project = spawn_with_leader::<T>(&T::Identity::get_identity_id(&caller))?;
+	}: _(RawOrigin::Signed(caller), project)
+	verify {
+		assert!(<ProjectStorage<T>>::get(project).and_then(|p| p.project_leader)
+			.map_or(false, |pl| pl.leaves_at.is_some()));
+	}
+
+	// Worst case: The workers remove the project leader and the salaries of all workers are settled
+	vote_replace {
+		let w in 2 .. MAX_WORKERS;
+		let caller: T::AccountId = whitelisted_caller();
+		let leader: IdentityId<T> = account("leader", 0, SEED);
+		let project = spawn_with_leader::<T>(&leader)?;
+		let mut staff = workers::<T>(w - 1);
+		staff.push(T::Identity::get_identity_id(&caller));
+		hire::<T>(project, &leader, &staff)?;
+
+		for voter in staff.iter().take(w as usize - 1) {
+			<ReplaceVotes<T>>::append(project, &leader, voter);
+		}
+	}: _(RawOrigin::Signed(caller), leader.clone(), project)
+	verify {
+		assert_eq!(<ProjectStorage<T>>::get(project).map(|p| p.state), Some(ProjectState::Halted));
+	}
+
+	// Worst case: The project leader is elected for the first time
+	conclude_leader_election {
+		let c in 1 .. MAX_CANDIDATES;
+		let project = spawn::<T>()?;
+		add_candidates::<T>(project, c)?;
+	}: _(RawOrigin::Root, project)
+	verify {
+		assert_eq!(<ProjectStorage<T>>::get(project).map(|p| p.state), Some(ProjectState::Staffing));
+	}
+
+	payout {
+		let w in 0 .. MAX_WORKERS;
+		let leader: IdentityId<T> = account("leader", 0, SEED);
+		let project = spawn_with_leader::<T>(&leader)?;
+		hire::<T>(project, &leader, &workers::<T>(w))?;
+	}: _(RawOrigin::Root, project)
+
+	remove_worker {
+		let leader: IdentityId<T> = account("leader", 0, SEED);
+		let project = spawn_with_leader::<T>(&leader)?;
+		let worker: IdentityId<T> = account("worker", 0, SEED);
+		hire::<T>(project, &leader, &[worker.clone()])?;
+		Module::<T>::do_resign(worker.clone(), project)?;
+	}: _(RawOrigin::Root, project, worker.clone())
+	verify {
+		assert!(!<Workers<T>>::contains_key(project, &worker));
+	}
+}
@@ -18,14 +18,31 @@
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 
-use frame_support::{decl_event, decl_module, decl_storage, dispatch::{DispatchError, Vec},
-					traits::{Currency, ReservableCurrency}};
-use frame_system::{ensure_signed, ensure_root};
+use frame_support::{debug, decl_error, decl_event, decl_module, decl_storage, ensure, Parameter,
+					dispatch::{DispatchError, DispatchResult, Dispatchable, Vec},
+					traits::{Currency, Get, ReservableCurrency,
+						schedule::{Anon, DispatchTime, LOWEST_PRIORITY},
+					},
+					weights::Weight};
+use frame_support::sp_runtime::traits::{Saturating, UniqueSaturatedInto};
+use frame_system::{ensure_signed, ensure_root, RawOrigin::Root};
+use codec::Codec;
+// Fixed point arithmetic
+use sp_arithmetic::Permill;
 use pallet_community_identity::{IdentityId, IdentityLevel, ProofType, traits::PeerReviewedPhysicalIdentity};
+use pallet_community_treasury::traits::Treasury;
+use pallet_document_availability::traits::DocumentAvailability;
 use pallet_proposal_types::ProposalWinner;
-use crate::{traits::ProjectTrait, types::{DocumentCID, Project, ProjectID}};
+use crate::{migrations::Releases, traits::ProjectTrait,
+	types::{Application, DocumentCID, Offer, PositionId, Project, ProjectID, ProjectState, Worker}};
+/// Storage migrations
+pub mod migrations;
 pub mod traits;
 pub mod types;
+/// Weights of the extrinsics
+pub mod weights;
+
+pub use weights::WeightInfo;
 
 // use frame_system::ensure_root;
 // use frame_system::ensure_signed;
@@ -36,8 +53,13 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type ProjectOf<T> = Project<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>>;
+type WorkerOf<T> = Worker<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>>;
+type ProposalWinnerOf<T> = ProposalWinner<IdentityId<T>, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
@@ -49,12 +71,111 @@ pub trait Trait: frame_system::Trait {
 	/// Define Identity type. Must implement PeerReviewedPhysicalIdentity trait
 	type Identity: PeerReviewedPhysicalIdentity<ProofType, IdentityId = IdentityId<Self>,
 						IdentityLevel = IdentityLevel, Address = Self::AccountId>;
+
+	/// Define Treasury type. Salaries are paid from it. Must implement Treasury trait
+	type Treasury: Treasury<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+
+	/// Define the Scheduler type. Just implement (unamed) scheduling trait Anon
+	type Scheduler: Anon<Self::BlockNumber, Self::Proposal, Self::PalletsOrigin>;
+	type Proposal: Parameter + Dispatchable<Origin=Self::Origin> + From<Call<Self>>;
+	type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>> + Codec + Clone + Eq;
+
+	/// Define the availability checks of applications
+	type Documents: DocumentAvailability<DocumentCID = DocumentCID>;
+
+	// Parameters
+	/// How long can identified users apply and vote for a project leader? Value in blocks.
+	type LeaderElectionDuration: Get<Self::BlockNumber>;
+
+	/// How often are the salaries of the workers paid? Value in blocks.
+	type PayoutInterval: Get<Self::BlockNumber>;
+
+	/// How long does a dismissed worker stay in the project (and receive the salary)? Value in blocks.
+	type GracePeriod: Get<Self::BlockNumber>;
+
+	/// How long does a resigning worker stay in the project (and receive the salary)? Value in blocks.
+	type NoticePeriod: Get<Self::BlockNumber>;
+
+	/// How many votes (%) of the colleagues are required to dismiss a worker?
+	type ReplaceWorkerMinVotes: Get<Permill>;
+
+	/// How many votes (%) of the workers are required to remove the project leader?
+	type ReplaceLeaderMinVotes: Get<Permill>;
+
+	/// Weight information for extrinsics in this pallet
+	type WeightInfo: WeightInfo;
 }
 
 decl_event! {
-	pub enum Event<T> where PRJ = Project<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>> {
+	pub enum Event<T> where PRJ = ProjectOf<T>,
+							ID = IdentityId<T>,
+							Balance = BalanceOf<T>,
+							BlockNumber = <T as frame_system::Trait>::BlockNumber {
 		/// A new project has been spawned \[Project\]
 		ProjectSpawned(PRJ),
+		/// An identified user applied as project leader \[ProjectID, Applicant, Application\]
+		LeaderApplication(ProjectID, ID, DocumentCID),
+		/// An identified user voted for a project leader \[ProjectID, Voter, Candidate\]
+		LeaderVoted(ProjectID, ID, ID),
+		/// Nobody applied as project leader, the election was extended \[ProjectID, ElectionEnd\]
+		LeaderElectionExtended(ProjectID, BlockNumber),
+		/// A project leader was elected \[ProjectID, ProjectLeader\]
+		ProjectLeaderElected(ProjectID, ID),
+		/// The workers voted out the project leader, the project is halted \[ProjectID, ProjectLeader\]
+		ProjectLeaderRemoved(ProjectID, ID),
+		/// The project leader opened a position \[ProjectID, Position\]
+		PositionOpened(ProjectID, PositionId),
+		/// An identified user applied for a position \[ProjectID, Position, Applicant\]
+		PositionApplication(ProjectID, PositionId, ID),
+		/// The project leader offered a position to an applicant \[ProjectID, Position, Applicant, Salary\]
+		OfferMade(ProjectID, PositionId, ID, Balance),
+		/// An applicant accepted an offer \[ProjectID, Worker, Salary\]
+		WorkerHired(ProjectID, ID, Balance),
+		/// A worker voted to replace a colleague or the project leader \[ProjectID, Voter, Colleague\]
+		ReplaceVoted(ProjectID, ID, ID),
+		/// A salary was paid \[ProjectID, Worker, Salary\]
+		SalaryPaid(ProjectID, ID, Balance),
+		/// A worker was dismissed and leaves after the grace period \[ProjectID, Worker, LeavesAt\]
+		WorkerDismissed(ProjectID, ID, BlockNumber),
+		/// A worker resigned and leaves after the notice period \[ProjectID, Worker, LeavesAt\]
+		WorkerResigned(ProjectID, ID, BlockNumber),
+		/// The project leader resigned, a new project leader is being elected \[ProjectID, ProjectLeader\]
+		ProjectLeaderResigned(ProjectID, ID),
+		/// A worker left the project, the final salary was paid \[ProjectID, Worker, FinalSalary\]
+		WorkerRemoved(ProjectID, ID, Balance),
+		/// A project transited into a new state \[ProjectID, NewState\]
+		ProjectStateChanged(ProjectID, ProjectState),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Identity already applied
+		AlreadyApplied,
+		/// Identity already voted
+		AlreadyVoted,
+		/// Identity already works in the project
+		AlreadyWorker,
+		/// Application does not exist
+		ApplicationNotExistant,
+		/// A worker cannot vote to replace himself
+		CannotReplaceSelf,
+		/// Identity is not the project leader
+		NotProjectLeader,
+		/// Identity does not work in the project
+		NotAWorker,
+		/// Offer does not exist
+		OfferNotExistant,
+		/// Position is already open
+		PositionAlreadyOpen,
+		/// Position is not open
+		PositionNotOpen,
+		/// Project does not exist
+		ProjectNotExistant,
+		/// Worker is already leaving the project
+		WorkerAlreadyLeaving,
+		/// The operation requested cannot be executed because the project is in the wrong state.
+		WrongProjectState,
 	}
 }
 
@@ -62,53 +183,129 @@ decl_storage! {
 	trait Store for Module<T: Trait> as ProjectPallet {
 		pub ProjectNumber get(fn project_number): ProjectID = 0;
 		pub ProjectStorage get(fn project): map hasher(identity)
-			ProjectID => Option<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>> = None;
+			ProjectID => Option<ProjectOf<T>> = None;
+		/// Completed and abandoned projects
+		pub ArchivedProjects get(fn archived_project): map hasher(identity)
+			ProjectID => Option<ProjectOf<T>> = None;
+
+		/// Project -> Applications for the project leader (including votes)
+		pub LeaderCandidates get(fn leader_candidates): map hasher(identity)
+			ProjectID => Vec<Application<IdentityId<T>>> = Vec::new();
+		/// (Project, Voter) -> Project leader candidate the voter voted for
+		pub LeaderVotes get(fn leader_votes): double_map hasher(identity) ProjectID,
+			hasher(identity) IdentityId<T> => Option<IdentityId<T>> = None;
+
+		/// (Project, Worker) -> Worker
+		pub Workers get(fn worker): double_map hasher(identity) ProjectID,
+			hasher(identity) IdentityId<T> => Option<WorkerOf<T>> = None;
+		/// (Worker or project leader, Project) -> Does the identity work on the project?
+		pub WorkerProjects get(fn works_on): double_map hasher(identity) IdentityId<T>,
+			hasher(identity) ProjectID => bool = false;
+
+		/// (Project, Position) -> Block at which the position was opened
+		pub OpenPositions get(fn position_opened): double_map hasher(identity) ProjectID,
+			hasher(identity) PositionId => Option<T::BlockNumber> = None;
+		/// (Project, Position) -> Applications for the open position
+		pub Applications get(fn applications): double_map hasher(identity) ProjectID,
+			hasher(identity) PositionId => Vec<Application<IdentityId<T>>> = Vec::new();
+		/// (Project, Position) -> Offers made by the project leader
+		pub Offers get(fn offers): double_map hasher(identity) ProjectID,
+			hasher(identity) PositionId => Vec<Offer<BalanceOf<T>, IdentityId<T>>> = Vec::new();
+
+		/// (Project, Worker or project leader) -> Workers that voted to replace them
+		pub ReplaceVotes get(fn replace_votes): double_map hasher(identity) ProjectID,
+			hasher(identity) IdentityId<T> => Vec<IdentityId<T>> = Vec::new();
+
+		/// Layout of the storage, used to migrate it during runtime upgrades
+		pub StorageVersion get(fn storage_version) build(|_| Releases::V2_0_0): Releases;
+		/// Projects migrated by on_runtime_upgrade. Their next step is scheduled in the next block.
+		pub PendingSchedules get(fn pending_schedules): Vec<ProjectID>;
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
 		fn deposit_event() = default;
 
+		/// Migrate the storage to the current layout
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		/// Schedule the next step of the projects migrated by on_runtime_upgrade. The block number
+		/// is not updated yet during the upgrade.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let pending: Vec<ProjectID> = <PendingSchedules>::take();
+
+			for id in pending.iter() {
+				Self::schedule_migrated(*id, now);
+			}
+
+			T::DbWeight::get().reads_writes(1 + pending.len() as u64, 1 + pending.len() as u64)
+		}
+
+		/// How long can identified users apply and vote for a project leader? Value in blocks.
+		const LeaderElectionDuration: T::BlockNumber = T::LeaderElectionDuration::get();
+
+		/// How often are the salaries of the workers paid? Value in blocks.
+		const PayoutInterval: T::BlockNumber = T::PayoutInterval::get();
+
+		/// How long does a dismissed worker stay in the project (and receive the salary)? Value in blocks.
+		const GracePeriod: T::BlockNumber = T::GracePeriod::get();
+
+		/// How long does a resigning worker stay in the project (and receive the salary)? Value in blocks.
+		const NoticePeriod: T::BlockNumber = T::NoticePeriod::get();
+
+		/// How many votes (%) of the colleagues are required to dismiss a worker?
+		const ReplaceWorkerMinVotes: Permill = T::ReplaceWorkerMinVotes::get();
+
+		/// How many votes (%) of the workers are required to remove the project leader?
+		const ReplaceLeaderMinVotes: Permill = T::ReplaceLeaderMinVotes::get();
+
 		/// As root, spawn a project from a proposal
-		#[weight = 10_000]
-		fn spawn_project(origin, proposal: ProposalWinner<IdentityId<T>>) {
+		#[weight = T::WeightInfo::spawn_project(proposal.metadata.roles.len() as u32)]
+		fn spawn_project(origin, proposal: ProposalWinnerOf<T>) {
 			ensure_root(origin)?;
 			Self::do_spawn_project(proposal)?;
 		}
 
 		/// As an identified user, apply as project leader
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::application_project_leader(<Module<T>>::candidate_count(*project))]
 		fn application_project_leader(origin, project: ProjectID, application: DocumentCID) {
 			let caller = ensure_signed(origin)?;
 			Self::do_application_project_leader(T::Identity::get_identity_id(&caller), project, application)?;
 		}
 
 		/// As an identified user, Vote for project leader
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::vote_project_leader(<Module<T>>::candidate_count(*project))]
 		fn vote_project_leader(origin, pl: IdentityId<T>, project: ProjectID) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_project_leader(T::Identity::get_identity_id(&caller), pl, project)?;
 		}
 
 		/// As a project leader, open positions
-		#[weight = 10_000]
-		fn open_position(origin, project: ProjectID, position: DocumentCID) {
+		#[weight = T::WeightInfo::open_position()]
+		fn open_position(origin, project: ProjectID, position: PositionId) {
 			let caller = ensure_signed(origin)?;
 			Self::do_open_position(T::Identity::get_identity_id(&caller), project, position)?;
 		}
 
 		/// As an identified user, apply for a position
-		#[weight = 10_000]
-		fn apply(origin, project: ProjectID, position: DocumentCID, application: DocumentCID) {
+		#[weight = T::WeightInfo::apply(<Applications<T>>::decode_len(*project, position).unwrap_or(0) as u32)]
+		fn apply(origin, project: ProjectID, position: PositionId, application: DocumentCID) {
 			let caller = ensure_signed(origin)?;
 			Self::do_apply(T::Identity::get_identity_id(&caller), project, position, application)?;
 		}
 
 		/// As a project leader, accept application and offer salary
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::offer_applicant(
+			<Applications<T>>::decode_len(*project, position).unwrap_or(0) as u32,
+			<Offers<T>>::decode_len(*project, position).unwrap_or(0) as u32,
+		)]
 		fn offer_applicant(origin, applicant: IdentityId<T>, project: ProjectID,
-			position: DocumentCID, application: DocumentCID, salary: BalanceOf<T>)
+			position: PositionId, application: DocumentCID, salary: BalanceOf<T>)
 		{
 			let caller = ensure_signed(origin)?;
 			Self::do_offer_applicant(T::Identity::get_identity_id(&caller), applicant,
@@ -116,107 +313,712 @@ decl_module! {
 		}
 
 		/// As an applicant, accept an offer	
-		#[weight = 10_000]
-		fn accept_offer(origin, project: ProjectID, position: DocumentCID, salary: BalanceOf<T>) {
+		#[weight = T::WeightInfo::accept_offer(<Offers<T>>::decode_len(*project, position).unwrap_or(0) as u32)]
+		fn accept_offer(origin, project: ProjectID, position: PositionId, salary: BalanceOf<T>) {
 			let caller = ensure_signed(origin)?;
 			Self::do_accept_offer(T::Identity::get_identity_id(&caller), project, position, salary)?;
 		}
 
-		/// As a participant, vote to replace a colleague
-		#[weight = 10_000]
+		/// As a project leader, start the project after staffing it
+		#[weight = T::WeightInfo::start_project()]
+		fn start_project(origin, project: ProjectID) {
+			let caller = ensure_signed(origin)?;
+			Self::do_start_project(T::Identity::get_identity_id(&caller), project)?;
+		}
+
+		/// As a project leader, declare the project as completed
+		#[weight = T::WeightInfo::complete_project(<Module<T>>::worker_count(*project))]
+		fn complete_project(origin, project: ProjectID) {
+			let caller = ensure_signed(origin)?;
+			Self::do_complete_project(T::Identity::get_identity_id(&caller), project)?;
+		}
+
+		/// As root, abandon a project
+		#[weight = T::WeightInfo::abandon_project(<Module<T>>::worker_count(*project))]
+		fn abandon_project(origin, project: ProjectID) {
+			ensure_root(origin)?;
+			Self::do_abandon_project(project)?;
+		}
+
+		/// As a worker or project leader, resign from the project
+		#[weight = T::WeightInfo::resign()]
+		fn resign(origin, project: ProjectID) {
+			let caller = ensure_signed(origin)?;
+			Self::do_resign(T::Identity::get_identity_id(&caller), project)?;
+		}
+
+		/// As a participant, vote to replace a colleague or the project leader
+		#[weight = T::WeightInfo::vote_replace(<Module<T>>::worker_count(*project))]
 		fn vote_replace(origin, colleague: IdentityId<T>, project: ProjectID) {
 			let caller = ensure_signed(origin)?;
 			Self::do_vote_replace(colleague, T::Identity::get_identity_id(&caller), project)?;
 		}
+
+		/// As root, conclude the election of the project leader (scheduled)
+		#[weight = T::WeightInfo::conclude_leader_election(<Module<T>>::candidate_count(*project))]
+		fn conclude_leader_election(origin, project: ProjectID) {
+			ensure_root(origin)?;
+			Self::do_conclude_leader_election(project)?;
+		}
+
+		/// As root, pay the salaries of the project leader and the workers (scheduled)
+		#[weight = T::WeightInfo::payout(<Module<T>>::worker_count(*project))]
+		fn payout(origin, project: ProjectID) {
+			ensure_root(origin)?;
+			Self::do_payout(project)?;
+		}
+
+		/// As root, remove a leaving worker after the grace or notice period (scheduled)
+		#[weight = T::WeightInfo::remove_worker()]
+		fn remove_worker(origin, project: ProjectID, worker: IdentityId<T>) {
+			ensure_root(origin)?;
+			Self::do_remove_worker(project, worker)?;
+		}
 	}
 }
 
 impl<T: Trait> Module<T> {
 	/// As root, spawn a project from a proposal
-	fn do_spawn_project(proposal: ProposalWinner<IdentityId<T>>) 
-		-> Result<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>, DispatchError>
-	{
+	fn do_spawn_project(proposal: ProposalWinnerOf<T>) -> Result<ProjectOf<T>, DispatchError> {
 		let pn: ProjectID = <ProjectNumber>::get();
-		let project = Project::new(pn, proposal);
+		let now = frame_system::Module::<T>::block_number();
+		let election_end = now + T::LeaderElectionDuration::get();
+		Self::schedule(election_end, Call::conclude_leader_election(pn))?;
+		// The requested budget and duration of the proposal are approved with the project
+		let mut project = Project::new(pn, proposal);
+		project.budget = project.proposal.metadata.budget;
+		project.deadline = now + project.proposal.metadata.duration;
 		ProjectStorage::<T>::insert(pn, &project);
 		ProjectNumber::put(pn+1);
 		Self::deposit_event(Event::<T>::ProjectSpawned(project.clone()));
+
+		// Open a position for every role the proposal requires
+		for role in project.proposal.metadata.roles.iter() {
+			if !<OpenPositions<T>>::contains_key(pn, role) {
+				<OpenPositions<T>>::insert(pn, role, now);
+				Self::deposit_event(Event::<T>::PositionOpened(pn, role.clone()));
+			}
+		}
+
 		Ok(project)
 	}
 
 	/// As an identified user, apply as project leader
-	fn do_application_project_leader(_who: IdentityId<T>, _project: ProjectID, _application: DocumentCID)
+	fn do_application_project_leader(who: IdentityId<T>, project: ProjectID, application: DocumentCID)
 		-> Result<(), DispatchError>
 	{
+		let prj = Self::load_project(project)?;
+		ensure!(Self::leader_election_open(&prj), Error::<T>::WrongProjectState);
+		let mut candidates = <LeaderCandidates<T>>::get(project);
+		ensure!(!candidates.iter().any(|c| c.applicant == who), Error::<T>::AlreadyApplied);
+		candidates.push(Application::new(who.clone(), Vec::new(), application.clone()));
+		<LeaderCandidates<T>>::insert(project, candidates);
+		T::Documents::register(&application);
+		Self::deposit_event(Event::<T>::LeaderApplication(project, who, application));
 		Ok(())
 	}
 
 	/// As an identified user, Vote for project leader
-	fn do_vote_project_leader(_voter: IdentityId<T>, _pl: IdentityId<T>, _project: ProjectID)
+	fn do_vote_project_leader(voter: IdentityId<T>, pl: IdentityId<T>, project: ProjectID)
 		-> Result<(), DispatchError>
 	{
+		let prj = Self::load_project(project)?;
+		ensure!(Self::leader_election_open(&prj), Error::<T>::WrongProjectState);
+		ensure!(!<LeaderVotes<T>>::contains_key(project, &voter), Error::<T>::AlreadyVoted);
+		let mut candidates = <LeaderCandidates<T>>::get(project);
+		let candidate = candidates.iter_mut().find(|c| c.applicant == pl)
+			.ok_or(Error::<T>::ApplicationNotExistant)?;
+		candidate.votes = candidate.votes.saturating_add(1);
+		<LeaderCandidates<T>>::insert(project, candidates);
+		<LeaderVotes<T>>::insert(project, &voter, &pl);
+		Self::deposit_event(Event::<T>::LeaderVoted(project, voter, pl));
 		Ok(())
 	}
 
 	/// As a project leader, open positions
-	fn do_open_position(_pl: IdentityId<T>, _project: ProjectID, _position: DocumentCID)
+	fn do_open_position(pl: IdentityId<T>, project: ProjectID, position: PositionId)
 		-> Result<(), DispatchError>
 	{
+		let prj = Self::load_project(project)?;
+		ensure!(prj.state.is_active(), Error::<T>::WrongProjectState);
+		ensure!(Self::is_project_leader(&prj, &pl), Error::<T>::NotProjectLeader);
+		ensure!(!<OpenPositions<T>>::contains_key(project, &position), Error::<T>::PositionAlreadyOpen);
+		<OpenPositions<T>>::insert(project, &position, frame_system::Module::<T>::block_number());
+		Self::deposit_event(Event::<T>::PositionOpened(project, position));
 		Ok(())
 	}
 
 	/// As an identified user, apply for a position
-	fn do_apply(_applicant: IdentityId<T>, _project: ProjectID, _position: DocumentCID, _application: DocumentCID)
+	fn do_apply(applicant: IdentityId<T>, project: ProjectID, position: PositionId, application: DocumentCID)
 		-> Result<(), DispatchError>
 	{
+		let prj = Self::load_project(project)?;
+		ensure!(prj.state.is_active(), Error::<T>::WrongProjectState);
+		ensure!(<OpenPositions<T>>::contains_key(project, &position), Error::<T>::PositionNotOpen);
+		ensure!(!Self::is_worker(project, &applicant) && !Self::is_project_leader(&prj, &applicant),
+			Error::<T>::AlreadyWorker
+		);
+		let mut applications = <Applications<T>>::get(project, &position);
+		ensure!(!applications.iter().any(|a| a.applicant == applicant), Error::<T>::AlreadyApplied);
+		T::Documents::register(&application);
+		applications.push(Application::new(applicant.clone(), position.clone(), application));
+		<Applications<T>>::insert(project, &position, applications);
+		Self::deposit_event(Event::<T>::PositionApplication(project, position, applicant));
 		Ok(())
 	}
 
 	/// As a project leader, accept application and offer salary
-	fn do_offer_applicant(_pl: IdentityId<T>, _applicant: IdentityId<T>, _project: ProjectID,
-		_position: DocumentCID, _application: DocumentCID, _salary: BalanceOf<T>) -> Result<(), DispatchError>
+	fn do_offer_applicant(pl: IdentityId<T>, applicant: IdentityId<T>, project: ProjectID,
+		position: PositionId, application: DocumentCID, salary: BalanceOf<T>) -> Result<(), DispatchError>
 	{
+		let prj = Self::load_project(project)?;
+		ensure!(prj.state.is_active(), Error::<T>::WrongProjectState);
+		ensure!(Self::is_project_leader(&prj, &pl), Error::<T>::NotProjectLeader);
+		ensure!(<Applications<T>>::get(project, &position).iter().any(|a| {
+				a.applicant == applicant && a.application == application
+			}), Error::<T>::ApplicationNotExistant
+		);
+		<Offers<T>>::mutate(project, &position, |offers| {
+			// A new offer replaces the previous offer for the same applicant
+			offers.retain(|o| o.applicant != applicant);
+			offers.push(Offer::new(applicant.clone(), position.clone(), application, salary));
+		});
+		Self::deposit_event(Event::<T>::OfferMade(project, position, applicant, salary));
 		Ok(())
 	}
 
 	/// As an applicant, accept an offer	
-	fn do_accept_offer(_applicant: IdentityId<T>, _project: ProjectID, _position: DocumentCID, _salary: BalanceOf<T>)
+	fn do_accept_offer(applicant: IdentityId<T>, project: ProjectID, position: PositionId, salary: BalanceOf<T>)
 		-> Result<(), DispatchError>
 	{
+		let mut prj = Self::load_project(project)?;
+		ensure!(prj.state.is_active(), Error::<T>::WrongProjectState);
+		ensure!(<OpenPositions<T>>::contains_key(project, &position), Error::<T>::PositionNotOpen);
+		ensure!(!Self::is_worker(project, &applicant), Error::<T>::AlreadyWorker);
+		ensure!(<Offers<T>>::get(project, &position).iter().any(|o| {
+				o.applicant == applicant && o.salary == salary
+			}), Error::<T>::OfferNotExistant
+		);
+		let now = frame_system::Module::<T>::block_number();
+		// The position is filled, remove remaining offers and applications for it
+		<OpenPositions<T>>::remove(project, &position);
+		<Offers<T>>::remove(project, &position);
+		Self::forget_applications(<Applications<T>>::take(project, &position));
+		<Workers<T>>::insert(project, &applicant, Worker::new(applicant.clone(), position, salary, now));
+		<WorkerProjects<T>>::insert(&applicant, project, true);
+		prj.worker_count = prj.worker_count.saturating_add(1);
+		<ProjectStorage<T>>::insert(project, prj);
+		Self::deposit_event(Event::<T>::WorkerHired(project, applicant, salary));
 		Ok(())
 	}
 
-	/// As a participant, vote to replace a colleague
-	fn do_vote_replace(_colleague: IdentityId<T>, _worker: IdentityId<T>, _project: ProjectID)
+	/// As a project leader, start the project after staffing it
+	fn do_start_project(pl: IdentityId<T>, project: ProjectID) -> Result<(), DispatchError> {
+		let mut prj = Self::load_project(project)?;
+		ensure!(Self::is_project_leader(&prj, &pl), Error::<T>::NotProjectLeader);
+		Self::transit(&mut prj, ProjectState::Running)?;
+		<ProjectStorage<T>>::insert(project, prj);
+		Ok(())
+	}
+
+	/// As a project leader, declare the project as completed
+	fn do_complete_project(pl: IdentityId<T>, project: ProjectID) -> Result<(), DispatchError> {
+		let prj = Self::load_project(project)?;
+		ensure!(Self::is_project_leader(&prj, &pl), Error::<T>::NotProjectLeader);
+		Self::archive(prj, ProjectState::Completed)
+	}
+
+	/// As root, abandon a project
+	fn do_abandon_project(project: ProjectID) -> Result<(), DispatchError> {
+		let prj = Self::load_project(project)?;
+		Self::archive(prj, ProjectState::Abandoned)
+	}
+
+	/// As a worker, resign and leave the project after the notice period.
+	/// As the project leader, resign and stay until a new project leader is elected.
+	fn do_resign(who: IdentityId<T>, project: ProjectID) -> Result<(), DispatchError> {
+		let mut prj = Self::load_project(project)?;
+		ensure!(prj.state.is_active(), Error::<T>::WrongProjectState);
+		let now = frame_system::Module::<T>::block_number();
+
+		if let Some(leader) = prj.project_leader.as_mut() {
+			if leader.worker == who {
+				ensure!(leader.leaves_at.is_none(), Error::<T>::WorkerAlreadyLeaving);
+				let election_end = now + T::LeaderElectionDuration::get();
+				Self::schedule(election_end, Call::conclude_leader_election(project))?;
+				leader.leaves_at = Some(election_end);
+				Self::clear_leader_election(project);
+				<ProjectStorage<T>>::insert(project, prj);
+				Self::deposit_event(Event::<T>::ProjectLeaderResigned(project, who));
+				return Ok(());
+			}
+		}
+
+		let mut worker = <Workers<T>>::get(project, &who).ok_or(Error::<T>::NotAWorker)?;
+		ensure!(worker.leaves_at.is_none(), Error::<T>::WorkerAlreadyLeaving);
+		let leaves_at = now + T::NoticePeriod::get();
+		Self::schedule(leaves_at, Call::remove_worker(project, who.clone()))?;
+		worker.leaves_at = Some(leaves_at);
+		<Workers<T>>::insert(project, &who, worker);
+		Self::deposit_event(Event::<T>::WorkerResigned(project, who, leaves_at));
+		Ok(())
+	}
+
+	/// As a participant, vote to replace a colleague.
+	/// The project leader dismisses workers directly, workers have to reach a threshold
+	/// to dismiss a colleague or to remove the project leader.
+	fn do_vote_replace(colleague: IdentityId<T>, voter: IdentityId<T>, project: ProjectID)
 		-> Result<(), DispatchError>
 	{
+		let mut prj = Self::load_project(project)?;
+		ensure!(prj.state.is_active(), Error::<T>::WrongProjectState);
+		ensure!(colleague != voter, Error::<T>::CannotReplaceSelf);
+
+		if Self::is_project_leader(&prj, &colleague) {
+			Self::ensure_active_worker(project, &voter)?;
+			let vote_ratio = Self::add_replace_vote(&prj, &colleague, voter)?;
+
+			if vote_ratio >= T::ReplaceLeaderMinVotes::get() {
+				Self::remove_project_leader(&mut prj)?;
+				<ProjectStorage<T>>::insert(project, prj);
+			}
+
+			return Ok(());
+		}
+
+		let mut worker = <Workers<T>>::get(project, &colleague).ok_or(Error::<T>::NotAWorker)?;
+		ensure!(worker.leaves_at.is_none(), Error::<T>::WorkerAlreadyLeaving);
+
+		if Self::is_project_leader(&prj, &voter) {
+			Self::dismiss_worker(project, &mut worker)?;
+		} else {
+			Self::ensure_active_worker(project, &voter)?;
+			let vote_ratio = Self::add_replace_vote(&prj, &colleague, voter)?;
+
+			if vote_ratio >= T::ReplaceWorkerMinVotes::get() {
+				Self::dismiss_worker(project, &mut worker)?;
+			}
+		}
+
 		Ok(())
 	}
 
-	/// Get project
-	fn do_get_project(project: ProjectID) -> 
-		Result<Option<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>>, DispatchError>
+	/// Elect the candidate with the most votes as project leader. On a tie the candidate
+	/// that applied first is elected. If no candidate received a vote, the election is
+	/// extended (a resigning project leader stays until then).
+	fn do_conclude_leader_election(project: ProjectID) -> DispatchResult {
+		let mut prj = match <ProjectStorage<T>>::get(project) {
+			Some(prj) => prj,
+			None => return Ok(()),
+		};
+
+		if !Self::leader_election_open(&prj) {
+			return Ok(());
+		}
+
+		let now = frame_system::Module::<T>::block_number();
+		let candidates = <LeaderCandidates<T>>::get(project);
+		let mut winner: Option<&Application<IdentityId<T>>> = None;
+
+		// Candidates are stored in the order of their application
+		for candidate in candidates.iter().filter(|c| c.votes > 0) {
+			if winner.map_or(true, |w| candidate.votes > w.votes) {
+				winner = Some(candidate);
+			}
+		}
+
+		let winner = match winner {
+			Some(w) => w,
+			None => {
+				let election_end = now + T::LeaderElectionDuration::get();
+
+				if let Some(leader) = prj.project_leader.as_mut() {
+					leader.leaves_at = Some(election_end);
+					<ProjectStorage<T>>::insert(project, &prj);
+				}
+
+				Self::schedule(election_end, Call::conclude_leader_election(project))?;
+				Self::deposit_event(Event::<T>::LeaderElectionExtended(project, election_end));
+				return Ok(());
+			}
+		};
+
+		// Start the payroll once the first project leader is elected
+		if prj.state == ProjectState::Spawned {
+			Self::schedule(now + T::PayoutInterval::get(), Call::payout(project))?;
+		}
+
+		// Settle the salary of the resigning project leader
+		if let Some(mut leader) = prj.project_leader.take() {
+			Self::settle_salary(prj.id, prj.state, &mut leader, now);
+			<ReplaceVotes<T>>::remove(project, &leader.worker);
+			<WorkerProjects<T>>::remove(&leader.worker, project);
+		}
+
+		// A worker that is elected as project leader leaves the position
+		if let Some(worker) = <Workers<T>>::get(project, &winner.applicant) {
+			Self::offboard_worker(&mut prj, worker, now);
+		}
+
+		// TODO: Derive the salary of the project leader from the application
+		prj.project_leader = Some(Worker::new(winner.applicant.clone(), winner.application.clone(),
+			Default::default(), now));
+		<WorkerProjects<T>>::insert(&winner.applicant, project, true);
+
+		// After a halt the new project leader reviews the staff and restarts the project
+		if prj.state.is_leader_election() {
+			Self::transit(&mut prj, ProjectState::Staffing)?;
+		}
+
+		Self::clear_leader_election(project);
+		<ProjectStorage<T>>::insert(project, prj);
+		Self::deposit_event(Event::<T>::ProjectLeaderElected(project, winner.applicant.clone()));
+		Ok(())
+	}
+
+	/// Pay every project leader and worker (including leaving workers within the grace or notice period)
+	/// the salary since the last payout. No salaries are paid while the project is halted.
+	fn do_payout(project: ProjectID) -> DispatchResult {
+		let mut prj = match <ProjectStorage<T>>::get(project) {
+			Some(prj) => prj,
+			None => return Ok(()),
+		};
+
+		let now = frame_system::Module::<T>::block_number();
+		let state = prj.state;
+
+		if let Some(leader) = prj.project_leader.as_mut() {
+			Self::settle_salary(project, state, leader, now);
+			<ProjectStorage<T>>::insert(project, &prj);
+		}
+
+		let workers: Vec<WorkerOf<T>> = <Workers<T>>::iter_prefix_values(project).collect();
+
+		for mut worker in workers {
+			Self::settle_salary(project, state, &mut worker, now);
+			<Workers<T>>::insert(project, &worker.worker, &worker);
+		}
+
+		Self::schedule(now + T::PayoutInterval::get(), Call::payout(project))
+	}
+
+	/// Remove a leaving worker from the project, pay the final salary and reopen the position
+	fn do_remove_worker(project: ProjectID, worker: IdentityId<T>) -> DispatchResult {
+		let mut prj = match <ProjectStorage<T>>::get(project) {
+			Some(prj) => prj,
+			None => return Ok(()),
+		};
+
+		if let Some(worker) = <Workers<T>>::get(project, &worker) {
+			if worker.leaves_at.is_some() {
+				Self::offboard_worker(&mut prj, worker, frame_system::Module::<T>::block_number());
+				<ProjectStorage<T>>::insert(project, prj);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Pay the final salary, remove the worker and reopen the position.
+	/// The caller has to store the project.
+	fn offboard_worker(prj: &mut ProjectOf<T>, mut worker: WorkerOf<T>, now: T::BlockNumber) {
+		let final_salary = Self::settle_salary(prj.id, prj.state, &mut worker, now);
+		<Workers<T>>::remove(prj.id, &worker.worker);
+		<WorkerProjects<T>>::remove(&worker.worker, prj.id);
+		<ReplaceVotes<T>>::remove(prj.id, &worker.worker);
+		prj.worker_count = prj.worker_count.saturating_sub(1);
+
+		if !<OpenPositions<T>>::contains_key(prj.id, &worker.job_description) {
+			<OpenPositions<T>>::insert(prj.id, &worker.job_description, now);
+		}
+
+		Self::deposit_event(Event::<T>::WorkerRemoved(prj.id, worker.worker, final_salary));
+	}
+
+	/// Pay the salary pro rata by block since the last payout. Returns the paid salary.
+	/// If the treasury rejects the payout, the salary keeps accruing until the next payout.
+	fn settle_salary(project: ProjectID, state: ProjectState, worker: &mut WorkerOf<T>, now: T::BlockNumber)
+		-> BalanceOf<T>
 	{
-		Ok(<ProjectStorage<T>>::get(project))
+		let elapsed: u32 = now.saturating_sub(worker.paid_until).unique_saturated_into();
+		let interval: u32 = T::PayoutInterval::get().unique_saturated_into();
+		let mut salary: BalanceOf<T> = worker.salary;
+
+		if !state.is_active() {
+			worker.paid_until = now;
+			return Default::default();
+		}
+
+		if interval > 0 {
+			let elapsed: BalanceOf<T> = elapsed.into();
+			let interval: BalanceOf<T> = interval.into();
+			salary = worker.salary.saturating_mul(elapsed) / interval;
+		}
+
+		// The treasury queues payouts it cannot cover, it only fails if the transfer is invalid
+		if let Err(e) = T::Treasury::pay(&T::Identity::get_address(&worker.worker), salary) {
+			debug::warn!("Unable to pay the salary of {:?} in project {}: {:?}", worker.worker, project, e);
+			return Default::default();
+		}
+
+		worker.paid_until = now;
+		Self::deposit_event(Event::<T>::SalaryPaid(project, worker.worker.clone(), salary));
+		salary
 	}
 
-	/// Get all projects
-	fn do_get_projects() -> Result<Vec<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>>, DispatchError> {
-		let mut result: Vec<Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>> = Vec::new();
+	/// Add a vote to replace a colleague and return the share of eligible workers that voted for it
+	fn add_replace_vote(prj: &ProjectOf<T>, colleague: &IdentityId<T>, voter: IdentityId<T>)
+		-> Result<Permill, DispatchError>
+	{
+		let mut voters = <ReplaceVotes<T>>::get(prj.id, colleague);
+		ensure!(!voters.contains(&voter), Error::<T>::AlreadyVoted);
+		voters.push(voter.clone());
+		Self::deposit_event(Event::<T>::ReplaceVoted(prj.id, voter, colleague.clone()));
+		// Only votes from current workers of the project count, leaving workers are not eligible
+		voters.retain(|v| Self::is_active_worker(prj.id, v));
+		let mut eligible = <Workers<T>>::iter_prefix_values(prj.id).filter(|w| w.leaves_at.is_none()).count() as u32;
+		let mut vote_ratio = Permill::zero();
+
+		if Self::is_active_worker(prj.id, colleague) {
+			eligible = eligible.saturating_sub(1);
+		}
 
-		for (_, project) in <ProjectStorage<T>>::iter() {
-			result.push(project);
+		if eligible > 0 {
+			vote_ratio = Permill::from_rational_approximation(voters.len() as u32, eligible);
 		}
 
-		Ok(result)
+		<ReplaceVotes<T>>::insert(prj.id, colleague, voters);
+		Ok(vote_ratio)
+	}
+
+	/// Dismiss a worker. The worker stays in the project until the grace period has passed
+	fn dismiss_worker(project: ProjectID, worker: &mut WorkerOf<T>) -> DispatchResult {
+		let leaves_at = frame_system::Module::<T>::block_number() + T::GracePeriod::get();
+		Self::schedule(leaves_at, Call::remove_worker(project, worker.worker.clone()))?;
+		worker.leaves_at = Some(leaves_at);
+		<Workers<T>>::insert(project, &worker.worker, &*worker);
+		<ReplaceVotes<T>>::remove(project, &worker.worker);
+		Self::deposit_event(Event::<T>::WorkerDismissed(project, worker.worker.clone(), leaves_at));
+		Ok(())
+	}
+
+	/// Remove the project leader, halt the project and open the applications for a new project leader.
+	/// The salaries accrued until the halt are paid, the payroll pauses while the project is halted.
+	fn remove_project_leader(prj: &mut ProjectOf<T>) -> DispatchResult {
+		let now = frame_system::Module::<T>::block_number();
+
+		// The election for the successor of a resigning project leader is already scheduled
+		if !Self::leader_election_open(prj) {
+			Self::schedule(now + T::LeaderElectionDuration::get(), Call::conclude_leader_election(prj.id))?;
+			Self::clear_leader_election(prj.id);
+		}
+
+		if let Some(mut leader) = prj.project_leader.take() {
+			Self::settle_salary(prj.id, prj.state, &mut leader, now);
+			<ReplaceVotes<T>>::remove(prj.id, &leader.worker);
+			<WorkerProjects<T>>::remove(&leader.worker, prj.id);
+			Self::deposit_event(Event::<T>::ProjectLeaderRemoved(prj.id, leader.worker));
+		}
+
+		// Includes the workers who leave after the grace or notice period while the project is halted
+		let workers: Vec<WorkerOf<T>> = <Workers<T>>::iter_prefix_values(prj.id).collect();
+
+		for mut worker in workers {
+			Self::settle_salary(prj.id, prj.state, &mut worker, now);
+			<Workers<T>>::insert(prj.id, &worker.worker, &worker);
+		}
+
+		Self::transit(prj, ProjectState::Halted)
+	}
+
+	/// Continue the payroll or the election of the first project leader of a migrated project
+	fn schedule_migrated(project: ProjectID, now: T::BlockNumber) {
+		let scheduled = match <ProjectStorage<T>>::get(project) {
+			Some(prj) if prj.project_leader.is_some() =>
+				Self::schedule(now + T::PayoutInterval::get(), Call::payout(project)),
+			Some(_) => Self::schedule(now + T::LeaderElectionDuration::get(), Call::conclude_leader_election(project)),
+			None => return,
+		};
+
+		if scheduled.is_err() {
+			debug::warn!("Unable to schedule the next step of migrated project {}", project);
+		}
+	}
+
+	/// Schedule a call of this pallet with root origin
+	fn schedule(at: T::BlockNumber, call: Call<T>) -> DispatchResult {
+		if T::Scheduler::schedule(
+			DispatchTime::At(at),
+			None,
+			LOWEST_PRIORITY,
+			Root.into(),
+			call.into(),
+		).is_err() {
+			return Err(DispatchError::Other("Setting anonymous scheduler for project pallet failed"));
+		}
+
+		Ok(())
+	}
+
+	fn load_project(project: ProjectID) -> Result<ProjectOf<T>, DispatchError> {
+		<ProjectStorage<T>>::get(project).ok_or_else(|| Error::<T>::ProjectNotExistant.into())
+	}
+
+	/// Transit a project into the next state if the transit is allowed.
+	/// The caller has to store the project.
+	fn transit(prj: &mut ProjectOf<T>, next: ProjectState) -> DispatchResult {
+		ensure!(prj.state.can_transit(next), Error::<T>::WrongProjectState);
+		prj.state = next;
+		Self::deposit_event(Event::<T>::ProjectStateChanged(prj.id, next));
+		Ok(())
+	}
+
+	/// Pay the final salaries, transit a project into a final state and move it into the archive
+	fn archive(mut prj: ProjectOf<T>, next: ProjectState) -> DispatchResult {
+		ensure!(prj.state.can_transit(next), Error::<T>::WrongProjectState);
+		let now = frame_system::Module::<T>::block_number();
+		let state = prj.state;
+
+		if let Some(leader) = prj.project_leader.as_mut() {
+			Self::settle_salary(prj.id, state, leader, now);
+			<WorkerProjects<T>>::remove(&leader.worker, prj.id);
+		}
+
+		for (id, mut worker) in <Workers<T>>::drain_prefix(prj.id) {
+			Self::settle_salary(prj.id, state, &mut worker, now);
+			<WorkerProjects<T>>::remove(&id, prj.id);
+		}
+
+		prj.worker_count = 0;
+		Self::transit(&mut prj, next)?;
+		<ProjectStorage<T>>::remove(prj.id);
+		Self::clear_leader_election(prj.id);
+		<OpenPositions<T>>::remove_prefix(prj.id);
+		<Applications<T>>::drain_prefix(prj.id).for_each(|(_, applications)| Self::forget_applications(applications));
+		<Offers<T>>::remove_prefix(prj.id);
+		<ReplaceVotes<T>>::remove_prefix(prj.id);
+		<ArchivedProjects<T>>::insert(prj.id, prj);
+		Ok(())
+	}
+
+	/// Remove the candidates and votes of the leader election
+	fn clear_leader_election(project: ProjectID) {
+		Self::forget_applications(<LeaderCandidates<T>>::take(project));
+		<LeaderVotes<T>>::remove_prefix(project);
+	}
+
+	/// The availability of removed applications is not checked anymore
+	fn forget_applications(applications: Vec<Application<IdentityId<T>>>) {
+		for application in applications.iter() {
+			T::Documents::forget(&application.application);
+		}
+	}
+
+	/// Is a project leader being elected (after spawn, after a halt or because the project leader resigned)?
+	fn leader_election_open(prj: &ProjectOf<T>) -> bool {
+		prj.state.is_leader_election() || prj.project_leader.as_ref().map_or(false, |pl| pl.leaves_at.is_some())
+	}
+
+	fn is_project_leader(prj: &ProjectOf<T>, id: &IdentityId<T>) -> bool {
+		prj.project_leader.as_ref().map_or(false, |pl| pl.worker == *id)
+	}
+
+	fn is_worker(project: ProjectID, id: &IdentityId<T>) -> bool {
+		<Workers<T>>::contains_key(project, id)
+	}
+
+	/// Is the identity a worker of the project that is not leaving?
+	fn is_active_worker(project: ProjectID, id: &IdentityId<T>) -> bool {
+		<Workers<T>>::get(project, id).map_or(false, |w| w.leaves_at.is_none())
+	}
+
+	fn ensure_active_worker(project: ProjectID, id: &IdentityId<T>) -> DispatchResult {
+		ensure!(Self::is_worker(project, id), Error::<T>::NotAWorker);
+		ensure!(Self::is_active_worker(project, id), Error::<T>::WorkerAlreadyLeaving);
+		Ok(())
+	}
+
+	/// Number of workers of a project (required for weight calculation)
+	fn worker_count(project: ProjectID) -> u32 {
+		<ProjectStorage<T>>::get(project).map_or(0, |prj| prj.worker_count)
+	}
+
+	/// Number of project leader candidates of a project (required for weight calculation)
+	fn candidate_count(project: ProjectID) -> u32 {
+		<LeaderCandidates<T>>::decode_len(project).unwrap_or(0) as u32
+	}
+
+	/// Record the reward that was paid to the proposer of the project
+	fn do_set_proposer_reward(project: ProjectID, reward: BalanceOf<T>) -> Result<(), DispatchError> {
+		let mut prj = Self::load_project(project)?;
+		prj.proposer_reward = reward;
+		<ProjectStorage<T>>::insert(project, prj);
+		Ok(())
+	}
+
+	/// Get project (live or archived)
+	fn do_get_project(project: ProjectID) -> Result<Option<ProjectOf<T>>, DispatchError> {
+		Ok(<ProjectStorage<T>>::get(project).or_else(|| <ArchivedProjects<T>>::get(project)))
+	}
+
+	/// Get the live projects with an id within [start, start + limit)
+	fn do_get_projects(start: ProjectID, limit: u32) -> Result<Vec<ProjectOf<T>>, DispatchError> {
+		let end: ProjectID = start.saturating_add(limit.into()).min(<ProjectNumber>::get());
+		Ok((start..end).filter_map(|id| <ProjectStorage<T>>::get(id)).collect())
+	}
+
+	/// Get up to limit workers of a project, skipping the first start workers
+	fn do_get_workers(project: ProjectID, start: u32, limit: u32) -> Result<Vec<WorkerOf<T>>, DispatchError> {
+		Ok(<Workers<T>>::iter_prefix_values(project).skip(start as usize).take(limit as usize).collect())
+	}
+
+	/// Get up to limit projects an identity works on (as worker or project leader), skipping the first start projects
+	fn do_get_worker_projects(identity: &IdentityId<T>, start: u32, limit: u32)
+		-> Result<Vec<ProjectID>, DispatchError>
+	{
+		Ok(<WorkerProjects<T>>::iter_prefix(identity)
+			.map(|(project, _)| project)
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect())
+	}
+
+	/// Get the projects in a state with an id within [start, start + limit). Without a state, get the
+	/// live projects. Only limit projects are read, the second value is the id to continue with
+	/// (None if no project with a higher id exists).
+	pub fn get_projects_by_state(state: Option<ProjectState>, start: ProjectID, limit: u32)
+		-> (Vec<ProjectOf<T>>, Option<ProjectID>)
+	{
+		let number: ProjectID = <ProjectNumber>::get();
+		let end: ProjectID = start.saturating_add(limit.into()).min(number);
+		let projects = (start..end)
+			.filter_map(|id| match state {
+				Some(s) if s.is_final() => <ArchivedProjects<T>>::get(id),
+				_ => <ProjectStorage<T>>::get(id),
+			})
+			.filter(|prj| state.map_or(true, |s| prj.state == s))
+			.collect();
+
+		(projects, if end < number { Some(end) } else { None })
+	}
+
+	/// Get up to limit live projects an identity works on (as worker or project leader),
+	/// skipping the first start projects
+	pub fn get_projects_of_worker(identity: &IdentityId<T>, start: u32, limit: u32) -> Vec<ProjectOf<T>> {
+		<WorkerProjects<T>>::iter_prefix(identity)
+			.skip(start as usize)
+			.take(limit as usize)
+			.filter_map(|(project, _)| <ProjectStorage<T>>::get(project))
+			.collect()
 	}
 }
 
 impl<T: Trait> ProjectTrait for Module<T> {
 	type Balance = BalanceOf<T>;
 	type IdentityId = IdentityId<T>;
-	type ProposalWinner = ProposalWinner<IdentityId<T>>;
-	type Project = Project<BalanceOf<T>, T::BlockNumber, IdentityId<T>>;
+	type ProposalWinner = ProposalWinnerOf<T>;
+	type Project = ProjectOf<T>;
+	type Worker = WorkerOf<T>;
 
 	/// As root, spawn a project from a proposal
 	fn spawn_project(proposal: Self::ProposalWinner) -> Result<Self::Project, DispatchError> {
@@ -238,14 +1040,14 @@ impl<T: Trait> ProjectTrait for Module<T> {
 	}
 
 	/// As a project leader, open positions
-	fn open_position(pl: Self::IdentityId, project: ProjectID, position: DocumentCID)
+	fn open_position(pl: Self::IdentityId, project: ProjectID, position: PositionId)
 		-> Result<(), DispatchError>
 	{
 		Self::do_open_position(pl, project, position)
 	}
 
 	/// As an identified user, apply for a position
-	fn apply(applicant: Self::IdentityId, project: ProjectID, position: DocumentCID, application: DocumentCID)
+	fn apply(applicant: Self::IdentityId, project: ProjectID, position: PositionId, application: DocumentCID)
 		-> Result<(), DispatchError>
 	{
 		Self::do_apply(applicant, project, position, application)
@@ -253,32 +1055,49 @@ impl<T: Trait> ProjectTrait for Module<T> {
 
 	/// As a project leader, accept application and offer salary
 	fn offer_applicant(pl: Self::IdentityId, applicant: Self::IdentityId, project: ProjectID,
-		position: DocumentCID, application: DocumentCID, salary: BalanceOf<T>) -> Result<(), DispatchError>
+		position: PositionId, application: DocumentCID, salary: BalanceOf<T>) -> Result<(), DispatchError>
 	{
 		Self::do_offer_applicant(pl, applicant, project, position, application, salary)
 	}
 
 	/// As an applicant, accept an offer	
-	fn accept_offer(applicant: Self::IdentityId, project: ProjectID, position: DocumentCID, salary: BalanceOf<T>)
+	fn accept_offer(applicant: Self::IdentityId, project: ProjectID, position: PositionId, salary: BalanceOf<T>)
 		-> Result<(), DispatchError>
 	{
 		Self::do_accept_offer(applicant, project, position, salary)
 	}
 
 	/// As a participant, vote to replace a colleague
-	fn vote_replace(colleague: Self::IdentityId, worker: Self::IdentityId, project: ProjectID)
+	fn vote_replace(colleague: Self::IdentityId, voter: Self::IdentityId, project: ProjectID)
 		-> Result<(), DispatchError>
 	{
-		Self::do_vote_replace(colleague, worker, project)
+		Self::do_vote_replace(colleague, voter, project)
 	}
 
-	/// Get project
+	/// Record the reward that was paid to the proposer of the project
+	fn set_proposer_reward(project: ProjectID, reward: Self::Balance) -> Result<(), DispatchError> {
+		Self::do_set_proposer_reward(project, reward)
+	}
+
+	/// Get project (live or archived)
 	fn get_project(project: ProjectID) -> Result<Option<Self::Project>, DispatchError> {
 		Self::do_get_project(project)
 	}
 
-	/// Get all projects
-	fn get_projects() -> Result<Vec<Self::Project>, DispatchError> {
-		Self::do_get_projects()
+	/// Get the live projects with an id within [start, start + limit)
+	fn get_projects(start: ProjectID, limit: u32) -> Result<Vec<Self::Project>, DispatchError> {
+		Self::do_get_projects(start, limit)
+	}
+
+	/// Get up to limit workers of a project, skipping the first start workers
+	fn get_workers(project: ProjectID, start: u32, limit: u32) -> Result<Vec<Self::Worker>, DispatchError> {
+		Self::do_get_workers(project, start, limit)
+	}
+
+	/// Get up to limit projects an identity works on (as worker or project leader), skipping the first start projects
+	fn get_worker_projects(identity: &Self::IdentityId, start: u32, limit: u32)
+		-> Result<Vec<ProjectID>, DispatchError>
+	{
+		Self::do_get_worker_projects(identity, start, limit)
 	}
 }
@@ -0,0 +1,140 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::Vec,
+	sp_std::cell::Cell,
+	storage::{IterableStorageMap, StorageDoubleMap, StorageValue},
+	traits::Get,
+	weights::Weight,
+};
+use pallet_community_identity::IdentityId;
+use pallet_proposal_types::ProposalWinner;
+use sp_arithmetic::Permill;
+use crate::{BalanceOf, OpenPositions, PendingSchedules, ProjectStorage, StorageVersion, Trait, WorkerOf, WorkerProjects,
+	Workers, types::{ConcernCID, DocumentCID, Project, ProjectID, ProjectState, ProposalCID}};
+
+/// Storage layouts of the pallet
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum Releases {
+	/// Workers and open positions are stored in the project (storage without a version)
+	V1_0_0,
+	/// Workers and open positions are stored separately, projects have a state and a budget
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Migrate the storage from any previous layout to the current layout
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if <StorageVersion>::get() == Releases::V1_0_0 {
+		weight = weight.saturating_add(migrate_to_separate_workers::<T>());
+		<StorageVersion>::put(Releases::V2_0_0);
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Worker before the payroll was added
+#[derive(Decode)]
+struct WorkerV0<Balance, BlockNumber, IdentityId> {
+	worker: IdentityId,
+	job_description: DocumentCID,
+	salary: Balance,
+	hired: BlockNumber,
+}
+
+/// Proposal winner before the metadata was added
+#[derive(Decode)]
+struct ProposalWinnerV0<IdentityId> {
+	concerns: Vec<ConcernCID>,
+	proposer: IdentityId,
+	proposal: ProposalCID,
+	vote_ratio: Permill,
+}
+
+/// Project before the workers and open positions were moved into separate maps
+#[derive(Decode)]
+struct ProjectV0<Balance, BlockNumber, IdentityId> {
+	id: ProjectID,
+	proposal: ProposalWinnerV0<IdentityId>,
+	project_leader: Option<WorkerV0<Balance, BlockNumber, IdentityId>>,
+	open_positions: Vec<DocumentCID>,
+	workers: Vec<WorkerV0<Balance, BlockNumber, IdentityId>>,
+	deadline: BlockNumber,
+}
+
+type ProjectV0Of<T> = ProjectV0<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>>;
+type WorkerV0Of<T> = WorkerV0<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, IdentityId<T>>;
+
+/// V1_0_0 -> V2_0_0: Move the workers into Workers and the open positions into OpenPositions.
+/// Projects with a project leader are staffing, the others elect their first project leader.
+/// The budget and the metadata of the proposal are unknown and left empty. Salaries were never
+/// paid before, they are paid from the upgrade on.
+fn migrate_to_separate_workers<T: Trait>() -> Weight {
+	let now = frame_system::Module::<T>::block_number();
+	let reads: Cell<u64> = Cell::new(1);
+	let writes: Cell<u64> = Cell::new(0);
+
+	let migrate_worker = |project: ProjectID, old: WorkerV0Of<T>| -> WorkerOf<T> {
+		let mut worker = WorkerOf::<T>::new(old.worker, old.job_description, old.salary, old.hired);
+		worker.paid_until = now;
+		<WorkerProjects<T>>::insert(&worker.worker, project, true);
+		writes.set(writes.get() + 1);
+		worker
+	};
+
+	<ProjectStorage<T>>::translate::<ProjectV0Of<T>, _>(|_, old: ProjectV0Of<T>| {
+		let id: ProjectID = old.id;
+		reads.set(reads.get() + 1);
+		writes.set(writes.get() + 1);
+
+		let proposal = ProposalWinner::new(old.proposal.concerns, old.proposal.proposer, old.proposal.proposal,
+			old.proposal.vote_ratio, Default::default());
+		let mut project = Project::new(id, proposal);
+		project.deadline = old.deadline;
+		project.project_leader = old.project_leader.map(|leader| migrate_worker(id, leader));
+		project.worker_count = old.workers.len() as u32;
+
+		for worker in old.workers.into_iter() {
+			let worker = migrate_worker(id, worker);
+			<Workers<T>>::insert(id, &worker.worker, &worker);
+			writes.set(writes.get() + 1);
+		}
+
+		for position in old.open_positions.iter() {
+			<OpenPositions<T>>::insert(id, position, now);
+			writes.set(writes.get() + 1);
+		}
+
+		if project.project_leader.is_some() {
+			project.state = ProjectState::Staffing;
+		}
+
+		// The payroll or the election of the first project leader continues in on_initialize
+		<PendingSchedules>::append(id);
+		writes.set(writes.get() + 1);
+		Some(project)
+	});
+
+	T::DbWeight::get().reads_writes(reads.get(), writes.get())
+}
@@ -1,13 +1,40 @@
-/*use crate::{Module, Trait};
+use crate::{Module, Trait, types::{DocumentCID, ProjectID}};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+	traits::OnInitialize, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_arithmetic::Permill;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	ModuleId, traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
 use frame_system as system;
+use pallet_community_treasury::traits::Treasury;
+use pallet_proposal_types::{ProposalMetadata, ProposalWinner};
 
 impl_outer_origin! {
-	pub enum Origin for Test {}
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		project::ProjectModule,
+	}
+}
+
+mod project {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		frame_system<T>,
+		pallet_balances<T>,
+		pallet_scheduler<T>,
+		pallet_community_identity<T>,
+		pallet_community_treasury<T>,
+		project<T>,
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -16,7 +43,8 @@ impl_outer_origin! {
 pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
-	pub const MaximumBlockWeight: Weight = 1024;
+	// Scheduled calls are postponed if they exceed the maximum weight
+	pub const MaximumBlockWeight: Weight = 2_000_000_000_000;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
 }
@@ -24,7 +52,7 @@ parameter_types! {
 impl system::Trait for Test {
 	type BaseCallFilter = ();
 	type Origin = Origin;
-	type Call = ();
+	type Call = Call;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
@@ -32,7 +60,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -43,19 +71,137 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Trait for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumBlockWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
+
+impl pallet_community_identity::Trait for Test {
+	type Event = Event;
+	type Timestamp = u64;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const EraDuration: u64 = 100;
+	pub const ActiveIdentities: u32 = 10;
+	pub const MaxPayoutsPerBlock: u32 = 10;
+}
+
+impl pallet_community_treasury::Trait for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type ModuleId = TreasuryModuleId;
+	type EraDuration = EraDuration;
+	type ActiveIdentities = ActiveIdentities;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type ConfigOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const LeaderElectionDuration: u64 = 10;
+	pub const PayoutInterval: u64 = 10;
+	pub const GracePeriod: u64 = 5;
+	pub const NoticePeriod: u64 = 3;
+	pub const ReplaceWorkerMinVotes: Permill = Permill::from_percent(60);
+	pub const ReplaceLeaderMinVotes: Permill = Permill::from_percent(60);
+}
+
 impl Trait for Test {
-	type Event = ();
+	type Event = Event;
+	type Currency = Balances;
+	type Identity = pallet_community_identity::Module<Test>;
+	type Treasury = CommunityTreasury;
+	type Scheduler = Scheduler;
+	type Proposal = Call;
+	type PalletsOrigin = OriginCaller;
+	type Documents = ();
+	type LeaderElectionDuration = LeaderElectionDuration;
+	type PayoutInterval = PayoutInterval;
+	type GracePeriod = GracePeriod;
+	type NoticePeriod = NoticePeriod;
+	type ReplaceWorkerMinVotes = ReplaceWorkerMinVotes;
+	type ReplaceLeaderMinVotes = ReplaceLeaderMinVotes;
+	type WeightInfo = ();
 }
 
-pub type TemplateModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Scheduler = pallet_scheduler::Module<Test>;
+pub type CommunityTreasury = pallet_community_treasury::Module<Test>;
+pub type ProjectModule = Module<Test>;
+
+/// Funds of the treasury at genesis
+pub const TREASURY_FUNDS: u64 = 1_000_000;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
-}*/
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(CommunityTreasury::account_id(), TREASURY_FUNDS)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not stored in the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Initialize the blocks up to (including) block n. Executes the scheduled calls.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Scheduler::on_initialize(System::block_number());
+		CommunityTreasury::on_initialize(System::block_number());
+		ProjectModule::on_initialize(System::block_number());
+	}
+}
+
+/// Was the event deposited in the current block?
+pub fn has_event(event: crate::Event<Test>) -> bool {
+	System::events().iter().any(|r| r.event == Event::project(event.clone()))
+}
+
+/// Proposal with a budget of 1000 that lasts 100 blocks and requires the given roles
+pub fn proposal(roles: Vec<DocumentCID>) -> ProposalWinner<u64, u64, u64> {
+	let metadata = ProposalMetadata { title: b"title".to_vec(), category: 0, budget: 1000, duration: 100, roles };
+	ProposalWinner::new(Vec::new(), 1, b"proposal".to_vec(), Permill::from_percent(60), metadata)
+}
+
+/// Spawn a project from proposal(roles)
+pub fn spawn(roles: Vec<DocumentCID>) -> ProjectID {
+	let id = ProjectModule::project_number();
+	ProjectModule::spawn_project(Origin::root(), proposal(roles)).expect("Spawning a project failed");
+	id
+}
@@ -1,25 +1,621 @@
-/*
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Error, mock::*, migrations::{self, Releases}, ProjectNumber, ProjectStorage, RawEvent,
+	traits::ProjectTrait, types::{ProjectID, ProjectState}};
+use codec::Encode;
+use pallet_scheduler::Agenda;
+use frame_support::{assert_noop, assert_ok, storage::{StorageMap, StorageValue, unhashed},
+	traits::{LockableCurrency, WithdrawReasons}};
+use sp_arithmetic::Permill;
+
+const LEADER: u64 = 10;
+const ROLE: &[u8] = b"developer";
+
+fn role() -> Vec<u8> {
+	ROLE.to_vec()
+}
+
+/// Apply as project leader and receive the vote of account 20
+fn apply_as_leader(candidate: u64, project: ProjectID) {
+	assert_ok!(ProjectModule::application_project_leader(Origin::signed(candidate), project, b"pl".to_vec()));
+	assert_ok!(ProjectModule::vote_project_leader(Origin::signed(20), candidate, project));
+}
+
+/// Spawn a project and elect LEADER as project leader (after LeaderElectionDuration)
+fn spawn_with_leader() -> ProjectID {
+	let project = spawn(vec![role()]);
+	apply_as_leader(LEADER, project);
+	run_to_block(System::block_number() + LeaderElectionDuration::get());
+	project
+}
+
+/// Let worker apply for the position and accept the offer of LEADER
+fn hire(project: ProjectID, position: Vec<u8>, worker: u64, salary: u64) {
+	assert_ok!(ProjectModule::apply(Origin::signed(worker), project, position.clone(), b"cv".to_vec()));
+	assert_ok!(ProjectModule::offer_applicant(Origin::signed(LEADER), worker, project, position.clone(),
+		b"cv".to_vec(), salary));
+	assert_ok!(ProjectModule::accept_offer(Origin::signed(worker), project, position, salary));
+}
+
+#[test]
+fn spawn_project_opens_required_roles() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![role(), b"designer".to_vec()]);
+		let prj = ProjectModule::project(project).unwrap();
+		assert_eq!(prj.state, ProjectState::Spawned);
+		assert_eq!(prj.budget, 1000);
+		assert_eq!(prj.deadline, 101);
+		assert_eq!(ProjectModule::project_number(), 1);
+		assert_eq!(ProjectModule::position_opened(project, role()), Some(1));
+		assert!(ProjectModule::position_opened(project, b"designer".to_vec()).is_some());
+		assert!(has_event(RawEvent::ProjectSpawned(prj)));
+	});
+}
+
+#[test]
+fn spawn_project_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert!(ProjectModule::spawn_project(Origin::signed(1), proposal(vec![])).is_err());
+		assert_eq!(ProjectModule::project_number(), 0);
+	});
+}
+
+#[test]
+fn leader_election_elects_candidate_with_most_votes() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"a".to_vec()));
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(11), project, b"b".to_vec()));
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(20), 11, project));
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(21), 11, project));
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(22), LEADER, project));
+		assert_eq!(ProjectModule::leader_votes(project, 20), Some(11));
+		run_to_block(11);
+		let prj = ProjectModule::project(project).unwrap();
+		assert_eq!(prj.state, ProjectState::Staffing);
+		assert_eq!(prj.project_leader.map(|pl| pl.worker), Some(11));
+		assert!(ProjectModule::works_on(11, project));
+		assert!(ProjectModule::leader_candidates(project).is_empty());
+		assert_eq!(ProjectModule::leader_votes(project, 20), None);
+		assert!(has_event(RawEvent::ProjectLeaderElected(project, 11)));
+	});
+}
+
+#[test]
+fn leader_election_is_extended_without_candidates() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		run_to_block(11);
+		assert!(has_event(RawEvent::LeaderElectionExtended(project, 21)));
+		assert_eq!(ProjectModule::project(project).unwrap().state, ProjectState::Spawned);
+		apply_as_leader(LEADER, project);
+		run_to_block(21);
+		assert_eq!(ProjectModule::project(project).unwrap().state, ProjectState::Staffing);
+	});
+}
+
+#[test]
+fn leader_election_is_extended_without_votes() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"a".to_vec()));
+		run_to_block(11);
+		assert!(has_event(RawEvent::LeaderElectionExtended(project, 21)));
+		assert_eq!(ProjectModule::project(project).unwrap().project_leader, None);
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(20), LEADER, project));
+		run_to_block(21);
+		assert_eq!(ProjectModule::project(project).unwrap().project_leader.map(|pl| pl.worker), Some(LEADER));
+	});
+}
+
+#[test]
+fn leader_election_tie_elects_first_candidate() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(11), project, b"a".to_vec()));
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"b".to_vec()));
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(20), LEADER, project));
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(21), 11, project));
+		run_to_block(11);
+		assert_eq!(ProjectModule::project(project).unwrap().project_leader.map(|pl| pl.worker), Some(11));
+	});
+}
+
+#[test]
+fn hiring_fills_position() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		let worker = ProjectModule::worker(project, 30).unwrap();
+		assert_eq!((worker.salary, worker.hired, worker.leaves_at), (100, 11, None));
+		assert_eq!(ProjectModule::project(project).unwrap().worker_count, 1);
+		assert_eq!(ProjectModule::position_opened(project, role()), None);
+		assert!(ProjectModule::applications(project, role()).is_empty());
+		assert!(ProjectModule::offers(project, role()).is_empty());
+		assert!(has_event(RawEvent::WorkerHired(project, 30, 100)));
+		assert_eq!(<ProjectModule as ProjectTrait>::get_worker_projects(&30, 0, 10), Ok(vec![project]));
+		assert_eq!(<ProjectModule as ProjectTrait>::get_worker_projects(&30, 1, 10), Ok(Vec::new()));
+		assert_eq!(ProjectModule::get_projects_of_worker(&30, 0, 10).len(), 1);
+		assert!(ProjectModule::get_projects_of_worker(&30, 0, 0).is_empty());
+		assert_eq!(<ProjectModule as ProjectTrait>::get_workers(project, 0, 10).unwrap().len(), 1);
+	});
+}
+
+#[test]
+fn new_offer_replaces_previous_offer() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::apply(Origin::signed(30), project, role(), b"cv".to_vec()));
+		assert_ok!(ProjectModule::offer_applicant(Origin::signed(LEADER), 30, project, role(), b"cv".to_vec(), 100));
+		assert_ok!(ProjectModule::offer_applicant(Origin::signed(LEADER), 30, project, role(), b"cv".to_vec(), 120));
+		assert_eq!(ProjectModule::offers(project, role()).len(), 1);
+		assert_noop!(ProjectModule::accept_offer(Origin::signed(30), project, role(), 100),
+			Error::<Test>::OfferNotExistant);
+		assert_ok!(ProjectModule::accept_offer(Origin::signed(30), project, role(), 120));
+	});
+}
+
+#[test]
+fn payout_pays_salaries_pro_rata() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		run_to_block(16);
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::start_project(Origin::signed(LEADER), project));
+		assert_eq!(ProjectModule::project(project).unwrap().state, ProjectState::Running);
+		// The payroll started with the election at block 11
+		run_to_block(21);
+		assert_eq!(Balances::free_balance(30), 50);
+		assert!(has_event(RawEvent::SalaryPaid(project, 30, 50)));
+		run_to_block(31);
+		assert_eq!(Balances::free_balance(30), 150);
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().paid_until, 31);
+		assert_eq!(Balances::free_balance(CommunityTreasury::account_id()), TREASURY_FUNDS - 150);
+	});
+}
+
+#[test]
+fn rejected_salary_keeps_accruing() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		run_to_block(16);
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::start_project(Origin::signed(LEADER), project));
+		// The treasury can cover the salary, but the funds are locked
+		Balances::set_lock(*b"testlock", &CommunityTreasury::account_id(), TREASURY_FUNDS, WithdrawReasons::all());
+		run_to_block(21);
+		assert!(!has_event(RawEvent::SalaryPaid(project, 30, 50)));
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().paid_until, 16);
+
+		Balances::remove_lock(*b"testlock", &CommunityTreasury::account_id());
+		run_to_block(31);
+		assert!(has_event(RawEvent::SalaryPaid(project, 30, 150)));
+		assert_eq!(Balances::free_balance(30), 150);
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().paid_until, 31);
+	});
+}
+
+#[test]
+fn leader_dismisses_worker_after_grace_period() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(LEADER), 30, project));
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().leaves_at, Some(16));
+		assert!(has_event(RawEvent::WorkerDismissed(project, 30, 16)));
+		run_to_block(16);
+		assert_eq!(ProjectModule::worker(project, 30), None);
+		assert!(!ProjectModule::works_on(30, project));
+		assert_eq!(ProjectModule::project(project).unwrap().worker_count, 0);
+		// The position is open again and the final salary was paid
+		assert_eq!(ProjectModule::position_opened(project, role()), Some(16));
+		assert!(has_event(RawEvent::WorkerRemoved(project, 30, 50)));
+		assert_eq!(Balances::free_balance(30), 50);
+	});
+}
+
+#[test]
+fn workers_dismiss_colleague_by_vote() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"designer".to_vec()));
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"tester".to_vec()));
+		hire(project, role(), 30, 100);
+		hire(project, b"designer".to_vec(), 31, 100);
+		hire(project, b"tester".to_vec(), 32, 100);
+		// One of two eligible colleagues is below ReplaceWorkerMinVotes
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(31), 30, project));
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().leaves_at, None);
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(32), 30, project));
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().leaves_at, Some(16));
+		assert!(ProjectModule::replace_votes(project, 30).is_empty());
+	});
+}
+
+#[test]
+fn leaving_workers_do_not_vote_to_replace() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"designer".to_vec()));
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"tester".to_vec()));
+		hire(project, role(), 30, 100);
+		hire(project, b"designer".to_vec(), 31, 100);
+		hire(project, b"tester".to_vec(), 32, 100);
+		assert_ok!(ProjectModule::resign(Origin::signed(32), project));
+		assert_noop!(ProjectModule::vote_replace(Origin::signed(32), 30, project),
+			Error::<Test>::WorkerAlreadyLeaving);
+		// The resigning worker is not eligible, one of one eligible colleague voted
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(31), 30, project));
+		assert_eq!(ProjectModule::worker(project, 30).unwrap().leaves_at, Some(16));
+	});
+}
+
+#[test]
+fn workers_remove_project_leader() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"designer".to_vec()));
+		hire(project, role(), 30, 100);
+		hire(project, b"designer".to_vec(), 31, 100);
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(30), LEADER, project));
+		assert!(ProjectModule::project(project).unwrap().project_leader.is_some());
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(31), LEADER, project));
+		let prj = ProjectModule::project(project).unwrap();
+		assert_eq!(prj.state, ProjectState::Halted);
+		assert_eq!(prj.project_leader, None);
+		assert!(!ProjectModule::works_on(LEADER, project));
+		assert!(has_event(RawEvent::ProjectLeaderRemoved(project, LEADER)));
+		// A worker is elected as the new project leader and leaves the position
+		apply_as_leader(30, project);
+		run_to_block(21);
+		let prj = ProjectModule::project(project).unwrap();
+		assert_eq!(prj.state, ProjectState::Staffing);
+		assert_eq!(prj.project_leader.map(|pl| pl.worker), Some(30));
+		assert_eq!(prj.worker_count, 1);
+		assert_eq!(ProjectModule::worker(project, 30), None);
+	});
+}
+
+#[test]
+fn halt_settles_salaries_of_leaving_workers() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"designer".to_vec()));
+		hire(project, role(), 30, 100);
+		hire(project, b"designer".to_vec(), 31, 100);
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(LEADER), 30, project));
+		run_to_block(14);
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(31), LEADER, project));
+		assert_eq!(ProjectModule::project(project).unwrap().state, ProjectState::Halted);
+		assert!(has_event(RawEvent::SalaryPaid(project, 30, 30)));
+		assert!(has_event(RawEvent::SalaryPaid(project, 31, 30)));
+		// The dismissed worker leaves during the halt without further salary
+		run_to_block(16);
+		assert!(has_event(RawEvent::WorkerRemoved(project, 30, 0)));
+		assert_eq!(Balances::free_balance(30), 30);
+	});
+}
+
+#[test]
+fn removing_resigning_leader_keeps_pending_election() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::resign(Origin::signed(LEADER), project));
+		apply_as_leader(11, project);
+		run_to_block(13);
+		assert_ok!(ProjectModule::vote_replace(Origin::signed(30), LEADER, project));
+		assert!(has_event(RawEvent::ProjectLeaderRemoved(project, LEADER)));
+		// The election that ends at block 21 is not scheduled again and keeps its candidates
+		assert!(Agenda::<Test>::get(23).is_empty());
+		assert_eq!(ProjectModule::leader_candidates(project).len(), 1);
+		run_to_block(21);
+		let prj = ProjectModule::project(project).unwrap();
+		assert_eq!(prj.state, ProjectState::Staffing);
+		assert_eq!(prj.project_leader.map(|pl| pl.worker), Some(11));
+	});
+}
+
+#[test]
+fn worker_resigns_after_notice_period() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::resign(Origin::signed(30), project));
+		assert!(has_event(RawEvent::WorkerResigned(project, 30, 14)));
+		run_to_block(14);
+		assert_eq!(ProjectModule::worker(project, 30), None);
+		assert_eq!(Balances::free_balance(30), 30);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn leader_resigns_and_stays_until_successor_is_elected() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::resign(Origin::signed(LEADER), project));
+		assert!(has_event(RawEvent::ProjectLeaderResigned(project, LEADER)));
+		assert_eq!(ProjectModule::project(project).unwrap().state, ProjectState::Staffing);
+		apply_as_leader(11, project);
+		run_to_block(21);
+		let prj = ProjectModule::project(project).unwrap();
+		assert_eq!(prj.project_leader.map(|pl| pl.worker), Some(11));
+		assert!(!ProjectModule::works_on(LEADER, project));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn complete_project_archives_project() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
-		);
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::start_project(Origin::signed(LEADER), project));
+		run_to_block(16);
+		assert_ok!(ProjectModule::complete_project(Origin::signed(LEADER), project));
+		assert_eq!(ProjectModule::project(project), None);
+		let archived = ProjectModule::archived_project(project).unwrap();
+		assert_eq!((archived.state, archived.worker_count), (ProjectState::Completed, 0));
+		assert_eq!(ProjectModule::worker(project, 30), None);
+		assert!(!ProjectModule::works_on(30, project));
+		// Final salary for blocks 11 to 16
+		assert_eq!(Balances::free_balance(30), 50);
+		assert_eq!(<ProjectModule as ProjectTrait>::get_project(project), Ok(Some(archived)));
+		assert_eq!(ProjectModule::get_projects_by_state(Some(ProjectState::Completed), 0, 10).0.len(), 1);
+	});
+}
+
+#[test]
+fn abandon_project_requires_root() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		assert!(ProjectModule::abandon_project(Origin::signed(1), project).is_err());
+		assert_ok!(ProjectModule::abandon_project(Origin::root(), project));
+		assert_eq!(ProjectModule::archived_project(project).unwrap().state, ProjectState::Abandoned);
+		// The scheduled election does nothing for archived projects
+		run_to_block(11);
+		assert_eq!(ProjectModule::project(project), None);
+	});
+}
+
+#[test]
+fn set_proposer_reward_updates_project() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		assert_ok!(<ProjectModule as ProjectTrait>::set_proposer_reward(project, 42));
+		assert_eq!(ProjectModule::project(project).unwrap().proposer_reward, 42);
+		assert_noop!(<ProjectModule as ProjectTrait>::set_proposer_reward(7, 42), Error::<Test>::ProjectNotExistant);
+	});
+}
+
+#[test]
+fn get_projects_returns_live_projects() {
+	new_test_ext().execute_with(|| {
+		spawn(vec![]);
+		let project = spawn(vec![]);
+		spawn(vec![]);
+		assert_ok!(ProjectModule::abandon_project(Origin::root(), project));
+		let live = <ProjectModule as ProjectTrait>::get_projects(0, 10).unwrap();
+		assert_eq!(live.iter().map(|p| p.id).collect::<Vec<_>>(), vec![0, 2]);
+		assert_eq!(<ProjectModule as ProjectTrait>::get_projects(1, 1).unwrap().len(), 0);
+	});
+}
+
+#[test]
+fn get_projects_by_state_reads_limit_projects() {
+	new_test_ext().execute_with(|| {
+		spawn(vec![]);
+		let project = spawn(vec![]);
+		spawn(vec![]);
+		assert_ok!(ProjectModule::abandon_project(Origin::root(), project));
+		let (abandoned, next) = ProjectModule::get_projects_by_state(Some(ProjectState::Abandoned), 0, 1);
+		assert!(abandoned.is_empty());
+		assert_eq!(next, Some(1));
+		let (abandoned, next) = ProjectModule::get_projects_by_state(Some(ProjectState::Abandoned), 1, 1);
+		assert_eq!(abandoned.iter().map(|p| p.id).collect::<Vec<_>>(), vec![project]);
+		assert_eq!(next, Some(2));
+		let (live, next) = ProjectModule::get_projects_by_state(None, 2, 10);
+		assert_eq!((live.len(), next), (1, None));
+	});
+}
+
+#[test]
+fn error_already_applied() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![role()]);
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"a".to_vec()));
+		assert_noop!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"b".to_vec()),
+			Error::<Test>::AlreadyApplied);
+		run_to_block(11);
+		assert_ok!(ProjectModule::apply(Origin::signed(30), project, role(), b"cv".to_vec()));
+		assert_noop!(ProjectModule::apply(Origin::signed(30), project, role(), b"cv2".to_vec()),
+			Error::<Test>::AlreadyApplied);
+	});
+}
+
+#[test]
+fn error_already_voted() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![]);
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"a".to_vec()));
+		assert_ok!(ProjectModule::vote_project_leader(Origin::signed(20), LEADER, project));
+		assert_noop!(ProjectModule::vote_project_leader(Origin::signed(20), LEADER, project),
+			Error::<Test>::AlreadyVoted);
+	});
+}
+
+#[test]
+fn error_already_worker() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::open_position(Origin::signed(LEADER), project, b"designer".to_vec()));
+		hire(project, role(), 30, 100);
+		assert_noop!(ProjectModule::apply(Origin::signed(30), project, b"designer".to_vec(), b"cv".to_vec()),
+			Error::<Test>::AlreadyWorker);
+		assert_noop!(ProjectModule::apply(Origin::signed(LEADER), project, b"designer".to_vec(), b"cv".to_vec()),
+			Error::<Test>::AlreadyWorker);
+	});
+}
+
+#[test]
+fn error_application_not_existant() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![role()]);
+		assert_noop!(ProjectModule::vote_project_leader(Origin::signed(20), LEADER, project),
+			Error::<Test>::ApplicationNotExistant);
+		assert_ok!(ProjectModule::application_project_leader(Origin::signed(LEADER), project, b"a".to_vec()));
+		run_to_block(11);
+		assert_noop!(ProjectModule::offer_applicant(Origin::signed(LEADER), 30, project, role(), b"cv".to_vec(), 1),
+			Error::<Test>::ApplicationNotExistant);
+	});
+}
+
+#[test]
+fn error_cannot_replace_self() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_noop!(ProjectModule::vote_replace(Origin::signed(30), 30, project), Error::<Test>::CannotReplaceSelf);
+	});
+}
+
+#[test]
+fn error_not_project_leader() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_noop!(ProjectModule::open_position(Origin::signed(30), project, b"designer".to_vec()),
+			Error::<Test>::NotProjectLeader);
+		assert_noop!(ProjectModule::start_project(Origin::signed(30), project), Error::<Test>::NotProjectLeader);
+		assert_noop!(ProjectModule::complete_project(Origin::signed(30), project), Error::<Test>::NotProjectLeader);
+	});
+}
+
+#[test]
+fn error_not_a_worker() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_noop!(ProjectModule::resign(Origin::signed(31), project), Error::<Test>::NotAWorker);
+		assert_noop!(ProjectModule::vote_replace(Origin::signed(31), LEADER, project), Error::<Test>::NotAWorker);
+		assert_noop!(ProjectModule::vote_replace(Origin::signed(LEADER), 31, project), Error::<Test>::NotAWorker);
+	});
+}
+
+#[test]
+fn error_offer_not_existant() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_ok!(ProjectModule::apply(Origin::signed(30), project, role(), b"cv".to_vec()));
+		assert_noop!(ProjectModule::accept_offer(Origin::signed(30), project, role(), 100),
+			Error::<Test>::OfferNotExistant);
+	});
+}
+
+#[test]
+fn error_position_already_open() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_noop!(ProjectModule::open_position(Origin::signed(LEADER), project, role()),
+			Error::<Test>::PositionAlreadyOpen);
+	});
+}
+
+#[test]
+fn error_position_not_open() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		assert_noop!(ProjectModule::apply(Origin::signed(30), project, b"designer".to_vec(), b"cv".to_vec()),
+			Error::<Test>::PositionNotOpen);
+		assert_noop!(ProjectModule::accept_offer(Origin::signed(30), project, b"designer".to_vec(), 100),
+			Error::<Test>::PositionNotOpen);
+	});
+}
+
+#[test]
+fn error_project_not_existant() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ProjectModule::application_project_leader(Origin::signed(LEADER), 0, b"a".to_vec()),
+			Error::<Test>::ProjectNotExistant);
+		assert_noop!(ProjectModule::apply(Origin::signed(30), 0, role(), b"cv".to_vec()),
+			Error::<Test>::ProjectNotExistant);
+		assert_noop!(ProjectModule::abandon_project(Origin::root(), 0), Error::<Test>::ProjectNotExistant);
+	});
+}
+
+#[test]
+fn error_worker_already_leaving() {
+	new_test_ext().execute_with(|| {
+		let project = spawn_with_leader();
+		hire(project, role(), 30, 100);
+		assert_ok!(ProjectModule::resign(Origin::signed(30), project));
+		assert_noop!(ProjectModule::resign(Origin::signed(30), project), Error::<Test>::WorkerAlreadyLeaving);
+		assert_noop!(ProjectModule::vote_replace(Origin::signed(LEADER), 30, project),
+			Error::<Test>::WorkerAlreadyLeaving);
+		assert_ok!(ProjectModule::resign(Origin::signed(LEADER), project));
+		assert_noop!(ProjectModule::resign(Origin::signed(LEADER), project), Error::<Test>::WorkerAlreadyLeaving);
+	});
+}
+
+#[test]
+fn error_wrong_project_state() {
+	new_test_ext().execute_with(|| {
+		let project = spawn(vec![role()]);
+		// No project leader was elected yet
+		assert_noop!(ProjectModule::apply(Origin::signed(30), project, role(), b"cv".to_vec()),
+			Error::<Test>::WrongProjectState);
+		apply_as_leader(LEADER, project);
+		run_to_block(11);
+		// The election is over
+		assert_noop!(ProjectModule::application_project_leader(Origin::signed(11), project, b"b".to_vec()),
+			Error::<Test>::WrongProjectState);
+		assert_ok!(ProjectModule::start_project(Origin::signed(LEADER), project));
+		assert_noop!(ProjectModule::start_project(Origin::signed(LEADER), project), Error::<Test>::WrongProjectState);
+	});
+}
+
+#[test]
+fn migrate_projects_with_embedded_workers() {
+	new_test_ext().execute_with(|| {
+		// Layout without a version. Project: (id, proposal winner, project leader, open positions,
+		// workers, deadline), ProposalWinner: (concerns, proposer, proposal, vote ratio) and
+		// Worker: (worker, job description, salary, hired)
+		type WorkerV0 = (u64, Vec<u8>, u64, u64);
+		let staffed = (0 as ProjectID, (Vec::<Vec<u8>>::new(), 1u64, b"P0".to_vec(), Permill::from_percent(60)),
+			Some((LEADER, b"pl".to_vec(), 100u64, 1u64)), vec![b"tester".to_vec()],
+			vec![(30u64, role(), 50u64, 1u64)], 100u64);
+		let spawned = (1 as ProjectID, (Vec::<Vec<u8>>::new(), 2u64, b"P1".to_vec(), Permill::from_percent(40)),
+			None::<WorkerV0>, Vec::<Vec<u8>>::new(), Vec::<WorkerV0>::new(), 200u64);
+		unhashed::put_raw(&ProjectStorage::<Test>::hashed_key_for(0), &staffed.encode());
+		unhashed::put_raw(&ProjectStorage::<Test>::hashed_key_for(1), &spawned.encode());
+		ProjectNumber::put(2);
+
+		migrations::migrate::<Test>();
+		assert_eq!(ProjectModule::storage_version(), Releases::V2_0_0);
+		assert_eq!(ProjectModule::pending_schedules(), vec![0, 1]);
+
+		let prj = ProjectModule::project(0).unwrap();
+		assert_eq!(prj.state, ProjectState::Staffing);
+		assert_eq!((prj.proposal.proposer, prj.proposal.proposal), (1, b"P0".to_vec()));
+		assert_eq!((prj.worker_count, prj.deadline, prj.budget), (1, 100, 0));
+		assert_eq!(prj.project_leader.unwrap().worker, LEADER);
+		assert_eq!(ProjectModule::worker(0, 30).unwrap().salary, 50);
+		assert!(ProjectModule::works_on(LEADER, 0));
+		assert!(ProjectModule::works_on(30, 0));
+		assert_eq!(ProjectModule::position_opened(0, b"tester".to_vec()), Some(1));
+
+		let prj = ProjectModule::project(1).unwrap();
+		assert_eq!(prj.state, ProjectState::Spawned);
+		assert_eq!(prj.deadline, 200);
+		apply_as_leader(11, 1);
+
+		// The payroll and the election of the first project leader continue from the next block on,
+		// the salary accrues since the upgrade
+		run_to_block(2);
+		assert!(ProjectModule::pending_schedules().is_empty());
+		run_to_block(1 + PayoutInterval::get());
+		assert_eq!(Balances::free_balance(30), 0);
+		run_to_block(2 + PayoutInterval::get());
+		assert!(has_event(RawEvent::SalaryPaid(0, 30, 55)));
+		assert_eq!(Balances::free_balance(30), 55);
+		let prj = ProjectModule::project(1).unwrap();
+		assert_eq!(prj.state, ProjectState::Staffing);
+		assert_eq!(prj.project_leader.unwrap().worker, 11);
 	});
 }
-*/
@@ -13,12 +13,13 @@
 // limitations under the License.
 
 use frame_support::dispatch::{Codec, DispatchError, EncodeLike, fmt::Debug, Vec};
-use crate::types::{DocumentCID, ProjectID};
+use crate::types::{DocumentCID, PositionId, ProjectID};
 
 /// Beginning of the project trait definition.
 /// A project is spawned from a proposal and the concerns.
 /// Project leaders can apply for that project for a specific time interval.
-/// Verified identities can vote for a project leader.
+/// Verified identities can vote for a project leader. The candidate with the most votes is elected,
+/// on a tie the candidate that applied first.
 /// An elected project leader creates open positions.
 /// Workers can apply for that open positions.
 /// The project leader selects applicants for the open roles.
@@ -27,12 +28,14 @@ use crate::types::{DocumentCID, ProjectID};
 /// The project leader can fire workers anytime (giving a grace period).
 /// The workers can vote to fire the project leader. The project is halted and applications
 /// for a new project leader is opened again.
+/// Completed and abandoned projects are archived.
 pub trait ProjectTrait
 {
 	type Balance: Codec + Clone + Debug + Eq + PartialEq;
 	type IdentityId: Codec + Clone + Eq + EncodeLike + Debug;
 	type ProposalWinner: Codec + Clone + Eq + Debug + PartialEq;
 	type Project: Codec + Clone + Debug + Eq + PartialEq;
+	type Worker: Codec + Clone + Debug + Eq + PartialEq;
 
 	/// As root, spawn a project from a proposal
 	fn spawn_project(proposal: Self::ProposalWinner) -> Result<Self::Project, DispatchError>;
@@ -43,22 +46,29 @@ pub trait ProjectTrait
 	fn vote_project_leader(voter: Self::IdentityId, pl: Self::IdentityId, project: ProjectID)
 		-> Result<(), DispatchError>;
 	/// As a project leader, open positions
-	fn open_position(pl: Self::IdentityId, project: ProjectID, position: DocumentCID)
+	fn open_position(pl: Self::IdentityId, project: ProjectID, position: PositionId)
 		-> Result<(), DispatchError>;
 	/// As an identified user, apply for a position
-	fn apply(applicant: Self::IdentityId, project: ProjectID, position: DocumentCID, application: DocumentCID)
+	fn apply(applicant: Self::IdentityId, project: ProjectID, position: PositionId, application: DocumentCID)
 		-> Result<(), DispatchError>;
 	// As a project leader, accept application and offer salary
 	fn offer_applicant(pl: Self::IdentityId, applicant: Self::IdentityId, project: ProjectID,
-		position: DocumentCID, application: DocumentCID, salary: Self::Balance) -> Result<(), DispatchError>;
+		position: PositionId, application: DocumentCID, salary: Self::Balance) -> Result<(), DispatchError>;
 	// As an applicant, accept an offer	
-	fn accept_offer(applicant: Self::IdentityId, project: ProjectID, position: DocumentCID, salary: Self::Balance)
+	fn accept_offer(applicant: Self::IdentityId, project: ProjectID, position: PositionId, salary: Self::Balance)
 		-> Result<(), DispatchError>;
-	// As a participant, vote to replace a colleague
-	fn vote_replace(pl: Self::IdentityId, worker: Self::IdentityId, project: ProjectID)
+	// As a participant, vote to replace a colleague (or the project leader)
+	fn vote_replace(colleague: Self::IdentityId, voter: Self::IdentityId, project: ProjectID)
 		-> Result<(), DispatchError>;
-	/// Get project
+	/// Record the reward that was paid to the proposer of the project
+	fn set_proposer_reward(project: ProjectID, reward: Self::Balance) -> Result<(), DispatchError>;
+	/// Get project (live or archived)
 	fn get_project(project: ProjectID) -> Result<Option<Self::Project>, DispatchError>;
-	/// Get all projects
-	fn get_projects() -> Result<Vec<Self::Project>, DispatchError>;
+	/// Get the live projects with an id within [start, start + limit)
+	fn get_projects(start: ProjectID, limit: u32) -> Result<Vec<Self::Project>, DispatchError>;
+	/// Get up to limit workers of a project, skipping the first start workers
+	fn get_workers(project: ProjectID, start: u32, limit: u32) -> Result<Vec<Self::Worker>, DispatchError>;
+	/// Get up to limit projects an identity works on (as worker or project leader), skipping the first start projects
+	fn get_worker_projects(identity: &Self::IdentityId, start: u32, limit: u32)
+		-> Result<Vec<ProjectID>, DispatchError>;
 }
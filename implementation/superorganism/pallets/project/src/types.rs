@@ -23,6 +23,8 @@ pub type DocumentCID = Vec<u8>;
 pub type ProposalCID = Vec<u8>;
 pub type ConcernCID = ProposalCID;
 pub type ProjectID = u64;
+/// Positions are identified by the CID of the job description
+pub type PositionId = DocumentCID;
 
 /// Contains all relevant information regarding a worker
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
@@ -32,10 +34,14 @@ pub struct Worker<Balance, BlockNumber, IdentityId> where
 	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
 	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
 {
-	worker: IdentityId,
-	job_description: DocumentCID,
-	salary: Balance,
-	hired: BlockNumber,
+	pub worker: IdentityId,
+	pub job_description: DocumentCID,
+	pub salary: Balance,
+	pub hired: BlockNumber,
+	/// Salary was paid until this block
+	pub paid_until: BlockNumber,
+	/// Block at which the worker leaves the project (end of the grace or notice period)
+	pub leaves_at: Option<BlockNumber>,
 }
 
 impl<Balance, BlockNumber, IdentityId> Worker<Balance, BlockNumber, IdentityId> where
@@ -46,7 +52,7 @@ impl<Balance, BlockNumber, IdentityId> Worker<Balance, BlockNumber, IdentityId>
 	pub fn new(worker: IdentityId, job_description: DocumentCID,
 				salary: Balance, hired: BlockNumber) -> Self 
 	{
-		Worker{worker, job_description, salary, hired}
+		Worker{worker, job_description, salary, paid_until: hired.clone(), hired, leaves_at: None}
 	}
 }
 
@@ -61,6 +67,112 @@ impl<Balance, BlockNumber, IdentityId> Default for Worker<Balance, BlockNumber,
 }
 
 
+/// Contains an application for a project leader or an open position
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Application<IdentityId> where
+	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
+{
+	pub applicant: IdentityId,
+	/// Empty for applications for the project leader
+	pub position: DocumentCID,
+	pub application: DocumentCID,
+	/// Only used for applications for the project leader
+	pub votes: u32,
+}
+
+impl<IdentityId> Application<IdentityId> where
+	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
+{
+	pub fn new(applicant: IdentityId, position: DocumentCID, application: DocumentCID) -> Self {
+		Application{applicant, position, application, votes: 0}
+	}
+}
+
+/// Contains an offer the project leader made to an applicant
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Offer<Balance, IdentityId> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
+{
+	pub applicant: IdentityId,
+	pub position: DocumentCID,
+	pub application: DocumentCID,
+	pub salary: Balance,
+}
+
+impl<Balance, IdentityId> Offer<Balance, IdentityId> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
+{
+	pub fn new(applicant: IdentityId, position: DocumentCID, application: DocumentCID, salary: Balance) -> Self {
+		Offer{applicant, position, application, salary}
+	}
+}
+
+/// Contains the different states a project can be in
+///
+/// Allowed transitions:
+/// Spawned -> Staffing | Abandoned
+/// Staffing -> Running | Halted | Abandoned
+/// Running -> Completed | Halted | Abandoned
+/// Halted -> Staffing | Abandoned
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProjectState {
+	/// Spawned from a proposal, the first project leader is being elected
+	Spawned,
+	/// A project leader exists and hires workers
+	Staffing,
+	/// The project leader started the project
+	Running,
+	/// The project leader was voted out, a new project leader is being elected
+	Halted,
+	/// The project leader declared the project as completed (archived)
+	Completed,
+	/// The project was abandoned (archived)
+	Abandoned,
+}
+
+impl ProjectState {
+	/// Is the transit from this state into the next state allowed?
+	pub fn can_transit(&self, next: ProjectState) -> bool {
+		match (self, next) {
+			(ProjectState::Spawned, ProjectState::Staffing) => true,
+			(ProjectState::Staffing, ProjectState::Running) => true,
+			(ProjectState::Staffing, ProjectState::Halted) => true,
+			(ProjectState::Running, ProjectState::Completed) => true,
+			(ProjectState::Running, ProjectState::Halted) => true,
+			(ProjectState::Halted, ProjectState::Staffing) => true,
+			(current, ProjectState::Abandoned) => !current.is_final(),
+			_ => false,
+		}
+	}
+
+	/// Is a project leader being elected in this state?
+	pub fn is_leader_election(&self) -> bool {
+		*self == ProjectState::Spawned || *self == ProjectState::Halted
+	}
+
+	/// Does a project leader exist who can hire and does the payroll run in this state?
+	pub fn is_active(&self) -> bool {
+		*self == ProjectState::Staffing || *self == ProjectState::Running
+	}
+
+	/// Is the project archived in this state?
+	pub fn is_final(&self) -> bool {
+		*self == ProjectState::Completed || *self == ProjectState::Abandoned
+	}
+}
+
+impl Default for ProjectState {
+	fn default() -> Self {
+		ProjectState::Spawned
+	}
+}
+
+
 /// Contains all relevant information for a project
 #[derive(Clone, Decode, Debug, Encode, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -70,11 +182,17 @@ pub struct Project<Balance, BlockNumber, IdentityId> where
 	IdentityId: Codec + Clone + Debug + Eq + EncodeLike,
 {
 	pub id: ProjectID,
-	pub proposal: ProposalWinner<IdentityId>,
+	pub state: ProjectState,
+	pub proposal: ProposalWinner<IdentityId, Balance, BlockNumber>,
 	pub project_leader: Option<Worker<Balance, BlockNumber, IdentityId>>,
-	pub open_positions: Vec<DocumentCID>,
-	pub workers: Vec<Worker<Balance, BlockNumber, IdentityId>>, // Maybe HashMap?
+	/// Number of workers (without the project leader). Workers and open positions are
+	/// stored separately by the project pallet.
+	pub worker_count: u32,
 	pub deadline: BlockNumber,
+	/// Approved budget of the project
+	pub budget: Balance,
+	/// Reward that was paid to the proposer when the project was spawned
+	pub proposer_reward: Balance,
 }
 
 impl<Balance, BlockNumber, IdentityId> Project<Balance, BlockNumber, IdentityId> where
@@ -82,9 +200,10 @@ impl<Balance, BlockNumber, IdentityId> Project<Balance, BlockNumber, IdentityId>
 	BlockNumber: Codec + Clone + Debug + Default + Eq + PartialEq,
 	IdentityId: Codec + Clone + Debug + Default + Eq + EncodeLike,
 {
-	pub fn new(id: ProjectID, proposal: ProposalWinner<IdentityId>) -> Self {
-		Project{id, proposal, project_leader: None, workers: Default::default(),
-				open_positions: Default::default(), deadline: Default::default()}
+	pub fn new(id: ProjectID, proposal: ProposalWinner<IdentityId, Balance, BlockNumber>) -> Self {
+		Project{id, state: ProjectState::Spawned, proposal, project_leader: None, worker_count: 0,
+				deadline: Default::default(),
+				budget: Default::default(), proposer_reward: Default::default()}
 	}
 }
 
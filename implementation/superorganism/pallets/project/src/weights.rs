@@ -0,0 +1,249 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_project
+//!
+//! Estimated from the storage accesses of the extrinsics, the values were not measured yet.
+//! SubstrateWeight prices the storage accesses with the database weights of the runtime.
+//! Replace them with the results of the benchmarks in benchmarking.rs on reference hardware:
+//! ./target/release/node-superorganism benchmark --chain dev --execution wasm --wasm-execution compiled
+//!   --pallet pallet_project --extrinsic '*' --steps 50 --repeat 20
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{sp_std::marker::PhantomData, traits::Get,
+	weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+/// Weight functions needed for pallet_project
+pub trait WeightInfo {
+	fn spawn_project(r: u32, ) -> Weight;
+	fn application_project_leader(c: u32, ) -> Weight;
+	fn vote_project_leader(c: u32, ) -> Weight;
+	fn open_position() -> Weight;
+	fn apply(a: u32, ) -> Weight;
+	fn offer_applicant(a: u32, o: u32, ) -> Weight;
+	fn accept_offer(o: u32, ) -> Weight;
+	fn start_project() -> Weight;
+	fn complete_project(w: u32, ) -> Weight;
+	fn abandon_project(w: u32, ) -> Weight;
+	fn resign() -> Weight;
+	fn vote_replace(w: u32, ) -> Weight;
+	fn conclude_leader_election(c: u32, ) -> Weight;
+	fn payout(w: u32, ) -> Weight;
+	fn remove_worker() -> Weight;
+}
+
+/// Weights for pallet_project using the database weights of the runtime
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn spawn_project(r: u32, ) -> Weight {
+		(92_000_000 as Weight)
+			.saturating_add((9_400_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn application_project_leader(c: u32, ) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((310_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn vote_project_leader(c: u32, ) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((340_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn open_position() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn apply(a: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((330_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn offer_applicant(a: u32, o: u32, ) -> Weight {
+		(51_000_000 as Weight)
+			.saturating_add((290_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add((360_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn accept_offer(o: u32, ) -> Weight {
+		(84_000_000 as Weight)
+			.saturating_add((350_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn start_project() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn complete_project(w: u32, ) -> Weight {
+		(118_000_000 as Weight)
+			.saturating_add((58_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn abandon_project(w: u32, ) -> Weight {
+		(115_000_000 as Weight)
+			.saturating_add((58_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn resign() -> Weight {
+		(79_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn vote_replace(w: u32, ) -> Weight {
+		(94_000_000 as Weight)
+			.saturating_add((52_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(w as Weight)))
+	}
+	fn conclude_leader_election(c: u32, ) -> Weight {
+		(121_000_000 as Weight)
+			.saturating_add((420_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn payout(w: u32, ) -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add((52_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(w as Weight)))
+	}
+	fn remove_worker() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+}
+
+/// Weights for tests and runtimes without own database weights
+impl WeightInfo for () {
+	fn spawn_project(r: u32, ) -> Weight {
+		(92_000_000 as Weight)
+			.saturating_add((9_400_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn application_project_leader(c: u32, ) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((310_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn vote_project_leader(c: u32, ) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((340_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn open_position() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn apply(a: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((330_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn offer_applicant(a: u32, o: u32, ) -> Weight {
+		(51_000_000 as Weight)
+			.saturating_add((290_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add((360_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn accept_offer(o: u32, ) -> Weight {
+		(84_000_000 as Weight)
+			.saturating_add((350_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn start_project() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn complete_project(w: u32, ) -> Weight {
+		(118_000_000 as Weight)
+			.saturating_add((58_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn abandon_project(w: u32, ) -> Weight {
+		(115_000_000 as Weight)
+			.saturating_add((58_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn resign() -> Weight {
+		(79_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn vote_replace(w: u32, ) -> Weight {
+		(94_000_000 as Weight)
+			.saturating_add((52_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(w as Weight)))
+	}
+	fn conclude_leader_election(c: u32, ) -> Weight {
+		(121_000_000 as Weight)
+			.saturating_add((420_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn payout(w: u32, ) -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add((52_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(w as Weight)))
+	}
+	fn remove_worker() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+}
@@ -0,0 +1,448 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for pallet_proposal
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::sp_std::vec;
+use frame_system::RawOrigin;
+
+const MAX_CONCERNS: u32 = 100;
+const MAX_DELEGATIONS: u32 = 100;
+const MAX_DELEGATION_DEPTH: u32 = 32;
+const MAX_DEFERRED: u32 = 20;
+const MAX_PROPOSALS: u32 = 100;
+const MAX_USER_VOTES: u32 = 100;
+const MAX_VOTERS: u32 = 100;
+const MAX_WINNERS: u32 = 20;
+const SEED: u32 = 0;
+
+/// CID of the length of a CIDv1 (sha2-256, base32)
+fn cid(prefix: &[u8], i: u32) -> ProposalCID {
+	let mut cid = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd".to_vec();
+	cid.extend_from_slice(prefix);
+	cid.extend_from_slice(&i.to_le_bytes());
+	cid
+}
+
+/// Metadata with the longest allowed title in an existing category
+fn metadata<T: Trait>() -> ProposalMetadataOf<T> {
+	<Categories>::insert(0, b"Benchmark".to_vec());
+	ProposalMetadata {
+		title: vec![b'a'; Module::<T>::config().propose_max_title_length as usize],
+		category: 0,
+		budget: Default::default(),
+		duration: 100u32.into(),
+		roles: Vec::new(),
+	}
+}
+
+fn identity<T: Trait>(caller: &T::AccountId) -> IdentityId<T> {
+	T::Identity::get_identity_id(caller)
+}
+
+/// Submit p proposals from distinct proposers
+fn add_proposals<T: Trait>(p: u32) -> Vec<(IdentityId<T>, ProposalCID)> {
+	(0..p).map(|i| {
+		let proposer: IdentityId<T> = account("proposer", i, SEED);
+		Module::<T>::add_proposal(proposer.clone(), cid(b"proposal", i), metadata::<T>());
+		(proposer, cid(b"proposal", i))
+	}).collect()
+}
+
+/// Store w winners of the current round
+fn add_winners<T: Trait>(w: u32) -> VecDeque<ProposalWinnerOf<T>> {
+	let winners: VecDeque<ProposalWinnerOf<T>> = (0..w).map(|i| {
+		ProposalWinnerOf::<T>::new(Vec::new(), account("proposer", i, SEED), cid(b"proposal", i),
+			Permill::one(), metadata::<T>())
+	}).collect();
+	<ProposalWinners<T>>::insert(<Round>::get(), winners.clone());
+	winners
+}
+
+/// Submit c concerns from distinct concerners, spread over the winners
+fn add_concerns<T: Trait>(c: u32, winners: &VecDeque<ProposalWinnerOf<T>>)
+	-> Vec<(IdentityId<T>, ConcernCID, ProposalCID)>
+{
+	(0..c).map(|i| {
+		let concerner: IdentityId<T> = account("concerner", i, SEED);
+		let proposal = winners[(i as usize) % winners.len()].proposal.clone();
+		Module::<T>::add_concern(concerner.clone(), cid(b"concern", i), proposal.clone());
+		(concerner, cid(b"concern", i), proposal)
+	}).collect()
+}
+
+/// Delegate the voting power of d identities to the first voter. The delegations expire at the
+/// end of the current round.
+fn add_delegations<T: Trait>(d: u32, topic: VoteTopic) {
+	let delegate: IdentityId<T> = account("voter", 0, SEED);
+	let round: RoundIndex = <Round>::get();
+
+	for i in 0..d {
+		let delegator: IdentityId<T> = account("delegator", i, SEED);
+		<Delegations<T>>::insert(topic, &delegator, Delegation::new(delegate.clone(), Some(round)));
+	}
+
+	<DelegationCount>::put(d);
+}
+
+/// Defer f proposals to the next round
+fn add_deferred<T: Trait>(f: u32) {
+	let deferred: Vec<ProposalWinnerOf<T>> = (0..f).map(|i| {
+		ProposalWinnerOf::<T>::new(Vec::new(), account("deferrer", i, SEED), cid(b"deferred", i),
+			Permill::one(), metadata::<T>())
+	}).collect();
+	<DeferredProposals<T>>::put(deferred);
+}
+
+/// Votes an identity has cast before the benchmarked vote (the limit is set by the runtime)
+fn previous_votes(v: u32, max: u32) -> u32 {
+	v.min(max.saturating_sub(1))
+}
+
+benchmarks! {
+	_ { }
+
+	state_transit_propose {
+		<State>::put(States::Propose);
+		add_proposals::<T>(1);
+	}: state_transit(RawOrigin::Root)
+	verify {
+		assert_eq!(<State>::get(), States::VotePropose);
+	}
+
+	// Hidden votes are already revealed, the votes are evaluated in any voting mode
+	state_transit_vote_propose {
+		let p in 1 .. MAX_PROPOSALS;
+		let v in 1 .. MAX_VOTERS;
+		let d in 0 .. MAX_DELEGATIONS;
+		let proposals = add_proposals::<T>(p);
+		add_delegations::<T>(d, VoteTopic::Proposal);
+
+		for i in 0..v {
+			let (proposer, proposal) = proposals[(i % p) as usize].clone();
+			Module::<T>::add_vote_proposal(account("voter", i, SEED), proposal, proposer);
+		}
+
+		<State>::put(States::RevealPropose);
+	}: state_transit(RawOrigin::Root)
+	verify {
+		assert_eq!(<ProposalCount>::get(), 0);
+	}
+
+	// Worst case: No concerns were submitted, the council polls are created immediately
+	state_transit_concern {
+		let w in 1 .. MAX_WINNERS;
+		add_winners::<T>(w);
+		<State>::put(States::Concern);
+	}: state_transit(RawOrigin::Root)
+	verify {
+		assert_eq!(<State>::get(), States::VoteCouncil);
+	}
+
+	state_transit_vote_concern {
+		let c in 1 .. MAX_CONCERNS;
+		let v in 1 .. MAX_VOTERS;
+		let w in 1 .. MAX_WINNERS;
+		let d in 0 .. MAX_DELEGATIONS;
+		let winners = add_winners::<T>(w);
+		let concerns = add_concerns::<T>(c, &winners);
+		add_delegations::<T>(d, VoteTopic::Concern);
+
+		for i in 0..v {
+			let (concerner, concern, proposal) = concerns[(i % c) as usize].clone();
+			Module::<T>::add_vote_concern(account("voter", i, SEED), concern, proposal, concerner);
+		}
+
+		<State>::put(States::RevealConcern);
+	}: state_transit(RawOrigin::Root)
+	verify {
+		assert_eq!(<State>::get(), States::VoteCouncil);
+	}
+
+	// Worst case: The council accepts every winner and a project is spawned for each of them,
+	// every delegation expires and the deferred proposals are resubmitted
+	state_transit_vote_council {
+		let w in 1 .. MAX_WINNERS;
+		let d in 0 .. MAX_DELEGATIONS;
+		let f in 0 .. MAX_DEFERRED;
+		let winners = add_winners::<T>(w);
+		add_delegations::<T>(d, VoteTopic::Proposal);
+		add_deferred::<T>(f);
+		Module::<T>::add_council_poll(winners);
+		<State>::put(States::VoteCouncil);
+	}: state_transit(RawOrigin::Root)
+	verify {
+		assert_eq!(<State>::get(), States::Propose);
+	}
+
+	concern {
+		let caller: T::AccountId = whitelisted_caller();
+		<State>::put(States::Concern);
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"concern", 0), cid(b"proposal", 0))
+	verify {
+		assert_eq!(<ConcernToIdentity<T>>::get((cid(b"concern", 0), cid(b"proposal", 0))), identity::<T>(&caller));
+	}
+
+	add_category {
+		let origin = T::ConfigOrigin::successful_origin();
+	}: { Module::<T>::add_category(origin, 0, vec![b'a'; 32])? }
+	verify {
+		assert!(<Categories>::contains_key(0));
+	}
+
+	remove_category {
+		<Categories>::insert(0, b"Benchmark".to_vec());
+		let origin = T::ConfigOrigin::successful_origin();
+	}: { Module::<T>::remove_category(origin, 0)? }
+	verify {
+		assert!(!<Categories>::contains_key(0));
+	}
+
+	set_config {
+		let origin = T::ConfigOrigin::successful_origin();
+		let config = Module::<T>::default_config();
+	}: { Module::<T>::set_config(origin, config.clone())? }
+	verify {
+		assert_eq!(<PendingConfig<T>>::get(), Some(config));
+	}
+
+	propose {
+		let caller: T::AccountId = whitelisted_caller();
+		<State>::put(States::Propose);
+		let metadata = metadata::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"proposal", 0), metadata)
+	verify {
+		assert_eq!(<ProposalToIdentity<T>>::get(cid(b"proposal", 0)), identity::<T>(&caller));
+	}
+
+	withdraw_concern {
+		let caller: T::AccountId = whitelisted_caller();
+		<State>::put(States::Concern);
+		Module::<T>::add_concern(identity::<T>(&caller), cid(b"concern", 0), cid(b"proposal", 0));
+	}: _(RawOrigin::Signed(caller), cid(b"concern", 0), cid(b"proposal", 0))
+	verify {
+		assert_eq!(<ConcernCount>::get(), 0);
+	}
+
+	amend_concern {
+		let caller: T::AccountId = whitelisted_caller();
+		<State>::put(States::Concern);
+		Module::<T>::add_concern(identity::<T>(&caller), cid(b"concern", 0), cid(b"proposal", 0));
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"concern", 0), cid(b"proposal", 0), cid(b"concern", 1))
+	verify {
+		assert_eq!(<ConcernToIdentity<T>>::get((cid(b"concern", 1), cid(b"proposal", 0))), identity::<T>(&caller));
+	}
+
+	withdraw_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		<State>::put(States::Propose);
+		Module::<T>::add_proposal(identity::<T>(&caller), cid(b"proposal", 0), metadata::<T>());
+	}: _(RawOrigin::Signed(caller), cid(b"proposal", 0))
+	verify {
+		assert_eq!(<ProposalCount>::get(), 0);
+	}
+
+	amend_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		<State>::put(States::Propose);
+		Module::<T>::add_proposal(identity::<T>(&caller), cid(b"proposal", 0), metadata::<T>());
+		let metadata = metadata::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), cid(b"proposal", 0), cid(b"proposal", 1), metadata)
+	verify {
+		assert_eq!(<ProposalToIdentity<T>>::get(cid(b"proposal", 1)), identity::<T>(&caller));
+	}
+
+	// Worst case: The caller already spent all but one vote
+	vote_concern {
+		let v in 0 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::config().concern_vote_max_per_identified_user.into());
+		let caller: T::AccountId = whitelisted_caller();
+		let concerns = add_concerns::<T>(v + 1, &add_winners::<T>(1));
+
+		for (concerner, concern, proposal) in concerns.iter().take(v as usize) {
+			Module::<T>::add_vote_concern(identity::<T>(&caller), concern.clone(), proposal.clone(), concerner.clone());
+		}
+
+		let (_, concern, proposal) = concerns[v as usize].clone();
+		<State>::put(States::VoteConcern);
+	}: _(RawOrigin::Signed(caller.clone()), concern, proposal)
+	verify {
+		assert_eq!(<ConcernVotes<T>>::decode_len(identity::<T>(&caller)), Some(v as usize + 1));
+	}
+
+	unvote_concern {
+		let v in 1 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::config().concern_vote_max_per_identified_user.into()).max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		let concerns = add_concerns::<T>(v, &add_winners::<T>(1));
+
+		for (concerner, concern, proposal) in concerns.iter() {
+			Module::<T>::add_vote_concern(identity::<T>(&caller), concern.clone(), proposal.clone(), concerner.clone());
+		}
+
+		let (_, concern, proposal) = concerns[v as usize - 1].clone();
+		<State>::put(States::VoteConcern);
+	}: _(RawOrigin::Signed(caller.clone()), concern, proposal)
+	verify {
+		assert_eq!(<ConcernVotes<T>>::decode_len(identity::<T>(&caller)), Some(v as usize - 1));
+	}
+
+	commit_vote_concern {
+		let v in 0 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::config().concern_vote_max_per_identified_user.into());
+		let caller: T::AccountId = whitelisted_caller();
+		let commits: Vec<T::Hash> = (0..v).map(|i| T::Hashing::hash_of(&i)).collect();
+		<ConcernVoteCommits<T>>::insert(identity::<T>(&caller), commits);
+		<State>::put(States::VoteConcern);
+		let commitment = T::Hashing::hash_of(&v);
+	}: {
+		// Only measured if the runtime enables commit-reveal voting
+		if Module::<T>::config().commit_reveal_voting {
+			Module::<T>::commit_vote_concern(RawOrigin::Signed(caller).into(), commitment)?;
+		}
+	}
+
+	// Worst case: The revealed vote is the last commitment
+	reveal_vote_concern {
+		let v in 1 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::config().concern_vote_max_per_identified_user.into()).max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		let (_, concern, proposal) = add_concerns::<T>(1, &add_winners::<T>(1))[0].clone();
+		let salt: Salt = [1u8; 32];
+		let mut commits: Vec<T::Hash> = (1..v).map(|i| T::Hashing::hash_of(&i)).collect();
+		let id = identity::<T>(&caller);
+		commits.push(Module::<T>::concern_commitment(&id, <Round>::get(), &concern, &proposal, &salt));
+		<ConcernVoteCommits<T>>::insert(identity::<T>(&caller), commits);
+		<State>::put(States::RevealConcern);
+	}: {
+		// Only measured if the runtime enables commit-reveal voting
+		if Module::<T>::config().commit_reveal_voting {
+			Module::<T>::reveal_vote_concern(RawOrigin::Signed(caller).into(), concern, proposal, salt)?;
+		}
+	}
+
+	// Worst case: The caller already spent all but one voice credit
+	vote_proposal {
+		let v in 0 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::proposal_vote_credits());
+		let caller: T::AccountId = whitelisted_caller();
+		let proposals = add_proposals::<T>(v + 1);
+
+		for (proposer, proposal) in proposals.iter().take(v as usize) {
+			Module::<T>::add_vote_proposal(identity::<T>(&caller), proposal.clone(), proposer.clone());
+		}
+
+		let (_, proposal) = proposals[v as usize].clone();
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Signed(caller.clone()), proposal)
+	verify {
+		assert_eq!(<ProposalVotes<T>>::decode_len(identity::<T>(&caller)), Some(v as usize + 1));
+	}
+
+	unvote_proposal {
+		let v in 1 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::proposal_vote_credits()).max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		let proposals = add_proposals::<T>(v);
+
+		for (proposer, proposal) in proposals.iter() {
+			Module::<T>::add_vote_proposal(identity::<T>(&caller), proposal.clone(), proposer.clone());
+		}
+
+		let (_, proposal) = proposals[v as usize - 1].clone();
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Signed(caller.clone()), proposal)
+	verify {
+		assert_eq!(<ProposalVotes<T>>::decode_len(identity::<T>(&caller)), Some(v as usize - 1));
+	}
+
+	commit_vote_proposal {
+		let v in 0 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::proposal_vote_credits());
+		let caller: T::AccountId = whitelisted_caller();
+		let commits: Vec<T::Hash> = (0..v).map(|i| T::Hashing::hash_of(&i)).collect();
+		<ProposalVoteCommits<T>>::insert(identity::<T>(&caller), commits);
+		<State>::put(States::VotePropose);
+		let commitment = T::Hashing::hash_of(&v);
+	}: {
+		// Only measured if the runtime enables commit-reveal voting
+		if Module::<T>::config().commit_reveal_voting {
+			Module::<T>::commit_vote_proposal(RawOrigin::Signed(caller).into(), commitment)?;
+		}
+	}
+
+	// Worst case: The revealed vote is the last commitment
+	reveal_vote_proposal {
+		let v in 1 .. MAX_USER_VOTES;
+		let v = previous_votes(v, Module::<T>::proposal_vote_credits()).max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		let (_, proposal) = add_proposals::<T>(1)[0].clone();
+		let salt: Salt = [1u8; 32];
+		let mut commits: Vec<T::Hash> = (1..v).map(|i| T::Hashing::hash_of(&i)).collect();
+		let id = identity::<T>(&caller);
+		commits.push(Module::<T>::proposal_commitment(&id, <Round>::get(), &proposal, &salt));
+		<ProposalVoteCommits<T>>::insert(identity::<T>(&caller), commits);
+		<State>::put(States::RevealPropose);
+	}: {
+		// Only measured if the runtime enables commit-reveal voting
+		if Module::<T>::config().commit_reveal_voting {
+			Module::<T>::reveal_vote_proposal(RawOrigin::Signed(caller).into(), proposal, salt)?;
+		}
+	}
+
+	// Worst case: The delegation chain is one link shorter than MaxDelegationDepth
+	delegate {
+		let d in 0 .. MAX_DELEGATION_DEPTH;
+		let d = d.min(Module::<T>::config().max_delegation_depth.saturating_sub(1));
+		let caller: T::AccountId = whitelisted_caller();
+		let delegates: Vec<IdentityId<T>> = (0..=d).map(|i| account("delegate", i, SEED)).collect();
+
+		for link in delegates.windows(2) {
+			<Delegations<T>>::insert(VoteTopic::Proposal, &link[0], Delegation::new(link[1].clone(), None));
+		}
+	}: _(RawOrigin::Signed(caller.clone()), VoteTopic::Proposal, delegates[0].clone(), false)
+	verify {
+		assert!(<Delegations<T>>::contains_key(VoteTopic::Proposal, identity::<T>(&caller)));
+	}
+
+	undelegate {
+		let caller: T::AccountId = whitelisted_caller();
+		<Delegations<T>>::insert(VoteTopic::Proposal, identity::<T>(&caller),
+			Delegation::new(account("delegate", 0, SEED), None));
+	}: _(RawOrigin::Signed(caller.clone()), VoteTopic::Proposal)
+	verify {
+		assert!(!<Delegations<T>>::contains_key(VoteTopic::Proposal, identity::<T>(&caller)));
+	}
+
+	// Worst case: The caller spent every voice credit and moves the first vote
+	move_vote {
+		let v in 1 .. MAX_USER_VOTES;
+		let v = v.min(Module::<T>::proposal_vote_credits()).max(1);
+		let caller: T::AccountId = whitelisted_caller();
+		let proposals = add_proposals::<T>(v + 1);
+
+		for (proposer, proposal) in proposals.iter().take(v as usize) {
+			Module::<T>::add_vote_proposal(identity::<T>(&caller), proposal.clone(), proposer.clone());
+		}
+
+		let from = proposals[0].1.clone();
+		let to = proposals[v as usize].1.clone();
+		<State>::put(States::VotePropose);
+	}: _(RawOrigin::Signed(caller.clone()), from, to.clone())
+	verify {
+		assert!(<ProposalVotes<T>>::get(identity::<T>(&caller)).contains(&to));
+	}
+}
@@ -18,13 +18,14 @@
 //! Manages proposal and concern rounds as well as the correspondant voting rounds
 
 
-use frame_support::{decl_error, decl_module, decl_storage, decl_event, Parameter, ensure, /*print, debug,*/
+use frame_support::{decl_error, decl_module, decl_storage, decl_event, Parameter, ensure, debug, /*print,*/
 	dispatch::{Vec, DispatchResult, Dispatchable, DispatchError},
-	traits::{Get, Currency, ReservableCurrency,
+	traits::{Get, Currency, EnsureOrigin, ReservableCurrency,
 		schedule::{Anon, DispatchTime, LOWEST_PRIORITY},
 	},
-	sp_std::collections::vec_deque::VecDeque,
-	//weights::Weight,
+	sp_runtime::traits::{Hash, Saturating, Zero},
+	sp_std::collections::{btree_map::BTreeMap, vec_deque::VecDeque},
+	weights::Weight,
 };
 use frame_system::{ensure_root, ensure_signed, RawOrigin::Root};
 // use frame_system;
@@ -33,16 +34,41 @@ use codec::Codec;
 use sp_arithmetic::Permill;
 // Identity pallet
 use pallet_community_identity::{ProofType, IdentityId, IdentityLevel, traits::PeerReviewedPhysicalIdentity};
+// Treasury pallet
+use pallet_community_treasury::{issuance::RewardCategory, traits::Treasury};
 use pallet_council::{BlockNumber, DocumentCID, Ticket, traits::Council};
-use pallet_project::{types::{Project as ProjectType}, traits::ProjectTrait};
+use pallet_document_availability::traits::DocumentAvailability;
+use pallet_project::{types::{Project as ProjectType, ProjectID}, traits::ProjectTrait};
 // Custom types
-use pallet_proposal_types::{Concern, ConcernCID, Proposal, ProposalCID, ProposalWinner, States};
+use pallet_proposal_types::{CategoryId, Concern, ConcernCID, CycleConfig, Delegation, Proposal, ProposalCID,
+	ProposalMetadata, ProposalWinner, RoundIndex, RoundSummary, States, VoteBudget, VoteTopic};
+use migrations::Releases;
+use traits::VoteTally;
+/// Storage migrations
+pub mod migrations;
+pub mod traits;
+/// Weights of the extrinsics and the state transit
+pub mod weights;
+
+pub use weights::WeightInfo;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod proptests;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type ProposalOf<T> = Proposal<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type ProposalMetadataOf<T> = ProposalMetadata<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type ProposalWinnerOf<T> = ProposalWinner<IdentityId<T>, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type RoundSummaryOf<T> = RoundSummary<IdentityId<T>, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber, ProjectID>;
+type CycleConfigOf<T> = CycleConfig<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+/// Salt used to blind a vote commitment
+pub type Salt = [u8; 32];
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
@@ -65,16 +91,44 @@ pub trait Trait: frame_system::Trait {
 	type Council: Council<IdentityId = IdentityId<Self>, DocumentCID=DocumentCID,
 							BlockNumber=BlockNumber<Self>, Ticket=Ticket>;
 
+	/// Define Treasury type. Rewards are paid from it. Must implement Treasury trait
+	type Treasury: Treasury<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+
 	// Define Project type. Must implement ProjectTrait trait
 	type Project: ProjectTrait<Balance = BalanceOf<Self>, IdentityId = IdentityId<Self>,
-					ProposalWinner=ProposalWinner<IdentityId<Self>>,
+					ProposalWinner=ProposalWinnerOf<Self>,
 					Project=ProjectType<BalanceOf<Self>, Self::BlockNumber, IdentityId<Self>>>;
 
+	/// Define the availability checks of proposals and concerns. Unavailable proposals
+	/// are excluded from voting.
+	type Documents: DocumentAvailability<DocumentCID = DocumentCID>;
+
+	/// Origin that can change the configuration of the proposal cycle and the categories
+	type ConfigOrigin: EnsureOrigin<Self::Origin>;
+
 	// Parameters
+	// Except for IdentifiedUserPenality, the following parameters are the initial ActiveConfig.
+	// Afterwards ConfigOrigin can change them (see set_config).
 	/// How long is an identified user locked out from submitting proposals / concerns
 	/// for bad behaviour. Value in seconds.
 	type IdentifiedUserPenality: Get<u32>;
 
+	/// Can an identified user spend several votes on the same proposal or concern?
+	type AllowVoteStacking: Get<bool>;
+
+	/// Must votes be committed as salted hashes and revealed in a separate reveal state?
+	type CommitRevealVoting: Get<bool>;
+
+	/// How long can committed votes be revealed?
+	type VoteRevealDuration: Get<Self::BlockNumber>;
+
+	/// How many delegations can be chained?
+	type MaxDelegationDepth: Get<u32>;
+
+	/// Which share of the reward for a correct delegated vote does the delegate receive?
+	/// The delegator receives the rest.
+	type DelegateRewardShare: Get<Permill>;
+
 	/// Part 1.1: Proposal state configuration
 	// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
 	// type UserProposeFee: Get<BalanceOf<Self>>;
@@ -88,6 +142,9 @@ pub trait Trait: frame_system::Trait {
 	/// Which identity level is required to create a proposal?
 	type ProposeIdentityLevel: Get<u8>;
 
+	/// How long can the title of a proposal be? Value in bytes.
+	type ProposeMaxTitleLength: Get<u32>;
+
 	/// How high is the reward (%) for the proposer if the proposal is converted into a project?
 	type ProposeReward: Get<Permill>;
 
@@ -107,8 +164,12 @@ pub trait Trait: frame_system::Trait {
 	/// How many votes can each identified user (with an appropriate identity level) submit?
 	type ProposeVoteMaxPerIdentifiedUser: Get<u16>;
 
-	/// How high is the reward if a proposal that the user voted for passes into next round?
-	type ProposeVoteCorrectReward: Get<BalanceOf<Self>>;
+	/// How are proposal votes paid for with voice credits and counted? (e.g. one vote per slot, quadratic)
+	type ProposalTally: VoteTally;
+
+	/// How much budget can the winning proposals of one round request in total?
+	/// Winners that exceed it are deferred to the next round.
+	type ProposeRoundBudget: Get<BalanceOf<Self>>;
 
 	/// Part 2.1: Concern state configuration
 	/// How many concerns can be submitted per concern round? (required for weight calculation)
@@ -120,9 +181,6 @@ pub trait Trait: frame_system::Trait {
 	/// Which identity level is required to submit a concern?
 	type ConcernIdentityLevel: Get<u8>;
 
-	/// How high is the reward if the concern receives enough votes to be passed to the next state?
-	type ConcernReward: Get<BalanceOf<Self>>;
-
 	/// How long can concerns be submitted? Value in seconds.
 	type ConcernRoundDuration: Get<Self::BlockNumber>;
 
@@ -142,9 +200,6 @@ pub trait Trait: frame_system::Trait {
 	/// How many votes can each identified user (with an appropriate identity level) submit?
 	type ConcernVoteMaxPerIdentifiedUser: Get<u16>;
 
-	/// How high is the reward if a concern that the user voted for passes into next round?
-	type ConcernVoteCorrectReward: Get<BalanceOf<Self>>;
-
 	/// Part 3: Final evaluation of the winning proposals and associated concern by the council
 	/// How much time is reserved for the council to vote? Value in seconds
 	type CouncilVoteRoundDuration: Get<Self::BlockNumber>;
@@ -152,6 +207,9 @@ pub trait Trait: frame_system::Trait {
 	/// How many percent of the council must agree that a concern is too serious to launch a
 	/// project from the associated proposal?
 	type CouncilAcceptConcernMinVotes: Get<Permill>;
+
+	/// Weight information for extrinsics and the state transit in this pallet
+	type WeightInfo: WeightInfo;
 }
 
 // TODO: Remove pub storage and write getters
@@ -164,28 +222,42 @@ decl_storage! {
 		pub State get(fn state) config(): States = States::Uninitialized;
 		/// BlockNumber for which the next state transit is scheduled
 		pub NextTransit get(fn next_transit): T::BlockNumber = T::BlockNumber::from(0);
+		/// Start the state machine in the next block (the pallet was added by a runtime upgrade)
+		pub KickOffPending get(fn kick_off_pending): bool = false;
+		/// Layout of the storage, used to migrate it during runtime upgrades
+		pub StorageVersion get(fn storage_version) build(|_| Releases::V4_0_0): Releases;
 		/// Current round
-		// decided for u8 because after 256 proposal rounds the old proposals should be converted
-		// into projects already. In addition, the blockchain state can be inspected at any block.
-		// Last, There is no gurantee that the proposals still exist in decentralized storage.
-		pub Round get(fn round): u8 = 0;
+		pub Round get(fn round): RoundIndex = 0;
+		/// Configuration of the proposal cycle used in the current round
+		pub ActiveConfig get(fn config): CycleConfigOf<T> = Module::<T>::default_config();
+		/// Configuration set by governance, applied when the next round starts
+		pub PendingConfig get(fn pending_config): Option<CycleConfigOf<T>>;
+		/// Round -> Summary of the round. Completed while the round progresses and never
+		/// changed after the round ended.
+		pub RoundSummaries get(fn round_summary): map hasher(twox_64_concat)
+			RoundIndex => Option<RoundSummaryOf<T>>;
 
 		/// Identity -> Proposals
 		pub Proposals get(fn proposals): map hasher(identity)
-			IdentityId<T> => Vec<Proposal> = Vec::new();
+			IdentityId<T> => Vec<ProposalOf<T>> = Vec::new();
 		/// Proposal -> Identity
 		pub ProposalToIdentity get(fn proposal_to_identity): map hasher(identity)
 			ProposalCID => IdentityId<T> = IdentityId::<T>::default();
 		/// Identity -> Votes (we have to keep track of the CIDs to reward the user)
 		pub ProposalVotes get(fn votes): map hasher(identity)
 			IdentityId<T> => Vec<ProposalCID> = Vec::new();
+		/// Identity -> Vote commitments, hash of (Identity, Round, ProposalCID, Salt) (commit-reveal voting only)
+		pub ProposalVoteCommits get(fn vote_commits): map hasher(identity)
+			IdentityId<T> => Vec<T::Hash> = Vec::new();
 		/// Total votes
 		pub ProposalVoteCount get(fn vote_count): u32 = 0;
 		/// Total proposals
 		pub ProposalCount get(fn proposal_count): u32 = 0;
-		/// Proposal winner for specific round
+		/// Proposal winners of the current round. Moved into RoundSummaries when the round ends.
 		pub ProposalWinners get(fn proposal_winners): map hasher(identity)
-			u8 => VecDeque<ProposalWinner<IdentityId<T>>> = VecDeque::new();
+			RoundIndex => VecDeque<ProposalWinnerOf<T>> = VecDeque::new();
+		/// Winners that did not fit into the round budget. They are resubmitted in the next round.
+		pub DeferredProposals get(fn deferred_proposals): Vec<ProposalWinnerOf<T>> = Vec::new();
 
 		/// Identity -> Concerns
 		pub Concerns get(fn concerns): map hasher(identity)
@@ -198,15 +270,31 @@ decl_storage! {
 
 		/// Identity -> Votes for concerns (we have to keep track of the CIDs to reward the user)
 		pub ConcernVotes get(fn votes_concern): map hasher(identity)
-			IdentityId<T> => Vec<ConcernCID> = Vec::new();
+			IdentityId<T> => Vec<(ConcernCID, ProposalCID)> = Vec::new();
+		/// Identity -> Vote commitments, hash of (Identity, Round, ConcernCID, ProposalCID, Salt)
+		/// (commit-reveal voting only)
+		pub ConcernVoteCommits get(fn vote_commits_concern): map hasher(identity)
+			IdentityId<T> => Vec<T::Hash> = Vec::new();
 		/// Total votes for concerns
 		pub ConcernVoteCount get(fn vote_count_concern): u32 = 0;
 
+		/// Category -> Name. Proposals must use one of these categories (managed by governance)
+		pub Categories get(fn category) config(): map hasher(identity)
+			CategoryId => Option<Vec<u8>>;
+
+		/// Topic, Delegator -> Delegation of the voting power
+		pub Delegations get(fn delegation): double_map hasher(identity) VoteTopic,
+			hasher(identity) IdentityId<T> => Option<Delegation<IdentityId<T>>>;
+		/// Total delegations of both topics
+		pub DelegationCount get(fn delegation_count): u32 = 0;
+
 		/// Tickets used as reference for council polls targeting proposals
 		pub CouncilVoteTickets get(fn council_vote_tickets): Vec<Ticket> = Vec::new();
 	}
 	add_extra_genesis {
 		build(|_| {
+			// Always store the round, migrations::migrate_to_wide_rounds detects the layout by it
+			<Round>::put(0);
 			let _ = <Module<T>>::do_state_transit();
 		}); 
 	}
@@ -214,14 +302,87 @@ decl_storage! {
 
 decl_event! {
 	pub enum Event<T> where Balance = BalanceOf<T>,
+							Hash = <T as frame_system::Trait>::Hash,
 							ID = IdentityId<T>,
-							PW = ProposalWinner<IdentityId<T>> {
+							PW = ProposalWinnerOf<T> {
 		/// Rotated to the next state. \[NewState\]
 		StateRotated(States),
+		/// A new proposal round started. \[Round\]
+		NewRound(RoundIndex),
+		/// Governance changed the configuration, it applies from the given round on \[Round\]
+		ConfigScheduled(RoundIndex),
+		/// The configuration set by governance is used from now on \[Round\]
+		ConfigApplied(RoundIndex),
+		/// Governance added or renamed a proposal category \[Category, Name\]
+		CategoryAdded(CategoryId, Vec<u8>),
+		/// Governance removed a proposal category \[Category\]
+		CategoryRemoved(CategoryId),
+		/// An identified user submitted a proposal \[Proposer, Proposal\]
+		ProposalSubmitted(ID, ProposalCID),
+		/// An identified user submitted a concern \[Concerner, Concern, Proposal\]
+		ConcernSubmitted(ID, ConcernCID, ProposalCID),
+		/// An identified user withdrew a proposal \[Proposer, Proposal\]
+		ProposalWithdrawn(ID, ProposalCID),
+		/// An identified user replaced a proposal by an amended version \[Proposer, Previous, Amended\]
+		ProposalAmended(ID, ProposalCID, ProposalCID),
+		/// An identified user withdrew a concern \[Concerner, Concern, Proposal\]
+		ConcernWithdrawn(ID, ConcernCID, ProposalCID),
+		/// An identified user replaced a concern by an amended version
+		/// \[Concerner, Previous, Amended, Proposal\]
+		ConcernAmended(ID, ConcernCID, ConcernCID, ProposalCID),
+		/// An identified user voted for a proposal \[Voter, Proposal\]
+		ProposalVoted(ID, ProposalCID),
+		/// An identified user voted for a concern \[Voter, Concern, Proposal\]
+		ConcernVoted(ID, ConcernCID, ProposalCID),
+		/// An identified user committed a hidden vote for a proposal \[Voter, Commitment\]
+		ProposalVoteCommitted(ID, Hash),
+		/// An identified user committed a hidden vote for a concern \[Voter, Commitment\]
+		ConcernVoteCommitted(ID, Hash),
+		/// Unrevealed vote commitments were discarded and forfeit any reward \[Voter, Count\]
+		VoteCommitsForfeited(ID, u32),
+		/// An identified user delegated the voting power \[Delegator, Topic, Delegate, Round\]
+		VoteDelegated(ID, VoteTopic, ID, Option<RoundIndex>),
+		/// An identified user revoked the delegation of the voting power \[Delegator, Topic\]
+		VoteUndelegated(ID, VoteTopic),
+		/// Reward for a delegated vote for a passed proposal or concern
+		/// \[Delegator, Delegate, Document, DelegatorReward, DelegateReward\]
+		DelegatedVoteReward(ID, ID, ProposalCID, Balance, Balance),
+		/// An identified user withdrew a vote for a proposal \[Voter, Proposal\]
+		ProposalUnvoted(ID, ProposalCID),
+		/// An identified user withdrew a vote for a concern \[Voter, Concern, Proposal\]
+		ConcernUnvoted(ID, ConcernCID, ProposalCID),
+		/// A proposal was excluded from the vote because it is unavailable \[Round, Proposal\]
+		ProposalExcluded(RoundIndex, ProposalCID),
+		/// A proposal received enough votes to pass into the concern round \[Round, Proposal, VoteRatio\]
+		ProposalPassed(RoundIndex, ProposalCID, Permill),
+		/// A proposal received enough votes but exceeds the remaining round budget. It is
+		/// resubmitted in the next round \[Round, Proposal, RequestedBudget, RemainingBudget\]
+		ProposalDeferred(RoundIndex, ProposalCID, Balance, Balance),
+		/// A deferred proposal was resubmitted \[Round, Proposal\]
+		ProposalCarriedOver(RoundIndex, ProposalCID),
+		/// A deferred proposal was dropped because it exceeds a proposal cap or the
+		/// round budget \[Round, Proposal\]
+		ProposalDropped(RoundIndex, ProposalCID),
+		/// A concern received enough votes to be passed to the council \[Round, Concern, Proposal\]
+		ConcernPassed(RoundIndex, ConcernCID, ProposalCID),
+		/// Reward for a vote for a passed proposal \[Voter, Proposal, Balance\]
+		ProposalVoteReward(ID, ProposalCID, Balance),
+		/// Reward for the submitter of a passed concern \[Concerner, Concern, Balance\]
+		ConcernSubmitReward(ID, ConcernCID, Balance),
+		/// Reward for a vote for a passed concern \[Voter, Concern, Balance\]
+		ConcernVoteReward(ID, ConcernCID, Balance),
 		/// Total reward for correct votes after VoteProposal round \[Balance\]
 		TotalProposalReward(Balance),
 		/// Total reward for winning concerns and votes after VoteConcern round \[Balance\]
 		TotalConcernReward(Balance),
+		/// Reward for the proposer of a proposal that was converted into a project
+		/// \[ProjectID, Proposer, Balance\]
+		ProposerReward(ProjectID, ID, Balance),
+		/// Reward for a vote in the council poll on a proposal \[Member, Proposal, Balance\]
+		CouncilVoteReward(ID, ProposalCID, Balance),
+		/// If the council decides to accept a proposal, announce the proposal
+		/// and the votes \[ProposalWinner, Vec(id, vote)\]
+		CouncilAcceptedProposal(PW, Vec<(ID, bool)>),
 		/// If the council decides to deny a proposal, announce the proposal
 		/// and the votes \[ProposalWinner, Vec(id, vote)\]
 		CouncilDeniedProposal(PW, Vec<(ID, bool)>),
@@ -234,16 +395,44 @@ decl_error! {
 		ConcernAlreadySubmitted,
 		/// Unable to add proposal because the concern limit is reached.
 		ConcernLimitReached,
+		/// Proposal category does not exist
+		CategoryNotExistant,
+		/// Requested budget exceeds propose_round_budget and could never be approved
+		BudgetExceedsRoundBudget,
 		/// Concern does not exist
 		ConcernNotExistant,
+		/// Identity already voted for the proposal or concern and vote stacking is not allowed
+		AlreadyVoted,
+		/// No vote commitment matches the revealed vote and salt
+		CommitmentNotExistant,
+		/// The delegation chain exceeds max_delegation_depth
+		DelegationChainTooLong,
+		/// The delegation would lead back to the delegator
+		DelegationCycle,
+		/// Identity did not delegate the voting power for the topic
+		DelegationNotExistant,
 		/// Identity level too low.
 		IdentityLevelTooLow,
+		/// A cap of the configuration is zero or a per user cap exceeds the total cap
+		InvalidCap,
+		/// A reward share of the configuration is zero or leaves nothing for the delegator
+		InvalidRewardShare,
+		/// An acceptance threshold of the configuration is zero or exceeds 100%
+		InvalidThreshold,
+		/// Identity did not submit the proposal or concern
+		NotSubmitter,
 		/// Proposal was already submitted by another person
 		ProposalAlreadySubmitted,
 		/// Proposal does not exist
 		ProposalNotExistant,
 		/// Unable to add proposal because the proposal limit is reached.
 		ProposalLimitReached,
+		/// Proposal was reported as unavailable and is excluded from voting
+		ProposalUnavailable,
+		/// Proposals and concerns are only stored during the current round
+		RoundNotCurrent,
+		/// Proposal title is longer than propose_max_title_length
+		TitleTooLong,
 		/// User submitted too many concerns.
 		UserConcernLimitReached,
 		/// User voted too many times on concerns.
@@ -252,8 +441,14 @@ decl_error! {
 		UserProposalLimitReached,
 		/// User voted too many times.
 		UserProposalVoteLimitReached,
+		/// User did not vote for the proposal or concern
+		VoteNotExistant,
 		/// The operation requested cannot be executed because the pallet is in the wrong state.
 		WrongState,
+		/// The operation is not available in the configured voting mode (open or commit-reveal)
+		WrongVotingMode,
+		/// A duration of the configuration is zero
+		ZeroDuration,
 	}
 }
 
@@ -269,103 +464,34 @@ decl_module! {
 		/// for bad behaviour. Value in seconds.
 		const IdentifiedUserPenality: u32 = T::IdentifiedUserPenality::get() as u32;
 
-		// Part 1.1: Proposal state configuration
-		// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
-		// const UserProposeFee: BalanceOf<T> = T::UserProposeFee::get();
-
-		/// How many proposals can be submitted per proposal round? (required for weight calculation)
-		const ProposeCap: u32 = T::ProposeCap::get() as u32;
-		
-		/// How many proposals can an identified user submit per proposal round?
-		const ProposeIdentifiedUserCap: u8 = T::ProposeIdentifiedUserCap::get() as u8;
-
-		/// Which identity level is required to create a proposal?
-		const ProposeIdentityLevel: u8 = T::ProposeIdentifiedUserCap::get() as u8;
-
-		/// How high is the reward (%) for the proposer if the proposal is converted into a project?
-		const ProposeReward: Permill = T::ProposeReward::get();
-
-		/// How long can proposals be submitted? Value in seconds.
-		const ProposeRoundDuration: T::BlockNumber = T::ProposeRoundDuration::get();
-
-		// Part 1.2: Proposal voting state configuration
-		/// How many votes (%) does a proposal require to be accepted for the next round?
-		const ProposeVoteAcceptanceMin: Permill = T::ProposeVoteAcceptanceMin::get() as Permill;
-
-		/// How long can votes for proposals be submitted?
-		const ProposeVoteDuration: T::BlockNumber = T::ProposeVoteDuration::get();
-
-		/// Which identity level (number of random verifications) is required to vote?
-		const ProposeVoteIdentityLevel: u8 = T::ProposeVoteIdentityLevel::get() as u8;
-
-		/// How many votes can each identified user (with an appropriate identity level) submit?
-		const ProposeVoteMaxPerIdentifiedUser: u16 = T::ProposeVoteMaxPerIdentifiedUser::get() as u16;
-
-		/// How high is the reward if a proposal that the user voted for passes into next round?
-		const ProposeVoteCorrectReward: BalanceOf<T> = T::ProposeVoteCorrectReward::get();
-
-		/// How many concerns can be submitted per concern round? (required for weight calculation)
-		const ConcernCap: u32 = T::ConcernCap::get() as u32;
-
-		// Part 2.1: Concern state configuration
-		/// How many concerns can an identified user submit per concern round?
-		const ConcernIdentifiedUserCap: u8 = T::ConcernIdentifiedUserCap::get() as u8;
-
-		/// Which identity level is required to submit a concern?
-		const ConcernIdentityLevel: u8 = T::ConcernIdentityLevel::get() as u8;
-
-		/// How high is the reward if the concern receives enough votes to be passed to the next state?
-		const ConcernReward: BalanceOf<T> = T::ConcernReward::get();
-
-		/// How long can concerns be submitted? Value in seconds.
-		const ConcernRoundDuration: T::BlockNumber = T::ConcernRoundDuration::get();
-
-		// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
-		// const UserConcernFee: BalanceOf<T> = T::UserConcernFee::get();
-
-		// Part 2.2: Concern voting state configuration
-		/// How many votes (%) does a concern require to be accepted for the next round?
-		const ConcernVoteAcceptanceMin: Permill = T::ConcernVoteAcceptanceMin::get() as Permill;
-
-		/// How long can votes for concerns be submitted?
-		const ConcernVoteDuration: T::BlockNumber = T::ConcernVoteDuration::get();
-
-		/// Which identity level (number of random verifications) is required to vote?
-		const ConcernVoteIdentityLevel: u8 = T::ConcernVoteIdentityLevel::get() as u8;
-
-		/// How many votes can each identified user (with an appropriate identity level) submit?
-		const ConcernVoteMaxPerIdentifiedUser: u16 = T::ConcernVoteMaxPerIdentifiedUser::get() as u16;
-
-		/// How high is the reward if a concern that the user voted for passes into next round?
-		const ConcernVoteCorrectReward: BalanceOf<T> = T::ConcernVoteCorrectReward::get();
+		/// Migrate the storage to the current layout. If this module was added during a runtime
+		/// upgrade, start the state machine in the next block.
+		// The current block number is not available yet, so the scheduler cannot be set up here (31. Oct 2020)
+		fn on_runtime_upgrade() -> Weight {
+			let weight: Weight = migrations::migrate::<T>();
 
-		/// Part 3: Final evaluation of the winning proposals and associated concern by the council
-		/// How much time is reserved for the council to vote? Value in seconds
-		const CouncilVoteRoundDuration: T::BlockNumber = T::CouncilVoteRoundDuration::get();
+			if let States::Uninitialized = <State>::get() {
+				<KickOffPending>::put(true);
+			}
 
-		/// How many percent of the council must agree that a concern is too serious to launch a
-		/// project from the associated proposal?
-		const CouncilAcceptConcernMinVotes: Permill = T::CouncilAcceptConcernMinVotes::get() as Permill;
-		
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
 
-		/// If this module was added during a runtime upgrade, start the state machine
-		// If you want to implement this feature, consider:
-		// 1. This function is called before the runtime state is initialized, therefore
-		// 	  we don't have access to the current block number. This means that we we cannot
-		//    figure out when the scheduler should transit into the next state in do_state_transit() (31. Oct 2020)
-		/*
-		fn on_runtime_upgrade() -> Weight {
-			if let States::Uninitialized = <State>::get() {
+		/// Start the state machine if it was deferred by on_runtime_upgrade
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			if <KickOffPending>::take() {
+				let weight: Weight = Self::state_transit_weight();
 				let _ = Self::do_state_transit();
+				return weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
 			}
 
-			0
-		}*/
+			T::DbWeight::get().reads(1)
+		}
 
 		
 		/// Enforce state transit
 		// Only for test purposes. Will be deleted in the future.
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(5000,3)]
+		#[weight = <Module<T>>::state_transit_weight()]
 		fn state_transit(origin) -> DispatchResult {
 			// check and change the current state
 			ensure_root(origin)?;
@@ -374,20 +500,21 @@ decl_module! {
 
 
 		/// As an identified user, submit a concern
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
+		#[weight = T::WeightInfo::concern()]
 		fn concern(origin, concern: ConcernCID, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
+			let config = Self::config();
 			// Ensure that the pallet is in the appropriate state
 			ensure!(<State>::get() == States::Concern, Error::<T>::WrongState);
 			// Ensure that the maximum concern count was not reached yet
-			ensure!(<ConcernCount>::get() < T::ConcernCap::get().into(), Error::<T>::ConcernLimitReached);
+			ensure!(<ConcernCount>::get() < config.concern_cap.into(), Error::<T>::ConcernLimitReached);
 			// Ensure the identity level is high enough to submit a concern.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ConcernIdentityLevel::get().into(),
+			ensure!(T::Identity::get_identity_level(&id) >= config.concern_identity_level.into(),
 					Error::<T>::IdentityLevelTooLow
 			);
 			// Ensure the user has not surpassed the concern limit per user
-			ensure!(<Concerns<T>>::get(&id).len() < T::ConcernIdentifiedUserCap::get().into(),
+			ensure!(<Concerns<T>>::get(&id).len() < config.concern_identified_user_cap.into(),
 					Error::<T>::UserConcernLimitReached
 			);
 			// Ensure that the concern was not already submitted
@@ -398,36 +525,125 @@ decl_module! {
 		}
 
 
+		/// As ConfigOrigin, add or rename a proposal category
+		#[weight = T::WeightInfo::add_category()]
+		fn add_category(origin, category: CategoryId, name: Vec<u8>) {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			<Categories>::insert(category, &name);
+			Self::deposit_event(Event::<T>::CategoryAdded(category, name));
+		}
+
+		/// As ConfigOrigin, remove a proposal category. Submitted proposals keep the category.
+		#[weight = T::WeightInfo::remove_category()]
+		fn remove_category(origin, category: CategoryId) {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			ensure!(<Categories>::contains_key(category), Error::<T>::CategoryNotExistant);
+			<Categories>::remove(category);
+			Self::deposit_event(Event::<T>::CategoryRemoved(category));
+		}
+
+		/// As ConfigOrigin, change the configuration of the proposal cycle.
+		/// The configuration is applied when the next round starts.
+		#[weight = T::WeightInfo::set_config()]
+		fn set_config(origin, config: CycleConfigOf<T>) {
+			T::ConfigOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_config(&config)?;
+			<PendingConfig<T>>::put(config);
+			Self::deposit_event(Event::<T>::ConfigScheduled(<Round>::get().saturating_add(1)));
+		}
+
 		/// As an identified user, submit a proposal
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
-		fn propose(origin, proposal: ProposalCID) {
+		#[weight = T::WeightInfo::propose()]
+		fn propose(origin, proposal: ProposalCID, metadata: ProposalMetadataOf<T>) {
 			let caller = ensure_signed(origin)?;
+			let config = Self::config();
 			// Ensure that the pallet is in the appropriate state
 			ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
 			// Ensure that the maximum proposal count was not reached yet
-			ensure!(<ProposalCount>::get() < T::ProposeCap::get().into(), Error::<T>::ProposalLimitReached);
+			ensure!(<ProposalCount>::get() < config.propose_cap.into(), Error::<T>::ProposalLimitReached);
 			// Ensure the identity level is high enough to propose.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ProposeIdentityLevel::get().into(),
+			ensure!(T::Identity::get_identity_level(&id) >= config.propose_identity_level.into(),
 					Error::<T>::IdentityLevelTooLow
 			);
 			// Ensure the user has not surpassed the proposal limit per user
-			ensure!(<Proposals<T>>::get(&id).len() < T::ProposeIdentifiedUserCap::get().into(),
+			ensure!(<Proposals<T>>::get(&id).len() < config.propose_identified_user_cap.into(),
 					Error::<T>::UserProposalLimitReached
 			);
 			// Ensure that the proposal was not already submitted
 			ensure!(<ProposalToIdentity<T>>::get(&proposal) == IdentityId::<T>::default(),
 					Error::<T>::ProposalAlreadySubmitted
 			);
-			Self::add_proposal(id, proposal);
+			Self::ensure_valid_metadata(&metadata)?;
+			Self::add_proposal(id, proposal, metadata);
+		}
+
+		/// As the submitter of a concern, withdraw it and free the slot
+		#[weight = T::WeightInfo::withdraw_concern()]
+		fn withdraw_concern(origin, concern: ConcernCID, proposal: ProposalCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::Concern, Error::<T>::WrongState);
+			// Ensure that the caller submitted the concern
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_concern_submitter(&id, &concern, &proposal)?;
+			Self::remove_concern(id, concern, proposal);
+		}
+
+		/// As the submitter of a concern, replace it by an amended version
+		#[weight = T::WeightInfo::amend_concern()]
+		fn amend_concern(origin, concern: ConcernCID, proposal: ProposalCID, amended: ConcernCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::Concern, Error::<T>::WrongState);
+			// Ensure that the caller submitted the concern
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_concern_submitter(&id, &concern, &proposal)?;
+			// Ensure that the amended concern was not already submitted
+			ensure!(<ConcernToIdentity<T>>::get((&amended, &proposal)) == IdentityId::<T>::default(),
+					Error::<T>::ConcernAlreadySubmitted
+			);
+			Self::replace_concern(id, concern, proposal, amended);
+		}
+
+		/// As the submitter of a proposal, withdraw it and free the slot
+		#[weight = T::WeightInfo::withdraw_proposal()]
+		fn withdraw_proposal(origin, proposal: ProposalCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
+			// Ensure that the caller submitted the proposal
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_proposal_submitter(&id, &proposal)?;
+			Self::remove_proposal(id, proposal);
+		}
+
+		/// As the submitter of a proposal, replace it by an amended version
+		#[weight = T::WeightInfo::amend_proposal()]
+		fn amend_proposal(origin, proposal: ProposalCID, amended: ProposalCID, metadata: ProposalMetadataOf<T>) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::Propose, Error::<T>::WrongState);
+			// Ensure that the caller submitted the proposal
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			Self::ensure_proposal_submitter(&id, &proposal)?;
+			// Ensure that the amended proposal was not already submitted
+			ensure!(<ProposalToIdentity<T>>::get(&amended) == IdentityId::<T>::default(),
+					Error::<T>::ProposalAlreadySubmitted
+			);
+			Self::ensure_valid_metadata(&metadata)?;
+			Self::replace_proposal(id, proposal, amended, metadata);
 		}
 
 		/// As an identified user, vote for a concern
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
+		#[weight = T::WeightInfo::vote_concern(<Module<T>>::config().concern_vote_max_per_identified_user.into())]
 		fn vote_concern(origin, concern: ConcernCID, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
+			let config = Self::config();
 			// Ensure that the pallet is in the appropriate state
 			ensure!(<State>::get() == States::VoteConcern, Error::<T>::WrongState);
+			// Ensure that votes are not hidden
+			ensure!(!config.commit_reveal_voting, Error::<T>::WrongVotingMode);
 			// Ensure that the concern exists
 			let proposer: IdentityId<T> = <ConcernToIdentity<T>>::get((&concern, &proposal));
 			ensure!(proposer != IdentityId::<T>::default(),
@@ -435,43 +651,246 @@ decl_module! {
 			);
 			// Ensure the identity level is high enough to vote.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ConcernVoteIdentityLevel::get().into(),
+			ensure!(T::Identity::get_identity_level(&id) >= config.concern_vote_identity_level.into(),
 					Error::<T>::IdentityLevelTooLow
 			);
 			// Ensure the user has not surpassed the vote limit per user
-			ensure!(<ConcernVotes<T>>::get(&id).len() < T::ConcernVoteMaxPerIdentifiedUser::get().into(),
+			ensure!(<ConcernVotes<T>>::get(&id).len() < config.concern_vote_max_per_identified_user.into(),
 					Error::<T>::UserConcernVoteLimitReached
 			);
 
-			// Optional: Ensure that the user did not already vote for the concern (design decision)
+			// Ensure that the user did not already vote for the concern (unless vote stacking is allowed)
+			ensure!(config.allow_vote_stacking || !Self::has_voted_concern(&id, &concern, &proposal),
+					Error::<T>::AlreadyVoted
+			);
+			Self::add_vote_concern(id, concern, proposal, proposer);
+		}
+
+		/// As an identified user, withdraw a vote for a concern
+		#[weight = T::WeightInfo::unvote_concern(<Module<T>>::config().concern_vote_max_per_identified_user.into())]
+		fn unvote_concern(origin, concern: ConcernCID, proposal: ProposalCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::VoteConcern, Error::<T>::WrongState);
+			// Ensure that the user voted for the concern
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			ensure!(Self::has_voted_concern(&id, &concern, &proposal), Error::<T>::VoteNotExistant);
+			let proposer: IdentityId<T> = <ConcernToIdentity<T>>::get((&concern, &proposal));
+			Self::remove_vote_concern(id, concern, proposal, proposer);
+		}
+
+		/// As an identified user, commit a hidden vote for a concern.
+		/// The commitment is the hash of (Identity, Round, ConcernCID, ProposalCID, Salt).
+		#[weight = T::WeightInfo::commit_vote_concern(<Module<T>>::config().concern_vote_max_per_identified_user.into())]
+		fn commit_vote_concern(origin, commitment: T::Hash) {
+			let caller = ensure_signed(origin)?;
+			let config = Self::config();
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::VoteConcern, Error::<T>::WrongState);
+			// Ensure that votes are hidden
+			ensure!(config.commit_reveal_voting, Error::<T>::WrongVotingMode);
+			// Ensure the identity level is high enough to vote.
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			ensure!(T::Identity::get_identity_level(&id) >= config.concern_vote_identity_level.into(),
+					Error::<T>::IdentityLevelTooLow
+			);
+			// Ensure the user has not surpassed the vote limit per user
+			ensure!(<ConcernVoteCommits<T>>::get(&id).len() < config.concern_vote_max_per_identified_user.into(),
+					Error::<T>::UserConcernVoteLimitReached
+			);
+			<ConcernVoteCommits<T>>::mutate(&id, |commits| commits.push(commitment));
+			Self::deposit_event(Event::<T>::ConcernVoteCommitted(id, commitment));
+		}
+
+		/// As an identified user, reveal a vote for a concern that was committed before
+		#[weight = T::WeightInfo::reveal_vote_concern(<Module<T>>::config().concern_vote_max_per_identified_user.into())]
+		fn reveal_vote_concern(origin, concern: ConcernCID, proposal: ProposalCID, salt: Salt) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::RevealConcern, Error::<T>::WrongState);
+			// Ensure that the concern exists
+			let proposer: IdentityId<T> = <ConcernToIdentity<T>>::get((&concern, &proposal));
+			ensure!(proposer != IdentityId::<T>::default(),
+				Error::<T>::ConcernNotExistant
+			);
+			// Ensure that the user committed to this vote
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			let commitment = Self::concern_commitment(&id, <Round>::get(), &concern, &proposal, &salt);
+			let mut commits: Vec<T::Hash> = <ConcernVoteCommits<T>>::get(&id);
+			let idx = commits.iter().position(|c| *c == commitment)
+				.ok_or(Error::<T>::CommitmentNotExistant)?;
+			// Ensure that the user did not already vote for the concern (unless vote stacking is allowed)
+			ensure!(Self::config().allow_vote_stacking || !Self::has_voted_concern(&id, &concern, &proposal),
+					Error::<T>::AlreadyVoted
+			);
+			commits.remove(idx);
+			<ConcernVoteCommits<T>>::insert(&id, commits);
 			Self::add_vote_concern(id, concern, proposal, proposer);
 		}
 
 		/// As an identified user, vote for a proposal
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(6,3)]
+		#[weight = T::WeightInfo::vote_proposal(<Module<T>>::proposal_vote_credits())]
 		fn vote_proposal(origin, proposal: ProposalCID) {
 			let caller = ensure_signed(origin)?;
+			let config = Self::config();
 			// Ensure that the pallet is in the appropriate state
 			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			// Ensure that votes are not hidden
+			ensure!(!config.commit_reveal_voting, Error::<T>::WrongVotingMode);
 			// Ensure that the proposal exists
 			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&proposal);
 			ensure!(proposer != IdentityId::<T>::default(),
 				Error::<T>::ProposalNotExistant
 			);
+			// Ensure that the proposal was not reported as unavailable
+			ensure!(!T::Documents::is_unavailable(&proposal), Error::<T>::ProposalUnavailable);
 			// Ensure the identity level is high enough to vote.
 			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
-			ensure!(T::Identity::get_identity_level(&id) >= T::ProposeVoteIdentityLevel::get().into(),
+			ensure!(T::Identity::get_identity_level(&id) >= config.propose_vote_identity_level.into(),
 					Error::<T>::IdentityLevelTooLow
 			);
-			// Ensure the user has not surpassed the vote limit per user
-			ensure!(<ProposalVotes<T>>::get(&id).len() < T::ProposeVoteMaxPerIdentifiedUser::get().into(),
+			// Ensure the user has enough voice credits left to pay for the vote
+			let votes: Vec<ProposalCID> = <ProposalVotes<T>>::get(&id);
+			ensure!(Self::can_afford_vote(&votes, &proposal), Error::<T>::UserProposalVoteLimitReached);
+
+			// Ensure that the user did not already vote for the proposal (unless vote stacking is allowed)
+			ensure!(config.allow_vote_stacking || !votes.contains(&proposal),
+					Error::<T>::AlreadyVoted
+			);
+			Self::add_vote_proposal(id, proposal, proposer);
+		}
+
+		/// As an identified user, withdraw a vote for a proposal
+		#[weight = T::WeightInfo::unvote_proposal(<Module<T>>::proposal_vote_credits())]
+		fn unvote_proposal(origin, proposal: ProposalCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			// Ensure that the user voted for the proposal
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			ensure!(<ProposalVotes<T>>::get(&id).contains(&proposal), Error::<T>::VoteNotExistant);
+			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&proposal);
+			Self::remove_vote_proposal(id, proposal, proposer);
+		}
+
+		/// As an identified user, commit a hidden vote for a proposal.
+		/// The commitment is the hash of (Identity, Round, ProposalCID, Salt).
+		#[weight = T::WeightInfo::commit_vote_proposal(<Module<T>>::proposal_vote_credits())]
+		fn commit_vote_proposal(origin, commitment: T::Hash) {
+			let caller = ensure_signed(origin)?;
+			let config = Self::config();
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			// Ensure that votes are hidden
+			ensure!(config.commit_reveal_voting, Error::<T>::WrongVotingMode);
+			// Ensure the identity level is high enough to vote.
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			ensure!(T::Identity::get_identity_level(&id) >= config.propose_vote_identity_level.into(),
+					Error::<T>::IdentityLevelTooLow
+			);
+			// Ensure the user has not surpassed the vote limit per user (every vote costs at least one credit)
+			ensure!((<ProposalVoteCommits<T>>::get(&id).len() as u32) < Self::proposal_vote_credits(),
 					Error::<T>::UserProposalVoteLimitReached
 			);
+			<ProposalVoteCommits<T>>::mutate(&id, |commits| commits.push(commitment));
+			Self::deposit_event(Event::<T>::ProposalVoteCommitted(id, commitment));
+		}
 
-			// Optional: Ensure that the user did not already vote for the proposal (design decision)
+		/// As an identified user, reveal a vote for a proposal that was committed before
+		#[weight = T::WeightInfo::reveal_vote_proposal(<Module<T>>::proposal_vote_credits())]
+		fn reveal_vote_proposal(origin, proposal: ProposalCID, salt: Salt) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::RevealPropose, Error::<T>::WrongState);
+			// Ensure that the proposal exists
+			let proposer: IdentityId<T> = <ProposalToIdentity<T>>::get(&proposal);
+			ensure!(proposer != IdentityId::<T>::default(),
+				Error::<T>::ProposalNotExistant
+			);
+			// Ensure that the proposal was not reported as unavailable
+			ensure!(!T::Documents::is_unavailable(&proposal), Error::<T>::ProposalUnavailable);
+			// Ensure that the user committed to this vote
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			let commitment = Self::proposal_commitment(&id, <Round>::get(), &proposal, &salt);
+			let mut commits: Vec<T::Hash> = <ProposalVoteCommits<T>>::get(&id);
+			let idx = commits.iter().position(|c| *c == commitment)
+				.ok_or(Error::<T>::CommitmentNotExistant)?;
+			// Ensure the user has enough voice credits left to pay for the vote
+			let votes: Vec<ProposalCID> = <ProposalVotes<T>>::get(&id);
+			ensure!(Self::can_afford_vote(&votes, &proposal), Error::<T>::UserProposalVoteLimitReached);
+			// Ensure that the user did not already vote for the proposal (unless vote stacking is allowed)
+			ensure!(Self::config().allow_vote_stacking || !votes.contains(&proposal),
+					Error::<T>::AlreadyVoted
+			);
+			commits.remove(idx);
+			<ProposalVoteCommits<T>>::insert(&id, commits);
 			Self::add_vote_proposal(id, proposal, proposer);
 		}
 
+		/// As an identified user, delegate the voting power for proposals or concerns.
+		/// If this_round_only is set, the delegation expires at the end of the current round.
+		#[weight = T::WeightInfo::delegate(<Module<T>>::config().max_delegation_depth)]
+		fn delegate(origin, topic: VoteTopic, delegate: IdentityId<T>, this_round_only: bool) {
+			let caller = ensure_signed(origin)?;
+			// Ensure the identity level is high enough to vote.
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			ensure!(T::Identity::get_identity_level(&id) >= Self::vote_identity_level(topic),
+					Error::<T>::IdentityLevelTooLow
+			);
+			// Ensure that the delegation chain is free of cycles and not too long
+			let round: RoundIndex = <Round>::get();
+			Self::check_delegation_chain(topic, &id, &delegate, round)?;
+			let delegation = Delegation::new(delegate.clone(), if this_round_only { Some(round) } else { None });
+			// Replacing a delegation does not change the count
+			if !<Delegations<T>>::contains_key(topic, &id) {
+				<DelegationCount>::mutate(|count| *count = count.saturating_add(1));
+			}
+			<Delegations<T>>::insert(topic, &id, &delegation);
+			Self::deposit_event(Event::<T>::VoteDelegated(id, topic, delegate, delegation.round));
+		}
+
+		/// As an identified user, revoke the delegation of the voting power for proposals or concerns
+		#[weight = T::WeightInfo::undelegate()]
+		fn undelegate(origin, topic: VoteTopic) {
+			let caller = ensure_signed(origin)?;
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			ensure!(<Delegations<T>>::contains_key(topic, &id), Error::<T>::DelegationNotExistant);
+			<Delegations<T>>::remove(topic, &id);
+			<DelegationCount>::mutate(|count| *count = count.saturating_sub(1));
+			Self::deposit_event(Event::<T>::VoteUndelegated(id, topic));
+		}
+
+		/// As an identified user, move a vote from one proposal to another proposal
+		#[weight = T::WeightInfo::move_vote(<Module<T>>::proposal_vote_credits())]
+		fn move_vote(origin, from: ProposalCID, to: ProposalCID) {
+			let caller = ensure_signed(origin)?;
+			// Ensure that the pallet is in the appropriate state
+			ensure!(<State>::get() == States::VotePropose, Error::<T>::WrongState);
+			// Ensure that the target proposal exists
+			let proposer_to: IdentityId<T> = <ProposalToIdentity<T>>::get(&to);
+			ensure!(proposer_to != IdentityId::<T>::default(),
+				Error::<T>::ProposalNotExistant
+			);
+			ensure!(!T::Documents::is_unavailable(&to), Error::<T>::ProposalUnavailable);
+			// Ensure that the user voted for the source proposal
+			let id: IdentityId<T> = T::Identity::get_identity_id(&caller);
+			let votes: Vec<ProposalCID> = <ProposalVotes<T>>::get(&id);
+			ensure!(votes.contains(&from), Error::<T>::VoteNotExistant);
+			// Ensure that the user did not already vote for the target proposal (unless vote stacking is allowed)
+			ensure!(Self::config().allow_vote_stacking || from == to || !votes.contains(&to),
+					Error::<T>::AlreadyVoted
+			);
+			// Ensure the user has enough voice credits left to pay for the moved vote
+			let mut remaining: Vec<ProposalCID> = votes;
+			if let Some(idx) = remaining.iter().position(|v| *v == from) {
+				remaining.remove(idx);
+			}
+			ensure!(Self::can_afford_vote(&remaining, &to), Error::<T>::UserProposalVoteLimitReached);
+			let proposer_from: IdentityId<T> = <ProposalToIdentity<T>>::get(&from);
+			Self::remove_vote_proposal(id.clone(), from, proposer_from);
+			Self::add_vote_proposal(id, to, proposer_to);
+		}
+
 		/*
 		#[weight = 10_000]
 		fn test_identity_level(origin) {
@@ -494,13 +913,105 @@ impl<T: Trait> Module<T> {
 		});
 		// Add mapping from (ConcernCID, ProposalCid) to identity
 		ConcernToIdentity::<T>::insert((&concern, &proposal), &id);
+		T::Documents::register(&concern);
 		// Increment total concern count
 		<ConcernCount>::mutate(|cc| *cc += 1);
+		Self::deposit_event(Event::<T>::ConcernSubmitted(id, concern, proposal));
 	}
 
-	fn add_council_poll(mut winners: VecDeque<ProposalWinner<IdentityId<T>>>) {
+	/// Ensure that id submitted the concern
+	fn ensure_concern_submitter(id: &IdentityId<T>, concern: &ConcernCID, proposal: &ProposalCID) -> DispatchResult {
+		let submitter: IdentityId<T> = <ConcernToIdentity<T>>::get((concern, proposal));
+		ensure!(submitter != IdentityId::<T>::default(), Error::<T>::ConcernNotExistant);
+		ensure!(submitter == *id, Error::<T>::NotSubmitter);
+		Ok(())
+	}
+
+	/// Remove concern from storage and update relevant storage values
+	fn remove_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID) {
+		// Remove concern from the users list of concerns, remove the list if it is empty
+		// (the state machine skips VoteConcern only if no concern exists)
+		<Concerns<T>>::mutate_exists(&id, |user_concerns| {
+			if let Some(concerns) = user_concerns.as_mut() {
+				concerns.retain(|c| !(c.concern == concern && c.associated_proposal == proposal));
+			}
+
+			if user_concerns.as_ref().map_or(false, |concerns| concerns.is_empty()) {
+				*user_concerns = None;
+			}
+		});
+		ConcernToIdentity::<T>::remove((&concern, &proposal));
+		T::Documents::forget(&concern);
+		<ConcernCount>::mutate(|cc| *cc = cc.saturating_sub(1));
+		Self::deposit_event(Event::<T>::ConcernWithdrawn(id, concern, proposal));
+	}
+
+	/// Replace concern by an amended version and link the previous version
+	fn replace_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID, amended: ConcernCID) {
+		<Concerns<T>>::mutate(&id, |user_concerns| {
+			if let Some(c) = user_concerns.iter_mut().find(|c| {
+				c.concern == concern && c.associated_proposal == proposal
+			}) {
+				c.concern = amended.clone();
+				c.amends = Some(concern.clone());
+			}
+		});
+		ConcernToIdentity::<T>::remove((&concern, &proposal));
+		ConcernToIdentity::<T>::insert((&amended, &proposal), &id);
+		T::Documents::forget(&concern);
+		T::Documents::register(&amended);
+		Self::deposit_event(Event::<T>::ConcernAmended(id, concern, amended, proposal));
+	}
+
+	/// Ensure that id submitted the proposal
+	fn ensure_proposal_submitter(id: &IdentityId<T>, proposal: &ProposalCID) -> DispatchResult {
+		let submitter: IdentityId<T> = <ProposalToIdentity<T>>::get(proposal);
+		ensure!(submitter != IdentityId::<T>::default(), Error::<T>::ProposalNotExistant);
+		ensure!(submitter == *id, Error::<T>::NotSubmitter);
+		Ok(())
+	}
+
+	/// Remove proposal from storage and update relevant storage values
+	fn remove_proposal(id: IdentityId<T>, proposal: ProposalCID) {
+		// Remove proposal from the users list of proposals, remove the list if it is empty
+		// (the state machine only transits into VotePropose if proposals exist)
+		<Proposals<T>>::mutate_exists(&id, |user_proposals| {
+			if let Some(proposals) = user_proposals.as_mut() {
+				proposals.retain(|p| p.proposal != proposal);
+			}
+
+			if user_proposals.as_ref().map_or(false, |proposals| proposals.is_empty()) {
+				*user_proposals = None;
+			}
+		});
+		ProposalToIdentity::<T>::remove(&proposal);
+		T::Documents::forget(&proposal);
+		<ProposalCount>::mutate(|pc| *pc = pc.saturating_sub(1));
+		Self::deposit_event(Event::<T>::ProposalWithdrawn(id, proposal));
+	}
+
+	/// Replace proposal by an amended version and link the previous version
+	fn replace_proposal(id: IdentityId<T>, proposal: ProposalCID, amended: ProposalCID,
+		metadata: ProposalMetadataOf<T>)
+	{
+		<Proposals<T>>::mutate(&id, |user_proposals| {
+			if let Some(p) = user_proposals.iter_mut().find(|p| p.proposal == proposal) {
+				p.proposal = amended.clone();
+				p.amends = Some(proposal.clone());
+				p.metadata = metadata;
+			}
+		});
+		ProposalToIdentity::<T>::remove(&proposal);
+		ProposalToIdentity::<T>::insert(&amended, &id);
+		T::Documents::forget(&proposal);
+		T::Documents::register(&amended);
+		Self::deposit_event(Event::<T>::ProposalAmended(id, proposal, amended));
+	}
+
+	fn add_council_poll(mut winners: VecDeque<ProposalWinnerOf<T>>) {
 		let mut tickets: Vec<Ticket> = Vec::new();
-		let transit_time: T::BlockNumber = T::CouncilVoteRoundDuration::get();
+		let until: T::BlockNumber = frame_system::Module::<T>::block_number()
+			+ Self::config().council_vote_round_duration;
 
 		// Add every proposal and its concerns to a freshly created council poll
 		for winner in winners.iter_mut() {
@@ -509,7 +1020,7 @@ impl<T: Trait> Module<T> {
 			documents.append(&mut winner.concerns);
 
 			// TODO: Better error handling
-			if let Ok(ticket) = T::Council::add_poll(documents, transit_time) {
+			if let Ok(ticket) = T::Council::add_poll(documents, until) {
 				tickets.push(ticket);
 			}
 		}
@@ -517,17 +1028,104 @@ impl<T: Trait> Module<T> {
 		CouncilVoteTickets::put(tickets);
 	}
 
+	/// Configuration built from the parameters of the runtime
+	fn default_config() -> CycleConfigOf<T> {
+		CycleConfig {
+			allow_vote_stacking: T::AllowVoteStacking::get(),
+			commit_reveal_voting: T::CommitRevealVoting::get(),
+			vote_reveal_duration: T::VoteRevealDuration::get(),
+			max_delegation_depth: T::MaxDelegationDepth::get(),
+			delegate_reward_share: T::DelegateRewardShare::get(),
+			propose_cap: T::ProposeCap::get(),
+			propose_identified_user_cap: T::ProposeIdentifiedUserCap::get(),
+			propose_identity_level: T::ProposeIdentityLevel::get(),
+			propose_max_title_length: T::ProposeMaxTitleLength::get(),
+			propose_reward: T::ProposeReward::get(),
+			propose_round_duration: T::ProposeRoundDuration::get(),
+			propose_round_budget: T::ProposeRoundBudget::get(),
+			propose_vote_acceptance_min: T::ProposeVoteAcceptanceMin::get(),
+			propose_vote_duration: T::ProposeVoteDuration::get(),
+			propose_vote_identity_level: T::ProposeVoteIdentityLevel::get(),
+			propose_vote_max_per_identified_user: T::ProposeVoteMaxPerIdentifiedUser::get(),
+			concern_cap: T::ConcernCap::get(),
+			concern_identified_user_cap: T::ConcernIdentifiedUserCap::get(),
+			concern_identity_level: T::ConcernIdentityLevel::get(),
+			concern_round_duration: T::ConcernRoundDuration::get(),
+			concern_vote_acceptance_min: T::ConcernVoteAcceptanceMin::get(),
+			concern_vote_duration: T::ConcernVoteDuration::get(),
+			concern_vote_identity_level: T::ConcernVoteIdentityLevel::get(),
+			concern_vote_max_per_identified_user: T::ConcernVoteMaxPerIdentifiedUser::get(),
+			council_vote_round_duration: T::CouncilVoteRoundDuration::get(),
+			council_accept_concern_min_votes: T::CouncilAcceptConcernMinVotes::get(),
+		}
+	}
+
+	/// Ensure that the configuration cannot stall the proposal cycle
+	fn ensure_valid_config(config: &CycleConfigOf<T>) -> DispatchResult {
+		let zero = T::BlockNumber::from(0);
+
+		// Every state must last at least one block, otherwise the state machine cannot rotate
+		for duration in [
+			config.propose_round_duration, config.propose_vote_duration, config.concern_round_duration,
+			config.concern_vote_duration, config.council_vote_round_duration,
+		].iter() {
+			ensure!(*duration > zero, Error::<T>::ZeroDuration);
+		}
+
+		ensure!(!config.commit_reveal_voting || config.vote_reveal_duration > zero, Error::<T>::ZeroDuration);
+		// Every identified user must be able to submit and vote at least once
+		ensure!(config.propose_identified_user_cap > 0 &&
+			u32::from(config.propose_identified_user_cap) <= config.propose_cap,
+			Error::<T>::InvalidCap
+		);
+		ensure!(config.concern_identified_user_cap > 0 &&
+			u32::from(config.concern_identified_user_cap) <= config.concern_cap,
+			Error::<T>::InvalidCap
+		);
+		ensure!(config.propose_vote_max_per_identified_user > 0, Error::<T>::InvalidCap);
+		ensure!(config.concern_vote_max_per_identified_user > 0, Error::<T>::InvalidCap);
+		ensure!(config.propose_max_title_length > 0, Error::<T>::InvalidCap);
+		// A proposal or concern must not pass without votes and a concern must not stop a project
+		// without a single council vote. Decoded shares are not bounded by 100%.
+		for threshold in [
+			config.propose_vote_acceptance_min, config.concern_vote_acceptance_min,
+			config.council_accept_concern_min_votes,
+		].iter() {
+			ensure!(*threshold > Permill::zero() && *threshold <= Permill::one(), Error::<T>::InvalidThreshold);
+		}
+
+		ensure!(config.propose_reward > Permill::zero() && config.propose_reward <= Permill::one(),
+			Error::<T>::InvalidRewardShare
+		);
+		// The reward of a delegated vote is split between the delegate and the delegator
+		ensure!(config.delegate_reward_share > Permill::zero() && config.delegate_reward_share < Permill::one(),
+			Error::<T>::InvalidRewardShare
+		);
+		Ok(())
+	}
+
+	/// Ensure that the title is bounded, the category exists and the budget fits into a round
+	fn ensure_valid_metadata(metadata: &ProposalMetadataOf<T>) -> DispatchResult {
+		let config = Self::config();
+		ensure!(metadata.title.len() <= config.propose_max_title_length as usize, Error::<T>::TitleTooLong);
+		ensure!(<Categories>::contains_key(metadata.category), Error::<T>::CategoryNotExistant);
+		ensure!(metadata.budget <= config.propose_round_budget, Error::<T>::BudgetExceedsRoundBudget);
+		Ok(())
+	}
+
 	/// Add proposal to storage and update relevant storage values
-	fn add_proposal(id: IdentityId<T>, proposal: ProposalCID) {
+	fn add_proposal(id: IdentityId<T>, proposal: ProposalCID, metadata: ProposalMetadataOf<T>) {
 		// Create proper Proposal and add it to the users list of proposals
-		let document = Proposal::new(proposal.clone());
+		let document = ProposalOf::<T>::new(proposal.clone(), metadata);
 		<Proposals<T>>::mutate(&id, |user_proposals| {
 			user_proposals.push(document);
 		});
 		// Add mapping from proposalCID to identity
 		ProposalToIdentity::<T>::insert(&proposal, &id);
+		T::Documents::register(&proposal);
 		// Increment total proposal count
 		<ProposalCount>::mutate(|pc| *pc += 1);
+		Self::deposit_event(Event::<T>::ProposalSubmitted(id, proposal));
 	}
 
 	/// Add vote to storage and update relevant storage values
@@ -546,13 +1144,14 @@ impl<T: Trait> Module<T> {
 		// Increment total vote count
 		// TODO: Overflow handling
 		<ProposalVoteCount>::mutate(|vc| *vc += 1);
+		Self::deposit_event(Event::<T>::ProposalVoted(id, proposal));
 	}
 
 	/// Add vote to storage and update relevant storage values
 	fn add_vote_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID, proposer: IdentityId<T>) {
-		// Add concernCID to id votes
+		// Add (concernCID, proposalCID) to id votes
 		<ConcernVotes<T>>::mutate(&id, |vote_cids| {
-			vote_cids.push(concern.clone())
+			vote_cids.push((concern.clone(), proposal.clone()))
 		});
 		// Increment vote count within Concern structure
 		<Concerns<T>>::mutate(&proposer, |concerns| {
@@ -566,11 +1165,207 @@ impl<T: Trait> Module<T> {
 		// Increment total vote count
 		// TODO: Overflow handling
 		<ConcernVoteCount>::mutate(|vc| *vc += 1);
+		Self::deposit_event(Event::<T>::ConcernVoted(id, concern, proposal));
+	}
+
+	/// Remove vote from storage and update relevant storage values
+	fn remove_vote_proposal(id: IdentityId<T>, proposal: ProposalCID, proposer: IdentityId<T>) {
+		// Remove one proposalCID from id votes
+		<ProposalVotes<T>>::mutate(&id, |vote_cids| {
+			if let Some(idx) = vote_cids.iter().position(|v| *v == proposal) {
+				vote_cids.remove(idx);
+			}
+		});
+		// Decrement vote count within Proposal structure
+		<Proposals<T>>::mutate(&proposer, |proposals| {
+			if let Some(p) = proposals.iter_mut().find(|el| el.proposal == proposal) {
+				p.votes = p.votes.saturating_sub(1);
+			}
+		});
+		// Decrement total vote count
+		<ProposalVoteCount>::mutate(|vc| *vc = vc.saturating_sub(1));
+		Self::deposit_event(Event::<T>::ProposalUnvoted(id, proposal));
+	}
+
+	/// Remove vote from storage and update relevant storage values
+	fn remove_vote_concern(id: IdentityId<T>, concern: ConcernCID, proposal: ProposalCID, proposer: IdentityId<T>) {
+		// Remove one (concernCID, proposalCID) from id votes
+		<ConcernVotes<T>>::mutate(&id, |vote_cids| {
+			if let Some(idx) = vote_cids.iter().position(|v| v.0 == concern && v.1 == proposal) {
+				vote_cids.remove(idx);
+			}
+		});
+		// Decrement vote count within Concern structure
+		<Concerns<T>>::mutate(&proposer, |concerns| {
+			if let Some(p) = concerns.iter_mut().find(|el| {
+				el.concern == concern && el.associated_proposal == proposal
+			}) {
+				p.votes = p.votes.saturating_sub(1);
+			}
+		});
+		// Decrement total vote count
+		<ConcernVoteCount>::mutate(|vc| *vc = vc.saturating_sub(1));
+		Self::deposit_event(Event::<T>::ConcernUnvoted(id, concern, proposal));
+	}
+
+	/// Voice credits every identified user can spend on proposal votes per round
+	fn proposal_vote_credits() -> u32 {
+		T::ProposalTally::budget(Self::config().propose_vote_max_per_identified_user)
+	}
+
+	/// Count the votes of one identity per proposal
+	fn count_votes(votes: &[ProposalCID]) -> BTreeMap<&ProposalCID, u32> {
+		let mut counted: BTreeMap<&ProposalCID, u32> = BTreeMap::new();
+
+		for vote in votes.iter() {
+			*counted.entry(vote).or_insert(0) += 1;
+		}
+
+		counted
+	}
+
+	/// Voice credits spent by one identity on proposal votes
+	fn spent_credits(votes: &[ProposalCID]) -> u32 {
+		Self::count_votes(votes).values()
+			.fold(0u32, |acc, n| acc.saturating_add(T::ProposalTally::cost(*n)))
+	}
+
+	/// Check whether one identity can pay for one more vote for proposal
+	fn can_afford_vote(votes: &[ProposalCID], proposal: &ProposalCID) -> bool {
+		let n = votes.iter().filter(|v| *v == proposal).count() as u32;
+		let cost = T::ProposalTally::cost(n + 1).saturating_sub(T::ProposalTally::cost(n));
+		Self::spent_credits(votes).saturating_add(cost) <= Self::proposal_vote_credits()
+	}
+
+	/// Identity level required to vote on a topic
+	fn vote_identity_level(topic: VoteTopic) -> IdentityLevel {
+		match topic {
+			VoteTopic::Proposal => Self::config().propose_vote_identity_level.into(),
+			VoteTopic::Concern => Self::config().concern_vote_identity_level.into(),
+		}
+	}
+
+	/// Ensure that delegating from id to delegate neither creates a cycle nor a chain
+	/// longer than MaxDelegationDepth
+	fn check_delegation_chain(topic: VoteTopic, id: &IdentityId<T>, delegate: &IdentityId<T>, round: RoundIndex)
+		-> DispatchResult
+	{
+		let mut current: IdentityId<T> = delegate.clone();
+
+		for _ in 0..Self::config().max_delegation_depth {
+			ensure!(current != *id, Error::<T>::DelegationCycle);
+
+			match <Delegations<T>>::get(topic, &current) {
+				Some(delegation) if delegation.is_active(round) => current = delegation.delegate,
+				_ => return Ok(()),
+			}
+		}
+
+		Err(Error::<T>::DelegationChainTooLong.into())
+	}
+
+	/// Follow the delegation chain of id until an identity that voted directly is found
+	fn resolve_delegate(topic: VoteTopic, id: &IdentityId<T>, round: RoundIndex,
+		has_voted: &dyn Fn(&IdentityId<T>) -> bool) -> Option<IdentityId<T>>
+	{
+		let mut visited: Vec<IdentityId<T>> = Vec::new();
+		let mut current: IdentityId<T> = id.clone();
+
+		for _ in 0..Self::config().max_delegation_depth {
+			visited.push(current.clone());
+
+			match <Delegations<T>>::get(topic, &current) {
+				Some(delegation) if delegation.is_active(round) => current = delegation.delegate,
+				_ => return None,
+			}
+
+			if has_voted(&current) {
+				return Some(current);
+			}
+
+			// Cycles are rejected on delegation, but expired delegations might be replaced
+			if visited.contains(&current) {
+				return None;
+			}
+		}
+
+		None
+	}
+
+	/// Get (delegator, delegate) for every identity that did not vote directly but delegated
+	/// its voting power to a chain that ends at an identity that voted directly.
+	// A direct vote overrides the delegation for the round.
+	fn delegated_voters(topic: VoteTopic, has_voted: &dyn Fn(&IdentityId<T>) -> bool)
+		-> Vec<(IdentityId<T>, IdentityId<T>)>
+	{
+		let round: RoundIndex = <Round>::get();
+
+		<Delegations<T>>::iter_prefix(topic)
+			.filter(|(delegator, delegation)| delegation.is_active(round) && !has_voted(delegator))
+			.filter_map(|(delegator, _)| {
+				Self::resolve_delegate(topic, &delegator, round, has_voted).map(|d| (delegator, d))
+			})
+			.collect()
+	}
+
+	/// Split the reward for a correct delegated vote between the delegate and the delegator
+	fn reward_delegated_vote(delegator: &IdentityId<T>, delegate: &IdentityId<T>, document: &ProposalCID,
+		reward: BalanceOf<T>) -> BalanceOf<T>
+	{
+		let delegate_reward: BalanceOf<T> = Self::config().delegate_reward_share * reward;
+		let delegator_reward: BalanceOf<T> = reward - delegate_reward;
+		let mut reward_issued = BalanceOf::<T>::from(0);
+
+		// TODO: Error handling
+		if T::Treasury::pay(&T::Identity::get_address(delegate), delegate_reward).is_ok() {
+			reward_issued += delegate_reward;
+		}
+
+		if T::Treasury::pay(&T::Identity::get_address(delegator), delegator_reward).is_ok() {
+			reward_issued += delegator_reward;
+		}
+
+		Self::deposit_event(Event::<T>::DelegatedVoteReward(
+			delegator.clone(), delegate.clone(), document.clone(), delegator_reward, delegate_reward
+		));
+		reward_issued
+	}
+
+	fn has_voted_concern(id: &IdentityId<T>, concern: &ConcernCID, proposal: &ProposalCID) -> bool {
+		<ConcernVotes<T>>::get(id).iter().any(|v| v.0 == *concern && v.1 == *proposal)
+	}
+
+	/// Weight of the next state transit. Depends on the current state and the number of
+	/// documents, votes, delegations and deferred proposals that are evaluated in it.
+	fn state_transit_weight() -> Weight {
+		let delegations: u32 = <DelegationCount>::get();
+
+		match <State>::get() {
+			States::Uninitialized | States::Propose => T::WeightInfo::state_transit_propose(),
+			// Starts the next round if no proposal won, the proposals deferred then are bounded by p
+			States::VotePropose | States::RevealPropose => T::WeightInfo::state_transit_vote_propose(
+				<ProposalCount>::get(), <ProposalVoteCount>::get(), delegations
+			),
+			States::Concern => T::WeightInfo::state_transit_concern(Self::winner_count()),
+			States::VoteConcern | States::RevealConcern => T::WeightInfo::state_transit_vote_concern(
+				<ConcernCount>::get(), <ConcernVoteCount>::get(), Self::winner_count(), delegations
+			),
+			States::VoteCouncil => T::WeightInfo::state_transit_vote_council(
+				<CouncilVoteTickets>::decode_len().unwrap_or(0) as u32, delegations,
+				<DeferredProposals<T>>::decode_len().unwrap_or(0) as u32
+			),
+		}
+	}
+
+	/// Number of proposal winners of the current round
+	fn winner_count() -> u32 {
+		<ProposalWinners<T>>::get(<Round>::get()).len() as u32
 	}
 
 	/// Execute the state transit and schedule the next state transit
 	fn do_state_transit() -> DispatchResult {
 		let mut transit_time: T::BlockNumber = T::BlockNumber::from(0);
+		let config = Self::config();
 
 		// TODO: Early state transit when the proposal limit was reached.
 		// TODO: Early state transition when every member of the council has voted.
@@ -581,63 +1376,81 @@ impl<T: Trait> Module<T> {
 			match state {
 				States::Uninitialized => {
 					*state = States::Propose;
-					transit_time = T::ProposeRoundDuration::get();
+					transit_time = config.propose_round_duration;
+					// Every round is rewarded from the budget of one treasury era
+					T::Treasury::new_era();
 				},
 				States::Propose => {
 					// Only transit state if proposals exist
-					transit_time = T::ProposeRoundDuration::get();
+					transit_time = config.propose_round_duration;
 					for _ in <Proposals<T>>::iter() {
-						transit_time = T::ProposeVoteDuration::get();
+						transit_time = config.propose_vote_duration;
 						*state = States::VotePropose;
 						break;
 					}
 				},
-				States::VotePropose => {
+				States::VotePropose | States::RevealPropose => {
+					// Hidden votes have to be revealed before they can be evaluated
+					if *state == States::VotePropose && config.commit_reveal_voting {
+						*state = States::RevealPropose;
+						transit_time = config.vote_reveal_duration;
+						return *state;
+					}
+
 					Self::evaluate_proposal_votes();
 					let round = <Round>::get();
 
 					// Start next proposal round if no proposal did receive enough votes
 					if <ProposalWinners<T>>::get(round).len() == 0 {
+						Self::incr_round();
 						*state = States::Propose;
-						transit_time = T::ProposeRoundDuration::get();
-						if round == u8::MAX { Round::put(0); }
-						else { Round::put(round+1); }
+						// A configuration scheduled by governance might have been applied
+						transit_time = Self::config().propose_round_duration;
 						return *state;
 					}
 
 					*state = States::Concern;
-					transit_time = T::ConcernRoundDuration::get();
+					transit_time = config.concern_round_duration;
 				},
 				States::Concern => {
 					// Skip VoteConcern if no concerns exist
 					if <ConcernCount>::get() == 0 {
 						// Add every proposal and its concerns to a freshly created council poll
-						let round: u8 = <Round>::get();
-						let winners: VecDeque<ProposalWinner<IdentityId<T>>> = <ProposalWinners<T>>::get(&round);
+						let round: RoundIndex = <Round>::get();
+						let winners: VecDeque<ProposalWinnerOf<T>> = <ProposalWinners<T>>::get(&round);
 						Self::add_council_poll(winners);
 						*state = States::VoteCouncil;
-						transit_time = T::CouncilVoteRoundDuration::get();
+						transit_time = config.council_vote_round_duration;
 					} else {
-						transit_time = T::ConcernVoteDuration::get();
+						transit_time = config.concern_vote_duration;
 						*state = States::VoteConcern;
 					}
 				},
-				States::VoteConcern => {
+				States::VoteConcern | States::RevealConcern => {
+					// Hidden votes have to be revealed before they can be evaluated
+					if *state == States::VoteConcern && config.commit_reveal_voting {
+						*state = States::RevealConcern;
+						transit_time = config.vote_reveal_duration;
+						return *state;
+					}
+
 					// Determine winning concerns and add to associated winning proposals
-					let winners: VecDeque<ProposalWinner<IdentityId<T>>> = Self::evaluate_concern_votes();
+					let winners: VecDeque<ProposalWinnerOf<T>> = Self::evaluate_concern_votes();
 					// Add every proposal and its concerns to a freshly created council poll
 					Self::add_council_poll(winners);
-					transit_time = T::CouncilVoteRoundDuration::get();
+					transit_time = config.council_vote_round_duration;
 					*state = States::VoteCouncil;
 				},
 				States::VoteCouncil => {
 					let round = <Round>::get();
 					let winners = <ProposalWinners<T>>::get(&round);
+					let mut council_votes: Vec<(IdentityId<T>, ProposalCID)> = Vec::new();
 
 					// Get voting result and evaluate vote percentage
 					for (idx, ticket) in <CouncilVoteTickets>::get().iter().enumerate() {
 						// TODO: Better error handling (error = ticket number not found in council)
 						if let Some(result) = T::Council::get_result(ticket) {
+							council_votes.extend(result.iter().map(|v| (v.0.clone(), winners[idx].proposal.clone())));
 							let mut percentage_no = Permill::zero();
 							let mut votes_no: u32 = 0;
 
@@ -650,19 +1463,30 @@ impl<T: Trait> Module<T> {
 							}
 
 							// Spawn project from passed proposals
-							if percentage_no < T::CouncilAcceptConcernMinVotes::get() {
-								let _ = T::Project::spawn_project(winners[idx].clone());
+							let accepted = percentage_no < config.council_accept_concern_min_votes;
+							Self::update_summary(round, |summary| {
+								summary.council_result.push((winners[idx].proposal.clone(), accepted));
+							});
+
+							if accepted {
+								Self::deposit_event(Event::<T>::CouncilAcceptedProposal(winners[idx].clone(), result));
+
+								if let Ok(project) = T::Project::spawn_project(winners[idx].clone()) {
+									Self::update_summary(round, |summary| summary.projects.push(project.id));
+									Self::reward_proposer(round, &project);
+								}
 							} else {
-								Event::<T>::CouncilDeniedProposal(winners[idx].clone(), result);
+								Self::deposit_event(Event::<T>::CouncilDeniedProposal(winners[idx].clone(), result));
 							}
 						}
 					}
 
+					Self::reward_council_votes(round, council_votes);
 					// increment round and rotate state
-					if round == u8::MAX { Round::put(0); }
-					else { Round::put(round+1); }
+					Self::incr_round();
 					*state = States::Propose;
-					transit_time = T::ProposeRoundDuration::get();
+					// A configuration scheduled by governance might have been applied
+					transit_time = Self::config().propose_round_duration;
 				}
 			}
 		*state
@@ -688,58 +1512,116 @@ impl<T: Trait> Module<T> {
 	}
 
 	/// On state transit from VoteConcern, evaluate all concerns and votes and pay winners and correct voters.
-	fn evaluate_concern_votes() -> VecDeque<ProposalWinner<IdentityId<T>>> {
-		let total_votes: u32 = <ConcernVoteCount>::get();
-		let round: u8 = <Round>::get();
-		let mut winners: VecDeque<ProposalWinner<IdentityId<T>>> = <ProposalWinners<T>>::get(&round);
+	fn evaluate_concern_votes() -> VecDeque<ProposalWinnerOf<T>> {
+		let round: RoundIndex = <Round>::get();
+		let mut winners: VecDeque<ProposalWinnerOf<T>> = <ProposalWinners<T>>::get(&round);
 		let mut total_reward_issued = BalanceOf::<T>::from(0);
-		let reward_propose: BalanceOf<T> = T::ConcernReward::get();
-		let reward_vote: BalanceOf<T> = T::ConcernVoteCorrectReward::get();
+		let mut passed: Vec<(IdentityId<T>, ConcernCID)> = Vec::new();
+
+		// Identities that did not vote vote like the identity their delegation chain ends at
+		let direct: BTreeMap<IdentityId<T>, Vec<(ConcernCID, ProposalCID)>> = <ConcernVotes<T>>::iter()
+			.filter(|(_, votes)| !votes.is_empty())
+			.collect();
+		let delegated = Self::delegated_voters(VoteTopic::Concern, &|id: &IdentityId<T>| {
+			direct.contains_key(id) || !<ConcernVoteCommits<T>>::get(id).is_empty()
+		});
+
+		// Tally the direct and delegated votes of every concern
+		let mut tally: BTreeMap<(ConcernCID, ProposalCID), u32> = BTreeMap::new();
+		let mut total_votes: u32 = 0;
+
+		for votes in direct.values().chain(delegated.iter().filter_map(|(_, d)| direct.get(d))) {
+			for vote in votes.iter() {
+				total_votes = total_votes.saturating_add(1);
+				let entry = tally.entry(vote.clone()).or_insert(0);
+				*entry = entry.saturating_add(1);
+			}
+		}
+
+		let acceptance_min: Permill = Self::config().concern_vote_acceptance_min;
 
 		// Drain all Concerns and add winners into winner variable and into storage ProposalWinners
 		for (id, concerns) in <Concerns<T>>::drain() {
 			for concern in concerns.iter() {
+				// The availability is not required after the vote
+				T::Documents::forget(&concern.concern);
 				// Here we inspect every single concern of a specific user. Add it if it won.
 				let mut vote_ratio = Permill::zero();
 
 				if total_votes > 0 {
-					vote_ratio = Permill::from_rational_approximation(concern.votes, total_votes);
+					let votes: u32 = tally.get(&(concern.concern.clone(), concern.associated_proposal.clone()))
+						.copied().unwrap_or(0);
+					vote_ratio = Permill::from_rational_approximation(votes, total_votes);
 				}
 
-				if vote_ratio >= T::ConcernVoteAcceptanceMin::get() {
+				if vote_ratio >= acceptance_min {
 					if let Some(winner) = winners.iter_mut().find(|el| el.proposal == concern.associated_proposal) {
 						winner.concerns.push(concern.concern.clone());
-
-						if T::Currency::deposit_into_existing(&T::Identity::get_address(&id), reward_propose).is_ok() {
-							total_reward_issued += reward_propose;
-						}
+						Self::deposit_event(Event::<T>::ConcernPassed(
+							round, concern.concern.clone(), concern.associated_proposal.clone()
+						));
+						passed.push((id.clone(), concern.concern.clone()));
 					}
 				}
 			}
 		}
 
+		// The era budget for passed concerns is shared among their submitters
+		let reward_propose: BalanceOf<T> = T::Treasury::reward_share(RewardCategory::ConcernSubmit, passed.len() as u32);
+
+		for (id, concern) in passed.into_iter() {
+			// TODO: Error handling
+			if T::Treasury::pay(&T::Identity::get_address(&id), reward_propose).is_ok() {
+				total_reward_issued += reward_propose;
+				Self::deposit_event(Event::<T>::ConcernSubmitReward(id, concern, reward_propose));
+			}
+		}
+
+		// Discard unrevealed commitments, they forfeit the reward for a correct vote
+		for (id, commits) in <ConcernVoteCommits<T>>::drain() {
+			if commits.len() > 0 {
+				Self::deposit_event(Event::<T>::VoteCommitsForfeited(id, commits.len() as u32));
+			}
+		}
+
+		// Only count votes for winning concerns
+		let is_winner = |v: &(ConcernCID, ProposalCID)| {
+			winners.iter().any(|w| w.proposal == v.1 && w.concerns.contains(&v.0))
+		};
+
+		// The era budget for correct votes is shared among all correct direct and delegated votes
+		let correct_votes: usize = direct.values().chain(delegated.iter().filter_map(|(_, d)| direct.get(d)))
+			.map(|votes| votes.iter().filter(|v| is_winner(v)).count())
+			.sum();
+		let reward_vote: BalanceOf<T> = T::Treasury::reward_share(RewardCategory::ConcernVote, correct_votes as u32);
+
 		// Drain all voters ProposalVotes and reward them if the proposal they voted for won
 		for (id, votes) in <ConcernVotes<T>>::drain() {
-			for _ in votes.iter().filter(|v| {
-				// Only count votes for winning proposals
-				for winner in winners.iter() {
-					for concern in winner.concerns.iter() {
-						if *concern == **v { return true; }
-					}
-				}
-				false
-			}) {
-				// TODO: When tx by identity is implemented, change to deposit_creating
-				// (since identity does not require to spend fees for tx,
-				// the account might not have been created on chain)
+			for vote in votes.iter().filter(|v| is_winner(v)) {
 				// TODO: Error handling
-				if T::Currency::deposit_into_existing(&T::Identity::get_address(&id), reward_vote).is_ok() {
+				if T::Treasury::pay(&T::Identity::get_address(&id), reward_vote).is_ok() {
 					total_reward_issued += reward_vote;
+					Self::deposit_event(Event::<T>::ConcernVoteReward(id.clone(), vote.0.clone(), reward_vote));
+				}
+			}
+		}
+
+		// Reward delegators and their delegates if the concern the delegate voted for won
+		for (delegator, delegate) in delegated.iter() {
+			if let Some(votes) = direct.get(delegate) {
+				for vote in votes.iter().filter(|v| is_winner(v)) {
+					total_reward_issued += Self::reward_delegated_vote(delegator, delegate, &vote.0, reward_vote);
 				}
 			}
 		}
 
 		ProposalWinners::<T>::insert(round, winners.clone());
+		Self::update_summary(round, |summary| {
+			summary.concern_winners = winners.iter()
+				.flat_map(|w| w.concerns.iter().map(move |c| (c.clone(), w.proposal.clone())))
+				.collect();
+			summary.total_reward = summary.total_reward.saturating_add(total_reward_issued);
+		});
 		// Clear ProposalToIdentity, ProposalVoteCount, ProposalCount
 		// Avoid collecting the iterator to avoid creating a new Vector
 		ConcernToIdentity::<T>::drain().nth(usize::MAX);
@@ -752,50 +1634,113 @@ impl<T: Trait> Module<T> {
 
 	/// On state transit from VotePropose, evaluate all proposals and votes and pay correct voters.
 	fn evaluate_proposal_votes() {
-		let total_votes: u32 = <ProposalVoteCount>::get();
-		let round: u8 = <Round>::get();
-		let mut winners: Vec<ProposalWinner<IdentityId<T>>> = Vec::new();
+		let round: RoundIndex = <Round>::get();
+		let mut winners: Vec<ProposalWinnerOf<T>> = Vec::new();
 		let mut total_reward_issued = BalanceOf::<T>::from(0);
-		let reward: BalanceOf<T> = T::ProposeVoteCorrectReward::get();
+
+		// Identities that did not vote vote like the identity their delegation chain ends at
+		let direct: BTreeMap<IdentityId<T>, Vec<ProposalCID>> = <ProposalVotes<T>>::iter()
+			.filter(|(_, votes)| !votes.is_empty())
+			.collect();
+		let delegated = Self::delegated_voters(VoteTopic::Proposal, &|id: &IdentityId<T>| {
+			direct.contains_key(id) || !<ProposalVoteCommits<T>>::get(id).is_empty()
+		});
+
+		// Tally the effective direct and delegated votes of every proposal according to ProposalTally
+		let mut tally: BTreeMap<ProposalCID, u32> = BTreeMap::new();
+		let mut total_votes: u32 = 0;
+
+		for votes in direct.values().chain(delegated.iter().filter_map(|(_, d)| direct.get(d))) {
+			for (proposal, n) in Self::count_votes(votes) {
+				let effective = T::ProposalTally::effective_votes(n);
+				total_votes = total_votes.saturating_add(effective);
+				let entry = tally.entry(proposal.clone()).or_insert(0);
+				*entry = entry.saturating_add(effective);
+			}
+		}
+
+		// Votes for unavailable proposals are not counted
+		let unavailable: Vec<ProposalCID> = tally.keys()
+			.filter(|proposal| T::Documents::is_unavailable(proposal))
+			.cloned()
+			.collect();
+
+		for proposal in unavailable.iter() {
+			if let Some(votes) = tally.remove(proposal) {
+				total_votes = total_votes.saturating_sub(votes);
+			}
+		}
+
+		let acceptance_min: Permill = Self::config().propose_vote_acceptance_min;
 
 		// Drain all Proposals and put winners into winner variable and into storage ProposalWinners
 		for (id, proposals) in <Proposals<T>>::drain() {
 			for proposal in proposals.iter() {
+				// The availability is not required after the vote
+				let unavailable = T::Documents::is_unavailable(&proposal.proposal);
+				T::Documents::forget(&proposal.proposal);
+
+				if unavailable {
+					Self::deposit_event(Event::<T>::ProposalExcluded(round, proposal.proposal.clone()));
+					continue;
+				}
+
 				// Here we inspect every single proposal of a specific user. Add it if it won.
 				let mut vote_ratio = Permill::zero();
 
 				if total_votes > 0 {
-					vote_ratio = Permill::from_rational_approximation(proposal.votes, total_votes);
+					let votes: u32 = tally.get(&proposal.proposal).copied().unwrap_or(0);
+					vote_ratio = Permill::from_rational_approximation(votes, total_votes);
 				}
 
-				if vote_ratio >= T::ProposeVoteAcceptanceMin::get() {
-					let document = ProposalWinner::<IdentityId<T>>::new(
-						Vec::new(), id.clone(), proposal.proposal.clone(), vote_ratio
+				if vote_ratio >= acceptance_min {
+					let document = ProposalWinnerOf::<T>::new(
+						Vec::new(), id.clone(), proposal.proposal.clone(), vote_ratio, proposal.metadata.clone()
 					);
 					winners.push(document);
 				}
 			}
 		}
 
-		winners.sort_by(|a, b| a.vote_ratio.cmp(&b.vote_ratio));
+		let winners: Vec<ProposalWinnerOf<T>> = Self::select_winners(round, winners);
+
+		for winner in winners.iter() {
+			Self::deposit_event(Event::<T>::ProposalPassed(round, winner.proposal.clone(), winner.vote_ratio));
+		}
+
 		ProposalWinners::<T>::insert(round, VecDeque::from(winners.clone()));
+		// Discard unrevealed commitments, they forfeit the reward for a correct vote
+		for (id, commits) in <ProposalVoteCommits<T>>::drain() {
+			if commits.len() > 0 {
+				Self::deposit_event(Event::<T>::VoteCommitsForfeited(id, commits.len() as u32));
+			}
+		}
+
+		// Only count votes for winning proposals
+		let is_winner = |v: &ProposalCID| winners.iter().any(|w| w.proposal == *v);
+
+		// The era budget for correct votes is shared among all correct direct and delegated votes
+		let correct_votes: usize = direct.values().chain(delegated.iter().filter_map(|(_, d)| direct.get(d)))
+			.map(|votes| votes.iter().filter(|v| is_winner(v)).count())
+			.sum();
+		let reward: BalanceOf<T> = T::Treasury::reward_share(RewardCategory::ProposalVote, correct_votes as u32);
+
 		// Drain all voters ProposalVotes and reward them if the proposal they voted for won
 		for (id, votes) in <ProposalVotes<T>>::drain() {
-			for _ in votes.iter().filter(|v| {
-				// Only count votes for winning proposals
-				for winner in winners.iter() {
-					if winner.proposal == **v {
-						return true;
-					}
-				}
-				false
-			}) {
-				// TODO: When tx by identity is implemented, change to deposit_creating
-				// (since identity does not require to spend fees for tx,
-				// the account might not have been created on chain)
+			for vote in votes.iter().filter(|v| is_winner(v)) {
 				// TODO: Error handling
-				if T::Currency::deposit_into_existing(&T::Identity::get_address(&id), reward).is_ok() {
+				if T::Treasury::pay(&T::Identity::get_address(&id), reward).is_ok() {
 					total_reward_issued += reward;
+					Self::deposit_event(Event::<T>::ProposalVoteReward(id.clone(), vote.clone(), reward));
+				}
+			}
+		}
+
+		// Reward delegators and their delegates if the proposal the delegate voted for won
+		for (delegator, delegate) in delegated.iter() {
+			if let Some(votes) = direct.get(delegate) {
+				for vote in votes.iter().filter(|v| is_winner(v)) {
+					total_reward_issued += Self::reward_delegated_vote(delegator, delegate, vote, reward);
 				}
 			}
 		}
@@ -805,13 +1750,266 @@ impl<T: Trait> Module<T> {
 		ProposalToIdentity::<T>::drain().nth(usize::MAX);
 		ProposalVoteCount::put(0);
 		ProposalCount::put(0);
+		Self::update_summary(round, |summary| {
+			summary.total_reward = summary.total_reward.saturating_add(total_reward_issued);
+		});
 		Self::deposit_event(Event::<T>::TotalProposalReward(total_reward_issued));
 	}
 
-	/*fn incr_round() {
-		<Round>::mutate(|r| {
-			if *r == u8::MAX { *r = 0; }
-			else { *r += 1; }
+	/// Rank the winners by vote share (descending) and admit them as long as the requested
+	/// budgets fit into ProposeRoundBudget. Defer the remaining winners to the next round.
+	fn select_winners(round: RoundIndex, mut candidates: Vec<ProposalWinnerOf<T>>) -> Vec<ProposalWinnerOf<T>> {
+		let mut remaining: BalanceOf<T> = Self::config().propose_round_budget;
+		let mut winners: Vec<ProposalWinnerOf<T>> = Vec::new();
+		let mut deferred: Vec<ProposalWinnerOf<T>> = Vec::new();
+
+		candidates.sort_by(|a, b| b.vote_ratio.cmp(&a.vote_ratio));
+
+		for candidate in candidates.into_iter() {
+			if candidate.metadata.budget <= remaining {
+				remaining -= candidate.metadata.budget;
+				winners.push(candidate);
+			} else {
+				Self::deposit_event(Event::<T>::ProposalDeferred(
+					round, candidate.proposal.clone(), candidate.metadata.budget, remaining
+				));
+				deferred.push(candidate);
+			}
+		}
+
+		<DeferredProposals<T>>::put(deferred);
+		winners
+	}
+
+	/// Resubmit the proposals that were deferred in the previous round. Proposals that exceed
+	/// ProposeCap, ProposeIdentifiedUserCap or the round budget of the new configuration are dropped.
+	fn carry_over_deferred(round: RoundIndex) {
+		let config = Self::config();
+
+		for winner in <DeferredProposals<T>>::take().into_iter() {
+			// The CID might have been submitted again in the meantime
+			if <ProposalToIdentity<T>>::contains_key(&winner.proposal) {
+				continue;
+			}
+
+			if <ProposalCount>::get() >= config.propose_cap
+				|| <Proposals<T>>::decode_len(&winner.proposer).unwrap_or(0)
+					>= config.propose_identified_user_cap.into()
+				|| winner.metadata.budget > config.propose_round_budget
+			{
+				Self::deposit_event(Event::<T>::ProposalDropped(round, winner.proposal));
+				continue;
+			}
+
+			let document = ProposalOf::<T>::new(winner.proposal.clone(), winner.metadata);
+			<Proposals<T>>::mutate(&winner.proposer, |user_proposals| {
+				user_proposals.push(document);
+			});
+			ProposalToIdentity::<T>::insert(&winner.proposal, &winner.proposer);
+			T::Documents::register(&winner.proposal);
+			<ProposalCount>::mutate(|pc| *pc += 1);
+			Self::deposit_event(Event::<T>::ProposalCarriedOver(round, winner.proposal));
+		}
+	}
+
+	/// Pay the proposer the share ProposeReward of the approved budget of the spawned project.
+	/// Nothing is paid or recorded for projects without a budget.
+	fn reward_proposer(round: RoundIndex, project: &ProjectType<BalanceOf<T>, T::BlockNumber, IdentityId<T>>) {
+		let reward: BalanceOf<T> = Self::config().propose_reward * project.budget;
+		let proposer: &IdentityId<T> = &project.proposal.proposer;
+
+		if reward.is_zero() {
+			return;
+		}
+
+		// The treasury queues payouts it cannot cover, it only fails if the transfer is invalid
+		if let Err(e) = T::Treasury::pay(&T::Identity::get_address(proposer), reward) {
+			debug::warn!("Unable to pay the proposer reward of project {}: {:?}", project.id, e);
+			return;
+		}
+
+		let _ = T::Project::set_proposer_reward(project.id, reward);
+		Self::update_summary(round, |summary| summary.total_reward = summary.total_reward.saturating_add(reward));
+		Self::deposit_event(Event::<T>::ProposerReward(project.id, proposer.clone(), reward));
+	}
+
+	/// Share the era budget for council votes among every vote cast in the council polls
+	fn reward_council_votes(round: RoundIndex, votes: Vec<(IdentityId<T>, ProposalCID)>) {
+		let reward: BalanceOf<T> = T::Treasury::reward_share(RewardCategory::CouncilVote, votes.len() as u32);
+
+		if reward.is_zero() {
+			return;
+		}
+
+		let mut total_reward_issued = BalanceOf::<T>::from(0);
+
+		for (member, proposal) in votes.into_iter() {
+			// The treasury queues payouts it cannot cover, it only fails if the transfer is invalid
+			if T::Treasury::pay(&T::Identity::get_address(&member), reward).is_ok() {
+				total_reward_issued += reward;
+				Self::deposit_event(Event::<T>::CouncilVoteReward(member, proposal, reward));
+			}
+		}
+
+		Self::update_summary(round, |summary| {
+			summary.total_reward = summary.total_reward.saturating_add(total_reward_issued);
 		});
-	}*/
+	}
+
+	/// Get the current state, round and block of the next state transit
+	pub fn get_phase() -> (States, RoundIndex, T::BlockNumber) {
+		(<State>::get(), <Round>::get(), <NextTransit<T>>::get())
+	}
+
+	/// Commitment of a hidden vote for a proposal. Binding it to the voter and the round
+	/// prevents others from replaying a revealed commitment.
+	pub fn proposal_commitment(id: &IdentityId<T>, round: RoundIndex, proposal: &ProposalCID, salt: &Salt) -> T::Hash {
+		T::Hashing::hash_of(&(id, round, proposal, salt))
+	}
+
+	/// Commitment of a hidden vote for a concern
+	pub fn concern_commitment(id: &IdentityId<T>, round: RoundIndex, concern: &ConcernCID, proposal: &ProposalCID,
+		salt: &Salt) -> T::Hash
+	{
+		T::Hashing::hash_of(&(id, round, concern, proposal, salt))
+	}
+
+	/// Get up to limit proposals of the current round, skipping the first start proposals.
+	/// Proposals are removed after the VotePropose round, earlier rounds have to be queried
+	/// at a block within that round. Fails with RoundNotCurrent for any other round.
+	pub fn get_proposals(round: RoundIndex, start: u32, limit: u32)
+		-> Result<Vec<(IdentityId<T>, ProposalOf<T>)>, DispatchError>
+	{
+		ensure!(round == <Round>::get(), Error::<T>::RoundNotCurrent);
+
+		Ok(<Proposals<T>>::iter()
+			.flat_map(|(id, proposals)| proposals.into_iter().map(move |p| (id.clone(), p)))
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect())
+	}
+
+	/// Get up to limit proposals of the current round in a category, skipping the first start proposals
+	pub fn get_proposals_by_category(category: CategoryId, start: u32, limit: u32)
+		-> Vec<(IdentityId<T>, ProposalOf<T>)>
+	{
+		<Proposals<T>>::iter()
+			.flat_map(|(id, proposals)| proposals.into_iter().map(move |p| (id.clone(), p)))
+			.filter(|(_, p)| p.metadata.category == category)
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect()
+	}
+
+	/// Get up to limit concerns of the current round, skipping the first start concerns.
+	/// Concerns are removed after the VoteConcern round, earlier rounds have to be queried
+	/// at a block within that round. Fails with RoundNotCurrent for any other round.
+	pub fn get_concerns(round: RoundIndex, start: u32, limit: u32)
+		-> Result<Vec<(IdentityId<T>, Concern)>, DispatchError>
+	{
+		ensure!(round == <Round>::get(), Error::<T>::RoundNotCurrent);
+
+		Ok(<Concerns<T>>::iter()
+			.flat_map(|(id, concerns)| concerns.into_iter().map(move |c| (id.clone(), c)))
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect())
+	}
+
+	/// Update the summary of a round
+	fn update_summary<F: FnOnce(&mut RoundSummaryOf<T>)>(round: RoundIndex, f: F) {
+		<RoundSummaries<T>>::mutate(round, |summary| f(summary.get_or_insert_with(Default::default)));
+	}
+
+	/// Get the winners of a round. Winners of finished rounds are kept in the round summary.
+	fn winners_of(round: RoundIndex) -> Vec<ProposalWinnerOf<T>> {
+		if round == <Round>::get() {
+			<ProposalWinners<T>>::get(round).into_iter().collect()
+		} else {
+			<RoundSummaries<T>>::get(round).map_or(Vec::new(), |summary| summary.winners)
+		}
+	}
+
+	/// Get up to limit winners of a round, skipping the first start winners
+	pub fn get_winners(round: RoundIndex, start: u32, limit: u32) -> Vec<ProposalWinnerOf<T>> {
+		Self::winners_of(round).into_iter()
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect()
+	}
+
+	/// Get up to limit winners of a round in a category, skipping the first start winners
+	pub fn get_winners_by_category(round: RoundIndex, category: CategoryId, start: u32, limit: u32)
+		-> Vec<ProposalWinnerOf<T>>
+	{
+		Self::winners_of(round).into_iter()
+			.filter(|w| w.metadata.category == category)
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect()
+	}
+
+	/// Get all proposal categories
+	pub fn get_categories() -> Vec<(CategoryId, Vec<u8>)> {
+		<Categories>::iter().collect()
+	}
+
+	/// Get the voice credits (proposals) and votes (concerns) an identity can still spend
+	pub fn get_vote_budget(id: &IdentityId<T>) -> VoteBudget {
+		let level = T::Identity::get_identity_level(id);
+		let mut budget = VoteBudget::default();
+		let config = Self::config();
+
+		if level >= config.propose_vote_identity_level.into() {
+			let credits: u32 = Self::proposal_vote_credits()
+				.saturating_sub(Self::spent_credits(&<ProposalVotes<T>>::get(id)))
+				.saturating_sub(<ProposalVoteCommits<T>>::get(id).len() as u32);
+			budget.proposal_votes = credits.min(u16::MAX.into()) as u16;
+		}
+
+		if level >= config.concern_vote_identity_level.into() {
+			budget.concern_votes = config.concern_vote_max_per_identified_user
+				.saturating_sub(<ConcernVotes<T>>::get(id).len() as u16)
+				.saturating_sub(<ConcernVoteCommits<T>>::get(id).len() as u16);
+		}
+
+		budget
+	}
+
+	/// Close the current round and start the next proposal round
+	fn incr_round() {
+		// The final winners (including passed concerns) complete the summary of the round
+		let finished: RoundIndex = <Round>::get();
+		let winners: Vec<ProposalWinnerOf<T>> = <ProposalWinners<T>>::take(finished).into_iter().collect();
+		Self::update_summary(finished, |summary| summary.winners = winners);
+
+		let round = <Round>::mutate(|r| {
+			*r = r.saturating_add(1);
+			*r
+		});
+
+		// Remove delegations that were limited to a previous round
+		for topic in [VoteTopic::Proposal, VoteTopic::Concern].iter() {
+			let expired: Vec<IdentityId<T>> = <Delegations<T>>::iter_prefix(topic)
+				.filter(|(_, delegation)| !delegation.is_active(round))
+				.map(|(id, _)| id)
+				.collect();
+
+			for id in expired.iter() {
+				<Delegations<T>>::remove(topic, id);
+			}
+
+			<DelegationCount>::mutate(|count| *count = count.saturating_sub(expired.len() as u32));
+		}
+
+		// The configuration set by governance is used from the new round on
+		if let Some(config) = <PendingConfig<T>>::take() {
+			<ActiveConfig<T>>::put(config);
+			Self::deposit_event(Event::<T>::ConfigApplied(round));
+		}
+
+		Self::deposit_event(Event::<T>::NewRound(round));
+		T::Treasury::new_era();
+		Self::carry_over_deferred(round);
+	}
 }
@@ -0,0 +1,208 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::Vec,
+	sp_std::{cell::{Cell, RefCell}, collections::{btree_map::BTreeMap, vec_deque::VecDeque}},
+	storage::{IterableStorageDoubleMap, IterableStorageMap, StorageMap, StoragePrefixedMap, StorageValue,
+		migration::StorageIterator, unhashed},
+	traits::Get,
+	weights::Weight,
+};
+use pallet_community_identity::IdentityId;
+use pallet_proposal_types::{Concern, ConcernCID, Delegation, ProposalCID, ProposalWinner, RoundIndex};
+use sp_arithmetic::Permill;
+use crate::{ActiveConfig, Concerns, ConcernVotes, DelegationCount, Delegations, Module, Proposals, ProposalOf, ProposalWinners,
+	ProposalWinnerOf, Round, RoundSummaries, RoundSummaryOf, StorageVersion, Trait};
+
+/// Storage layouts of the pallet
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum Releases {
+	/// Rounds are counted with u8 (storage without a version)
+	V1_0_0,
+	/// Rounds are counted with RoundIndex and summarized in RoundSummaries
+	V2_0_0,
+	/// The proposal cycle is configured by ActiveConfig instead of runtime parameters
+	V3_0_0,
+	/// Delegations are counted in DelegationCount
+	V4_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Migrate the storage from any previous layout to the current layout
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if <StorageVersion>::get() == Releases::V1_0_0 {
+		weight = weight.saturating_add(migrate_to_wide_rounds::<T>());
+		<StorageVersion>::put(Releases::V2_0_0);
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if <StorageVersion>::get() == Releases::V2_0_0 {
+		weight = weight.saturating_add(migrate_to_active_config::<T>());
+		<StorageVersion>::put(Releases::V3_0_0);
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if <StorageVersion>::get() == Releases::V3_0_0 {
+		weight = weight.saturating_add(migrate_to_delegation_count::<T>());
+		<StorageVersion>::put(Releases::V4_0_0);
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Delegation before the round index was widened
+#[derive(Decode)]
+struct DelegationV0<IdentityId> {
+	delegate: IdentityId,
+	round: Option<u8>,
+}
+
+/// Proposal before amendments and metadata were added
+#[derive(Decode)]
+struct ProposalV0 {
+	proposal: ProposalCID,
+	votes: u32,
+}
+
+/// Concern before amendments were added
+#[derive(Decode)]
+struct ConcernV0 {
+	associated_proposal: ProposalCID,
+	concern: ConcernCID,
+	votes: u32,
+}
+
+/// Proposal winner before the metadata was added
+#[derive(Decode)]
+struct ProposalWinnerV0<IdentityId> {
+	concerns: Vec<ConcernCID>,
+	proposer: IdentityId,
+	proposal: ProposalCID,
+	vote_ratio: Permill,
+}
+
+/// V1_0_0 -> V2_0_0: Widen the round index from u8 to RoundIndex and add the fields that were
+/// introduced since the storage without a version.
+/// ProposalWinners of finished rounds are moved into RoundSummaries. The council result,
+/// spawned projects and rewards of those rounds are unknown and left empty, as is the metadata
+/// of proposals and winners.
+/// Does nothing if the storage already uses the wide layout.
+fn migrate_to_wide_rounds<T: Trait>() -> Weight {
+	let round_key = <Round>::hashed_key();
+
+	// The wide layout always stores the round (see genesis build)
+	if unhashed::get_raw(&round_key).map_or(false, |raw| raw.len() == RoundIndex::default().encode().len()) {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let current: RoundIndex = unhashed::get::<u8>(&round_key).unwrap_or(0).into();
+	<Round>::put(current);
+	let mut reads: u64 = 1;
+	let mut writes: u64 = 1;
+
+	// The old key is the encoded u8 round (identity hasher)
+	let old_winners: Vec<(Vec<u8>, VecDeque<ProposalWinnerV0<IdentityId<T>>>)> =
+		StorageIterator::new(b"Proposal", b"ProposalWinners").drain().collect();
+	// Values that cannot be decoded are not drained
+	<ProposalWinners<T>>::remove_all();
+
+	for (key, old) in old_winners.into_iter() {
+		reads += 1;
+		writes += 2;
+
+		let round: RoundIndex = match u8::decode(&mut &key[..]) {
+			Ok(round) => round.into(),
+			Err(_) => continue,
+		};
+		let winners: VecDeque<ProposalWinnerOf<T>> = old.into_iter()
+			.map(|w| ProposalWinner::new(w.concerns, w.proposer, w.proposal, w.vote_ratio, Default::default()))
+			.collect();
+
+		if round == current {
+			<ProposalWinners<T>>::insert(round, winners);
+			continue;
+		}
+
+		let mut summary = RoundSummaryOf::<T>::default();
+		summary.concern_winners = winners.iter()
+			.flat_map(|w| w.concerns.iter().map(move |c| (c.clone(), w.proposal.clone())))
+			.collect();
+		summary.winners = winners.into_iter().collect();
+		<RoundSummaries<T>>::insert(round, summary);
+	}
+
+	let proposals: Cell<u64> = Cell::new(0);
+	<Proposals<T>>::translate::<Vec<ProposalV0>, _>(|_, old| {
+		proposals.set(proposals.get() + 1);
+		Some(old.into_iter().map(|p| {
+			let mut proposal = ProposalOf::<T>::new(p.proposal, Default::default());
+			proposal.votes = p.votes;
+			proposal
+		}).collect())
+	});
+
+	// Votes for concerns referred to the concern only, the concern of a vote identifies its proposal
+	let concern_proposals: RefCell<BTreeMap<ConcernCID, ProposalCID>> = RefCell::new(BTreeMap::new());
+	let concerns: Cell<u64> = Cell::new(0);
+	<Concerns<T>>::translate::<Vec<ConcernV0>, _>(|_, old| {
+		concerns.set(concerns.get() + 1);
+		Some(old.into_iter().map(|c| {
+			concern_proposals.borrow_mut().entry(c.concern.clone()).or_insert_with(|| c.associated_proposal.clone());
+			let mut concern = Concern::new(c.concern, c.associated_proposal);
+			concern.votes = c.votes;
+			concern
+		}).collect())
+	});
+
+	let concern_votes: Cell<u64> = Cell::new(0);
+	<ConcernVotes<T>>::translate::<Vec<ConcernCID>, _>(|_, old| {
+		concern_votes.set(concern_votes.get() + 1);
+		Some(old.into_iter()
+			.filter_map(|c| concern_proposals.borrow().get(&c).cloned().map(|p| (c, p)))
+			.collect())
+	});
+
+	let delegations: Cell<u64> = Cell::new(0);
+	<Delegations<T>>::translate::<DelegationV0<IdentityId<T>>, _>(|_, _, old| {
+		delegations.set(delegations.get() + 1);
+		Some(Delegation::new(old.delegate, old.round.map(Into::into)))
+	});
+
+	let translated = proposals.get() + concerns.get() + concern_votes.get() + delegations.get();
+	T::DbWeight::get().reads_writes(reads + translated, writes + translated)
+}
+
+/// V2_0_0 -> V3_0_0: Initialize ActiveConfig with the runtime parameters that configured the
+/// proposal cycle before.
+fn migrate_to_active_config<T: Trait>() -> Weight {
+	<ActiveConfig<T>>::put(Module::<T>::default_config());
+	T::DbWeight::get().writes(1)
+}
+
+/// V3_0_0 -> V4_0_0: Count the existing delegations of both topics
+fn migrate_to_delegation_count<T: Trait>() -> Weight {
+	let count = <Delegations<T>>::iter().count() as u32;
+	<DelegationCount>::put(count);
+	T::DbWeight::get().reads_writes(count.into(), 1)
+}
@@ -1,13 +1,45 @@
-/*use crate::{Module, Trait};
+use crate::{ActiveConfig, GenesisConfig, Module, Trait, traits::OneVotePerSlot};
+use std::{cell::RefCell, collections::{BTreeMap, BTreeSet}};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+	dispatch::DispatchError, storage::StorageValue, traits::OnInitialize, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_arithmetic::Permill;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	ModuleId, traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
 use frame_system as system;
+use pallet_community_identity::{IdentityLevel, ProofType, traits::PeerReviewedPhysicalIdentity};
+use pallet_community_treasury::{issuance::{IssuanceCurve, RewardCategory}, traits::Treasury};
+use pallet_council::{DocumentCID, Ticket, traits::Council};
+use pallet_document_availability::traits::DocumentAvailability;
+use pallet_project::{traits::ProjectTrait, types::{DocumentCID as ProjectDocumentCID, PositionId, Project,
+	ProjectID, Worker}};
+use pallet_proposal_types::{CategoryId, CycleConfig, ProposalMetadata, ProposalWinner, States};
 
 impl_outer_origin! {
-	pub enum Origin for Test {}
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		proposal::ProposalModule,
+	}
+}
+
+mod proposal {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		frame_system<T>,
+		pallet_balances<T>,
+		pallet_scheduler<T>,
+		pallet_community_treasury<T>,
+		proposal<T>,
+	}
 }
 
 // Configure a mock runtime to test the pallet.
@@ -16,7 +48,8 @@ impl_outer_origin! {
 pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
-	pub const MaximumBlockWeight: Weight = 1024;
+	// Scheduled calls are postponed if they exceed the maximum weight
+	pub const MaximumBlockWeight: Weight = 2_000_000_000_000;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
 }
@@ -24,7 +57,7 @@ parameter_types! {
 impl system::Trait for Test {
 	type BaseCallFilter = ();
 	type Origin = Origin;
-	type Call = ();
+	type Call = Call;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
@@ -32,7 +65,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -43,19 +76,455 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Trait for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumBlockWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	// Every round starts an era
+	pub const EraDuration: u64 = 0;
+	pub const ActiveIdentities: u32 = 10;
+	pub const MaxPayoutsPerBlock: u32 = 10;
+}
+
+impl pallet_community_treasury::Trait for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type ModuleId = TreasuryModuleId;
+	type EraDuration = EraDuration;
+	type ActiveIdentities = ActiveIdentities;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type ConfigOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+/// Identity level of identities without an explicitly set level
+pub const DEFAULT_IDENTITY_LEVEL: IdentityLevel = 5;
+
+thread_local! {
+	static IDENTITY_LEVELS: RefCell<BTreeMap<u64, IdentityLevel>> = RefCell::new(BTreeMap::new());
+	static POLLS: RefCell<Vec<(Vec<DocumentCID>, u64, Vec<(u64, bool)>)>> = RefCell::new(Vec::new());
+	static PROJECTS: RefCell<Vec<Project<u64, u64, u64>>> = RefCell::new(Vec::new());
+	static SPAWN_FAILS: RefCell<bool> = RefCell::new(false);
+	static REGISTERED_DOCUMENTS: RefCell<BTreeSet<DocumentCID>> = RefCell::new(BTreeSet::new());
+	static UNAVAILABLE_DOCUMENTS: RefCell<BTreeSet<DocumentCID>> = RefCell::new(BTreeSet::new());
+}
+
+/// Identity double. Every address is an identity, levels are set by the tests.
+pub struct TestIdentity;
+
+impl PeerReviewedPhysicalIdentity<ProofType> for TestIdentity {
+	type Address = u64;
+	type Ticket = u64;
+	type Timestamp = u64;
+	type IdentityLevel = IdentityLevel;
+	type IdentityId = u64;
+
+	fn request_peer_review(user: u64, _identity_level: IdentityLevel, _at: u64) -> Result<u64, DispatchError> {
+		Ok(user)
+	}
+
+	fn approve_identity(_review_process: u64, _proof_data: ProofType) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn reject_identity(_review_process: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn report_missing(_review_process: u64, _missing: Vec<u64>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn get_appointments(_identity: &u64) -> Vec<(u64, Vec<u64>)> {
+		Vec::new()
+	}
+
+	fn get_identity_level(identity: &u64) -> IdentityLevel {
+		IDENTITY_LEVELS.with(|l| l.borrow().get(identity).copied().unwrap_or(DEFAULT_IDENTITY_LEVEL))
+	}
+
+	fn get_identity_id(address: &u64) -> u64 {
+		*address
+	}
+
+	fn get_address(identity: &u64) -> u64 {
+		*identity
+	}
+}
+
+/// Council double. Polls are stored in memory, the tests vote with the Council trait.
+pub struct TestCouncil;
+
+impl Council for TestCouncil {
+	type IdentityId = u64;
+	type Ticket = Ticket;
+	type BlockNumber = u64;
+	type DocumentCID = DocumentCID;
+
+	fn vote_council_member(_voter: u64, _candidate: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn vote_reelect_council(_voter: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn vote_reelect(_voter: u64, _member: u64) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn add_poll(documents: Vec<DocumentCID>, until: u64) -> Result<Ticket, DispatchError> {
+		POLLS.with(|p| {
+			let mut polls = p.borrow_mut();
+			polls.push((documents, until, Vec::new()));
+			Ok(polls.len() as Ticket - 1)
+		})
+	}
+
+	fn vote_poll(member: u64, poll: Ticket, accept: bool) -> Result<(), DispatchError> {
+		POLLS.with(|p| {
+			let mut polls = p.borrow_mut();
+			let poll = polls.get_mut(poll as usize).ok_or(DispatchError::Other("Poll does not exist"))?;
+			poll.2.push((member, accept));
+			Ok(())
+		})
+	}
+
+	fn get_result(poll: &Ticket) -> Option<Vec<(u64, bool)>> {
+		POLLS.with(|p| p.borrow().get(*poll as usize).map(|poll| poll.2.clone()))
+	}
+}
+
+/// Project double. Spawned projects are stored in memory, spawning can be set to fail.
+pub struct TestProject;
+
+impl ProjectTrait for TestProject {
+	type Balance = u64;
+	type IdentityId = u64;
+	type ProposalWinner = ProposalWinner<u64, u64, u64>;
+	type Project = Project<u64, u64, u64>;
+	type Worker = Worker<u64, u64, u64>;
+
+	fn spawn_project(proposal: Self::ProposalWinner) -> Result<Self::Project, DispatchError> {
+		if SPAWN_FAILS.with(|f| *f.borrow()) {
+			return Err(DispatchError::Other("Spawning the project failed"));
+		}
+
+		PROJECTS.with(|p| {
+			let mut projects = p.borrow_mut();
+			let mut project = Project::new(projects.len() as ProjectID, proposal);
+			project.budget = project.proposal.metadata.budget;
+			projects.push(project.clone());
+			Ok(project)
+		})
+	}
+
+	fn application_project_leader(_who: u64, _project: ProjectID, _application: ProjectDocumentCID)
+		-> Result<(), DispatchError>
+	{
+		Ok(())
+	}
+
+	fn vote_project_leader(_voter: u64, _pl: u64, _project: ProjectID) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn open_position(_pl: u64, _project: ProjectID, _position: PositionId) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn apply(_applicant: u64, _project: ProjectID, _position: PositionId, _application: ProjectDocumentCID)
+		-> Result<(), DispatchError>
+	{
+		Ok(())
+	}
+
+	fn offer_applicant(_pl: u64, _applicant: u64, _project: ProjectID, _position: PositionId,
+		_application: ProjectDocumentCID, _salary: u64) -> Result<(), DispatchError>
+	{
+		Ok(())
+	}
+
+	fn accept_offer(_applicant: u64, _project: ProjectID, _position: PositionId, _salary: u64)
+		-> Result<(), DispatchError>
+	{
+		Ok(())
+	}
+
+	fn vote_replace(_colleague: u64, _voter: u64, _project: ProjectID) -> Result<(), DispatchError> {
+		Ok(())
+	}
+
+	fn set_proposer_reward(project: ProjectID, reward: u64) -> Result<(), DispatchError> {
+		PROJECTS.with(|p| {
+			let mut projects = p.borrow_mut();
+			let project = projects.get_mut(project as usize).ok_or(DispatchError::Other("Project does not exist"))?;
+			project.proposer_reward = reward;
+			Ok(())
+		})
+	}
+
+	fn get_project(project: ProjectID) -> Result<Option<Self::Project>, DispatchError> {
+		Ok(PROJECTS.with(|p| p.borrow().get(project as usize).cloned()))
+	}
+
+	fn get_projects(start: ProjectID, limit: u32) -> Result<Vec<Self::Project>, DispatchError> {
+		Ok(PROJECTS.with(|p| p.borrow().iter().skip(start as usize).take(limit as usize).cloned().collect()))
+	}
+
+	fn get_workers(_project: ProjectID, _start: u32, _limit: u32) -> Result<Vec<Self::Worker>, DispatchError> {
+		Ok(Vec::new())
+	}
+
+	fn get_worker_projects(_identity: &u64, _start: u32, _limit: u32) -> Result<Vec<ProjectID>, DispatchError> {
+		Ok(Vec::new())
+	}
+}
+
+/// Availability double. Registered documents are stored in memory, the tests mark them as unavailable.
+pub struct TestDocuments;
+
+impl DocumentAvailability for TestDocuments {
+	type DocumentCID = DocumentCID;
+
+	fn register(document: &DocumentCID) {
+		REGISTERED_DOCUMENTS.with(|d| d.borrow_mut().insert(document.clone()));
+	}
+
+	fn is_unavailable(document: &DocumentCID) -> bool {
+		UNAVAILABLE_DOCUMENTS.with(|d| d.borrow().contains(document))
+	}
+
+	fn forget(document: &DocumentCID) {
+		REGISTERED_DOCUMENTS.with(|d| d.borrow_mut().remove(document));
+		UNAVAILABLE_DOCUMENTS.with(|d| d.borrow_mut().remove(document));
+	}
+}
+
+parameter_types! {
+	pub const IdentifiedUserPenality: u32 = 100;
+	pub const AllowVoteStacking: bool = false;
+	pub const CommitRevealVoting: bool = false;
+	pub const VoteRevealDuration: u64 = 5;
+	pub const MaxDelegationDepth: u32 = 2;
+	pub const DelegateRewardShare: Permill = Permill::from_percent(20);
+	pub const ProposeCap: u32 = 3;
+	pub const ProposeIdentifiedUserCap: u8 = 2;
+	pub const ProposeIdentityLevel: u8 = 2;
+	pub const ProposeMaxTitleLength: u32 = 16;
+	pub const ProposeReward: Permill = Permill::from_percent(10);
+	pub const ProposeRoundDuration: u64 = 10;
+	pub const ProposeVoteAcceptanceMin: Permill = Permill::from_percent(30);
+	pub const ProposeVoteDuration: u64 = 10;
+	pub const ProposeVoteIdentityLevel: u8 = 3;
+	pub const ProposeVoteMaxPerIdentifiedUser: u16 = 2;
+	pub const ProposeRoundBudget: u64 = 2_000;
+	pub const ConcernCap: u32 = 3;
+	pub const ConcernIdentifiedUserCap: u8 = 2;
+	pub const ConcernIdentityLevel: u8 = 2;
+	pub const ConcernRoundDuration: u64 = 10;
+	pub const ConcernVoteAcceptanceMin: Permill = Permill::from_percent(30);
+	pub const ConcernVoteDuration: u64 = 10;
+	pub const ConcernVoteIdentityLevel: u8 = 3;
+	pub const ConcernVoteMaxPerIdentifiedUser: u16 = 2;
+	pub const CouncilVoteRoundDuration: u64 = 10;
+	pub const CouncilAcceptConcernMinVotes: Permill = Permill::from_percent(50);
+}
+
 impl Trait for Test {
-	type Event = ();
+	type Event = Event;
+	type Currency = Balances;
+	type Scheduler = Scheduler;
+	type Proposal = Call;
+	type PalletsOrigin = OriginCaller;
+	type Identity = TestIdentity;
+	type Council = TestCouncil;
+	type Treasury = CommunityTreasury;
+	type Project = TestProject;
+	type Documents = TestDocuments;
+	type ConfigOrigin = EnsureRoot<u64>;
+	type IdentifiedUserPenality = IdentifiedUserPenality;
+	type AllowVoteStacking = AllowVoteStacking;
+	type CommitRevealVoting = CommitRevealVoting;
+	type VoteRevealDuration = VoteRevealDuration;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type DelegateRewardShare = DelegateRewardShare;
+	type ProposeCap = ProposeCap;
+	type ProposeIdentifiedUserCap = ProposeIdentifiedUserCap;
+	type ProposeIdentityLevel = ProposeIdentityLevel;
+	type ProposeMaxTitleLength = ProposeMaxTitleLength;
+	type ProposeReward = ProposeReward;
+	type ProposeRoundDuration = ProposeRoundDuration;
+	type ProposeVoteAcceptanceMin = ProposeVoteAcceptanceMin;
+	type ProposeVoteDuration = ProposeVoteDuration;
+	type ProposeVoteIdentityLevel = ProposeVoteIdentityLevel;
+	type ProposeVoteMaxPerIdentifiedUser = ProposeVoteMaxPerIdentifiedUser;
+	type ProposalTally = OneVotePerSlot;
+	type ProposeRoundBudget = ProposeRoundBudget;
+	type ConcernCap = ConcernCap;
+	type ConcernIdentifiedUserCap = ConcernIdentifiedUserCap;
+	type ConcernIdentityLevel = ConcernIdentityLevel;
+	type ConcernRoundDuration = ConcernRoundDuration;
+	type ConcernVoteAcceptanceMin = ConcernVoteAcceptanceMin;
+	type ConcernVoteDuration = ConcernVoteDuration;
+	type ConcernVoteIdentityLevel = ConcernVoteIdentityLevel;
+	type ConcernVoteMaxPerIdentifiedUser = ConcernVoteMaxPerIdentifiedUser;
+	type CouncilVoteRoundDuration = CouncilVoteRoundDuration;
+	type CouncilAcceptConcernMinVotes = CouncilAcceptConcernMinVotes;
+	type WeightInfo = ();
 }
 
-pub type TemplateModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Scheduler = pallet_scheduler::Module<Test>;
+pub type CommunityTreasury = pallet_community_treasury::Module<Test>;
+pub type ProposalModule = Module<Test>;
+
+/// Category that exists at genesis
+pub const CATEGORY: CategoryId = 0;
+/// Budget minted into the treasury every era (round)
+pub const ERA_BUDGET: u64 = 1_000;
+/// Funds of the treasury at genesis. Rewards are paid right away instead of being queued.
+pub const TREASURY_FUNDS: u64 = 1_000_000;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
-}*/
+	// Reset the test doubles, the thread might have run another test before
+	IDENTITY_LEVELS.with(|l| l.borrow_mut().clear());
+	POLLS.with(|p| p.borrow_mut().clear());
+	PROJECTS.with(|p| p.borrow_mut().clear());
+	SPAWN_FAILS.with(|f| *f.borrow_mut() = false);
+	REGISTERED_DOCUMENTS.with(|d| d.borrow_mut().clear());
+	UNAVAILABLE_DOCUMENTS.with(|d| d.borrow_mut().clear());
+
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(CommunityTreasury::account_id(), TREASURY_FUNDS)],
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_community_treasury::GenesisConfig::<Test> {
+		issuance_curve: IssuanceCurve::Fixed(ERA_BUDGET),
+		reward_split: vec![
+			(RewardCategory::ProposalVote, Permill::from_percent(50)),
+			(RewardCategory::ConcernSubmit, Permill::from_percent(25)),
+			(RewardCategory::ConcernVote, Permill::from_percent(25)),
+		],
+	}.assimilate_storage(&mut t).unwrap();
+	GenesisConfig {
+		state: States::Uninitialized,
+		categories: vec![(CATEGORY, b"general".to_vec())],
+	}.assimilate_storage::<Test>(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not stored in the genesis block
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Initialize the blocks up to (including) block n. Pays queued payouts and executes the
+/// scheduled state transits in every block.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		CommunityTreasury::on_initialize(System::block_number());
+		Scheduler::on_initialize(System::block_number());
+		ProposalModule::on_initialize(System::block_number());
+	}
+}
+
+/// Initialize the blocks up to the scheduled state transit
+pub fn run_to_next_transit() {
+	run_to_block(ProposalModule::next_transit());
+}
+
+/// Was the event deposited (in any block)?
+pub fn has_event(event: crate::Event<Test>) -> bool {
+	System::events().iter().any(|r| r.event == Event::proposal(event.clone()))
+}
+
+/// Metadata of a proposal in CATEGORY that requests budget
+pub fn metadata(budget: u64) -> ProposalMetadata<u64, u64> {
+	ProposalMetadata { title: b"title".to_vec(), category: CATEGORY, budget, duration: 100, roles: Vec::new() }
+}
+
+/// Change the configuration of the current round
+pub fn set_config<F: FnOnce(&mut CycleConfig<u64, u64>)>(f: F) {
+	<ActiveConfig<Test>>::mutate(f);
+}
+
+/// Set the identity level of an identity (see TestIdentity)
+pub fn set_identity_level(identity: u64, level: IdentityLevel) {
+	IDENTITY_LEVELS.with(|l| l.borrow_mut().insert(identity, level));
+}
+
+/// Let the next spawn_project of TestProject fail (or succeed)
+pub fn set_spawn_fails(fails: bool) {
+	SPAWN_FAILS.with(|f| *f.borrow_mut() = fails);
+}
+
+/// Report a registered document as unavailable in TestDocuments
+pub fn set_unavailable(document: &[u8]) {
+	assert!(REGISTERED_DOCUMENTS.with(|d| d.borrow().contains(document)), "Document is not registered");
+	UNAVAILABLE_DOCUMENTS.with(|d| d.borrow_mut().insert(document.to_vec()));
+}
+
+/// Documents that await the availability check in TestDocuments
+pub fn registered_documents() -> Vec<DocumentCID> {
+	REGISTERED_DOCUMENTS.with(|d| d.borrow().iter().cloned().collect())
+}
+
+/// Documents of the polls created in TestCouncil
+pub fn council_polls() -> Vec<Vec<DocumentCID>> {
+	POLLS.with(|p| p.borrow().iter().map(|poll| poll.0.clone()).collect())
+}
+
+/// Vote in the TestCouncil poll for a proposal
+pub fn council_vote(proposal: &[u8], votes: &[(u64, bool)]) {
+	let ticket = council_polls().iter().position(|documents| documents[0] == proposal)
+		.expect("No poll for the proposal") as Ticket;
+
+	for (member, accept) in votes.iter() {
+		TestCouncil::vote_poll(*member, ticket, *accept).unwrap();
+	}
+}
+
+/// Projects spawned by TestProject
+pub fn spawned_projects() -> Vec<Project<u64, u64, u64>> {
+	PROJECTS.with(|p| p.borrow().clone())
+}
+
+/// Funds the treasury can pay out
+pub fn treasury_available() -> u64 {
+	CommunityTreasury::available()
+}
@@ -0,0 +1,302 @@
+//! Property based tests of the proposal state machine. Random sequences of user actions and
+//! state transits are applied to the mock runtime and the invariants are checked after every step.
+
+use crate::{ConcernToIdentity, ConcernVotes, Concerns, mock::*, ProposalToIdentity, ProposalVotes, Proposals,
+	RawEvent, Round, RoundSummaries};
+use frame_support::{IterableStorageMap, StorageMap, StorageValue};
+use pallet_council::traits::Council;
+use pallet_proposal_types::{ConcernCID, ProposalCID, RoundIndex, States};
+use proptest::{collection::vec, prelude::*};
+use sp_arithmetic::Permill;
+use std::collections::BTreeSet;
+
+/// Identities that submit and vote (1..=IDENTITIES)
+const IDENTITIES: u64 = 8;
+/// Distinct proposal CIDs
+const PROPOSALS: u8 = 5;
+/// Distinct concern CIDs per proposal
+const CONCERNS: u8 = 3;
+/// Council members vote with the ids COUNCIL..COUNCIL + 3
+const COUNCIL: u64 = 100;
+
+#[derive(Clone, Debug)]
+enum Action {
+	Propose(u64, u8, u64),
+	WithdrawProposal(u64, u8),
+	Concern(u64, u8, u8),
+	Vote(u64, u8),
+	Unvote(u64, u8),
+	VoteConcern(u64, u8, u8),
+	/// Council member, index of the proposal winner, accept
+	CouncilVote(u64, usize, bool),
+	Transit,
+}
+
+fn action() -> impl Strategy<Value = Action> {
+	let who = 1..=IDENTITIES;
+
+	prop_oneof![
+		3 => (who.clone(), 0..PROPOSALS, 0..=1500u64).prop_map(|(w, p, b)| Action::Propose(w, p, b)),
+		1 => (who.clone(), 0..PROPOSALS).prop_map(|(w, p)| Action::WithdrawProposal(w, p)),
+		3 => (who.clone(), 0..CONCERNS, 0..PROPOSALS).prop_map(|(w, c, p)| Action::Concern(w, c, p)),
+		4 => (who.clone(), 0..PROPOSALS).prop_map(|(w, p)| Action::Vote(w, p)),
+		1 => (who.clone(), 0..PROPOSALS).prop_map(|(w, p)| Action::Unvote(w, p)),
+		4 => (who, 0..CONCERNS, 0..PROPOSALS).prop_map(|(w, c, p)| Action::VoteConcern(w, c, p)),
+		3 => (COUNCIL..COUNCIL + 3, 0..3usize, any::<bool>()).prop_map(|(m, i, a)| Action::CouncilVote(m, i, a)),
+		3 => Just(Action::Transit),
+	]
+}
+
+fn proposal_cid(proposal: u8) -> ProposalCID {
+	vec![b'P', proposal]
+}
+
+/// Concern CIDs are unique per proposal, so rewards can be attributed by the concern CID
+fn concern_cid(concern: u8, proposal: u8) -> ConcernCID {
+	vec![b'C', concern, proposal]
+}
+
+/// State observed by the harness across steps
+#[derive(Default)]
+struct Harness {
+	/// Number of NewRound events. Unlike the round index it never saturates.
+	rounds: u64,
+	/// (rounds, reward kind, identity, document) of every reward paid so far
+	rewards: BTreeSet<(u64, u8, u64, Vec<u8>)>,
+}
+
+impl Harness {
+	fn apply(&mut self, action: &Action) {
+		let state = ProposalModule::state();
+		let round = ProposalModule::round();
+		// Council polls of the round, captured before their evaluation
+		let council = if state == States::VoteCouncil { Some(Self::council_votes(round)) } else { None };
+		let projects = spawned_projects().len();
+
+		// Most actions are invalid in the current state or for the identity, errors are expected
+		match action.clone() {
+			Action::Propose(who, p, budget) => {
+				let _ = ProposalModule::propose(Origin::signed(who), proposal_cid(p), metadata(budget));
+			},
+			Action::WithdrawProposal(who, p) => {
+				let _ = ProposalModule::withdraw_proposal(Origin::signed(who), proposal_cid(p));
+			},
+			Action::Concern(who, c, p) => {
+				let _ = ProposalModule::concern(Origin::signed(who), concern_cid(c, p), proposal_cid(p));
+			},
+			Action::Vote(who, p) => {
+				let _ = ProposalModule::vote_proposal(Origin::signed(who), proposal_cid(p));
+			},
+			Action::Unvote(who, p) => {
+				let _ = ProposalModule::unvote_proposal(Origin::signed(who), proposal_cid(p));
+			},
+			Action::VoteConcern(who, c, p) => {
+				let _ = ProposalModule::vote_concern(Origin::signed(who), concern_cid(c, p), proposal_cid(p));
+			},
+			Action::CouncilVote(member, idx, accept) => {
+				if let Some(ticket) = ProposalModule::council_vote_tickets().get(idx) {
+					let voted = TestCouncil::get_result(ticket).map_or(false, |r| r.iter().any(|v| v.0 == member));
+
+					if state == States::VoteCouncil && !voted {
+						TestCouncil::vote_poll(member, *ticket, accept).unwrap();
+					}
+				}
+			},
+			Action::Transit => run_to_next_transit(),
+		}
+
+		self.check_transition(state, round);
+		check_vote_records();
+		self.check_rewards();
+		check_projects(round, council, projects);
+
+		if let Action::Transit = action {
+			check_evaluated_storage(state, round);
+		}
+
+		System::reset_events();
+	}
+
+	/// Council polls of the current round: (proposal, votes) in the order of the winners
+	fn council_votes(round: RoundIndex) -> Vec<(ProposalCID, Vec<(u64, bool)>)> {
+		let winners = ProposalModule::proposal_winners(round);
+
+		ProposalModule::council_vote_tickets().iter().enumerate()
+			.map(|(idx, ticket)| (winners[idx].proposal.clone(), TestCouncil::get_result(ticket).unwrap()))
+			.collect()
+	}
+
+	/// Only the transitions of the state machine are possible. The round changes with every
+	/// new proposal round and never wraps around.
+	fn check_transition(&mut self, before: States, round: RoundIndex) {
+		let after = ProposalModule::state();
+		let new_round = System::events().iter().any(|r| matches!(r.event, Event::proposal(RawEvent::NewRound(_))));
+
+		let valid = match (&before, &after) {
+			(b, a) if a == b => !new_round,
+			(States::Propose, States::VotePropose) | (States::VotePropose, States::Concern)
+				| (States::Concern, States::VoteConcern) | (States::Concern, States::VoteCouncil)
+				| (States::VoteConcern, States::VoteCouncil) => !new_round,
+			(States::VotePropose, States::Propose) | (States::VoteCouncil, States::Propose) => new_round,
+			_ => false,
+		};
+
+		assert!(valid, "Invalid transition {:?} -> {:?} (new round: {})", before, after, new_round);
+		assert!(ProposalModule::next_transit() > System::block_number(), "No state transit scheduled");
+
+		if new_round {
+			self.rounds += 1;
+			assert_eq!(ProposalModule::round(), round.saturating_add(1), "Round did not advance");
+		} else {
+			assert_eq!(ProposalModule::round(), round, "Round changed without a new round");
+		}
+	}
+
+	/// Every vote, concern and proposal is rewarded at most once per round and every
+	/// reward is accounted in the summary of the round it was paid in.
+	fn check_rewards(&mut self) {
+		// Rewards are paid before the round is incremented
+		let rounds = self.rounds - System::events().iter()
+			.filter(|r| matches!(r.event, Event::proposal(RawEvent::NewRound(_))))
+			.count() as u64;
+
+		for record in System::events().iter() {
+			let key = match &record.event {
+				Event::proposal(RawEvent::ProposalVoteReward(id, proposal, _)) => (rounds, 0, *id, proposal.clone()),
+				Event::proposal(RawEvent::ConcernSubmitReward(id, concern, _)) => (rounds, 1, *id, concern.clone()),
+				Event::proposal(RawEvent::ConcernVoteReward(id, concern, _)) => (rounds, 2, *id, concern.clone()),
+				// Project ids are unique across rounds
+				Event::proposal(RawEvent::ProposerReward(project, id, _)) => (0, 3, *id, project.to_le_bytes().to_vec()),
+				_ => continue,
+			};
+
+			assert!(self.rewards.insert(key.clone()), "Reward paid twice: {:?}", key);
+		}
+
+		let paid: u64 = (1..=IDENTITIES).map(Balances::free_balance).sum::<u64>()
+			+ CommunityTreasury::pending_total();
+		let accounted: u64 = RoundSummaries::<Test>::iter().map(|(_, summary)| summary.total_reward).sum();
+		assert_eq!(paid, accounted, "Paid rewards differ from the round summaries");
+	}
+}
+
+/// The vote and document counters match the stored votes and documents
+fn check_vote_records() {
+	let votes: Vec<ProposalCID> = ProposalVotes::<Test>::iter().flat_map(|(_, v)| v).collect();
+	assert_eq!(ProposalModule::vote_count() as usize, votes.len(), "ProposalVoteCount differs from the votes");
+	let mut proposals: usize = 0;
+
+	for (id, user_proposals) in Proposals::<Test>::iter() {
+		for proposal in user_proposals.iter() {
+			proposals += 1;
+			assert_eq!(ProposalModule::proposal_to_identity(&proposal.proposal), id);
+			assert_eq!(proposal.votes as usize, votes.iter().filter(|v| **v == proposal.proposal).count(),
+				"Vote count of {:?} differs from the votes", proposal.proposal);
+		}
+	}
+
+	assert_eq!(ProposalModule::proposal_count() as usize, proposals, "ProposalCount differs from the proposals");
+	assert!(votes.iter().all(|v| ProposalToIdentity::<Test>::contains_key(v)), "Vote for an unknown proposal");
+
+	let votes: Vec<(ConcernCID, ProposalCID)> = ConcernVotes::<Test>::iter().flat_map(|(_, v)| v).collect();
+	assert_eq!(ProposalModule::vote_count_concern() as usize, votes.len(), "ConcernVoteCount differs from the votes");
+	let mut concerns: usize = 0;
+
+	for (id, user_concerns) in Concerns::<Test>::iter() {
+		for concern in user_concerns.iter() {
+			concerns += 1;
+			let key = (concern.concern.clone(), concern.associated_proposal.clone());
+			assert_eq!(ProposalModule::concern_to_identity(&key), id);
+			assert_eq!(concern.votes as usize, votes.iter().filter(|v| **v == key).count(),
+				"Vote count of {:?} differs from the votes", key);
+		}
+	}
+
+	assert_eq!(ProposalModule::concern_count() as usize, concerns, "ConcernCount differs from the concerns");
+}
+
+/// Only proposals accepted by the council become projects
+fn check_projects(round: RoundIndex, council: Option<Vec<(ProposalCID, Vec<(u64, bool)>)>>, before: usize) {
+	let projects = spawned_projects();
+	let summaries: Vec<_> = RoundSummaries::<Test>::iter().map(|(_, summary)| summary).collect();
+
+	for project in projects.iter() {
+		assert!(summaries.iter().any(|s| {
+			s.projects.contains(&project.id) && s.council_result.contains(&(project.proposal.proposal.clone(), true))
+		}), "Project {} was not accepted by the council", project.id);
+	}
+
+	// The council evaluated the polls in this step, compare the decisions with the votes
+	if let (Some(council), true) = (council, ProposalModule::state() == States::Propose) {
+		let expected: Vec<(ProposalCID, bool)> = council.into_iter().map(|(proposal, votes)| {
+			let no = votes.iter().filter(|v| !v.1).count() as u32;
+			let ratio = if votes.is_empty() { Permill::zero() } else {
+				Permill::from_rational_approximation(no, votes.len() as u32)
+			};
+			(proposal, ratio < ProposalModule::config().council_accept_concern_min_votes)
+		}).collect();
+		let summary = ProposalModule::round_summary(round).expect("Summary of the evaluated round");
+
+		assert!(summary.council_result.ends_with(&expected), "Council result differs from the votes");
+		let accepted: Vec<&ProposalCID> = expected.iter().filter(|r| r.1).map(|r| &r.0).collect();
+		let spawned: Vec<&ProposalCID> = projects[before..].iter().map(|p| &p.proposal.proposal).collect();
+		assert_eq!(spawned, accepted, "Spawned projects differ from the accepted proposals");
+	} else {
+		assert_eq!(projects.len(), before, "Project spawned outside of the council evaluation");
+	}
+}
+
+/// The documents and votes of a round are removed when they were evaluated
+fn check_evaluated_storage(before: States, round: RoundIndex) {
+	match (before, ProposalModule::state()) {
+		(States::VotePropose, after) => {
+			assert_eq!(ProposalVotes::<Test>::iter().count(), 0, "Proposal votes left after evaluation");
+			assert_eq!(ProposalModule::vote_count(), 0);
+
+			// Deferred proposals are resubmitted when the evaluation starts a new round
+			if after == States::Propose {
+				assert!(Proposals::<Test>::iter().all(|(_, p)| p.iter().all(|p| p.votes == 0)));
+			} else {
+				assert_eq!(Proposals::<Test>::iter().count(), 0, "Proposals left after evaluation");
+				assert_eq!(ProposalToIdentity::<Test>::iter().count(), 0);
+				assert_eq!(ProposalModule::proposal_count(), 0);
+			}
+		},
+		(States::VoteConcern, _) => {
+			assert_eq!(Concerns::<Test>::iter().count(), 0, "Concerns left after evaluation");
+			assert_eq!(ConcernVotes::<Test>::iter().count(), 0, "Concern votes left after evaluation");
+			assert_eq!(ConcernToIdentity::<Test>::iter().count(), 0);
+			assert_eq!(ProposalModule::concern_count(), 0);
+			assert_eq!(ProposalModule::vote_count_concern(), 0);
+		},
+		(States::VoteCouncil, _) => {
+			assert!(ProposalModule::proposal_winners(round).is_empty(), "Winners left after the round");
+		},
+		_ => (),
+	}
+}
+
+fn run(start: RoundIndex, actions: Vec<Action>) {
+	new_test_ext().execute_with(|| {
+		<Round>::put(start);
+		let mut harness = Harness::default();
+
+		for action in actions.iter() {
+			harness.apply(action);
+		}
+	});
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(128))]
+
+	#[test]
+	fn state_machine_invariants(actions in vec(action(), 1..120)) {
+		run(0, actions);
+	}
+
+	#[test]
+	fn state_machine_invariants_at_last_round(actions in vec(action(), 1..120)) {
+		run(RoundIndex::max_value() - 1, actions);
+	}
+}
@@ -1,25 +1,975 @@
-/*
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Delegations, Error, migrations::{self, Releases}, mock::*, Proposals, ProposalVotes, ProposalWinners, Concerns,
+	ConcernVotes, RawEvent, Round, Salt, StorageVersion};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, IterableStorageMap,
+	storage::{StorageDoubleMap, StorageMap, StoragePrefixedMap, StorageValue, unhashed}};
+use pallet_community_treasury::{issuance::RewardCategory, traits::Treasury};
+use pallet_proposal_types::{RoundIndex, States, VoteTopic};
+use sp_arithmetic::Permill;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const P1: &[u8] = b"P1";
+const P2: &[u8] = b"P2";
+const P3: &[u8] = b"P3";
+const C1: &[u8] = b"C1";
+const C2: &[u8] = b"C2";
+const C3: &[u8] = b"C3";
+const SALT: Salt = [1; 32];
+
+fn propose(who: u64, proposal: &[u8], budget: u64) {
+	assert_ok!(ProposalModule::propose(Origin::signed(who), proposal.to_vec(), metadata(budget)));
+}
+
+fn vote(who: u64, proposal: &[u8]) {
+	assert_ok!(ProposalModule::vote_proposal(Origin::signed(who), proposal.to_vec()));
+}
+
+fn concern(who: u64, concern: &[u8], proposal: &[u8]) {
+	assert_ok!(ProposalModule::concern(Origin::signed(who), concern.to_vec(), proposal.to_vec()));
+}
+
+fn vote_concern(who: u64, concern: &[u8], proposal: &[u8]) {
+	assert_ok!(ProposalModule::vote_concern(Origin::signed(who), concern.to_vec(), proposal.to_vec()));
+}
+
+/// Propose P1 (account 1) and P2 (account 2), let P1 win and enter the concern round
+fn to_concern_round() {
+	propose(1, P1, 1000);
+	propose(2, P2, 500);
+	run_to_next_transit();
+	vote(4, P1);
+	run_to_next_transit();
+	assert_eq!(ProposalModule::state(), States::Concern);
+}
+
+/// Start the proposal cycle with hidden votes
+fn enable_commit_reveal() {
+	set_config(|config| config.commit_reveal_voting = true);
+}
+
+#[test]
+fn genesis_starts_propose_round() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ProposalModule::get_phase(), (States::Propose, 0, 10));
+		assert_eq!(ProposalModule::category(CATEGORY), Some(b"general".to_vec()));
+	});
+}
+
+#[test]
+fn propose_round_without_proposals_is_repeated() {
+	new_test_ext().execute_with(|| {
+		run_to_next_transit();
+		assert_eq!(ProposalModule::get_phase(), (States::Propose, 0, 20));
+	});
+}
+
+#[test]
+fn full_cycle_spawns_accepted_projects_and_pays_rewards() {
+	new_test_ext().execute_with(|| {
+		// Propose
+		propose(1, P1, 1000);
+		propose(2, P2, 500);
+		propose(3, P3, 100);
+		assert_eq!(ProposalModule::proposal_count(), 3);
+		run_to_next_transit();
+
+		// VotePropose: P1 60%, P2 40%, P3 0%
+		assert_eq!(ProposalModule::get_phase(), (States::VotePropose, 0, 20));
+		vote(4, P1);
+		vote(4, P2);
+		vote(5, P1);
+		vote(6, P2);
+		vote(7, P1);
+		assert_eq!(ProposalModule::vote_count(), 5);
+		run_to_next_transit();
+
+		// Five correct votes share the ProposalVote budget (500)
+		assert_eq!(ProposalModule::get_phase(), (States::Concern, 0, 30));
+		assert!(has_event(RawEvent::ProposalPassed(0, P1.to_vec(), Permill::from_percent(60))));
+		assert!(has_event(RawEvent::ProposalPassed(0, P2.to_vec(), Permill::from_percent(40))));
+		assert!(has_event(RawEvent::TotalProposalReward(500)));
+		let winners = ProposalModule::proposal_winners(0);
+		assert_eq!(winners.iter().map(|w| w.proposal.as_slice()).collect::<Vec<_>>(), vec![P1, P2]);
+		assert_eq!(Balances::free_balance(4), 200);
+		assert_eq!(Balances::free_balance(5), 100);
+		assert_eq!(ProposalModule::proposal_count(), 0);
+		assert_eq!(ProposalModule::vote_count(), 0);
+		assert_eq!(Proposals::<Test>::iter().count(), 0);
+		assert_eq!(ProposalVotes::<Test>::iter().count(), 0);
+
+		// Concern
+		concern(8, C1, P1);
+		concern(9, C2, P2);
+		run_to_next_transit();
+
+		// VoteConcern: C1 75%, C2 25%
+		assert_eq!(ProposalModule::get_phase(), (States::VoteConcern, 0, 40));
+		vote_concern(4, C1, P1);
+		vote_concern(5, C1, P1);
+		vote_concern(6, C2, P2);
+		vote_concern(7, C1, P1);
+		run_to_next_transit();
+
+		// The only passed concern receives the ConcernSubmit budget (250), three correct votes
+		// share the ConcernVote budget (250)
+		assert_eq!(ProposalModule::get_phase(), (States::VoteCouncil, 0, 50));
+		assert!(has_event(RawEvent::ConcernPassed(0, C1.to_vec(), P1.to_vec())));
+		assert!(has_event(RawEvent::TotalConcernReward(250 + 3 * 83)));
+		assert_eq!(ProposalModule::proposal_winners(0)[0].concerns, vec![C1.to_vec()]);
+		assert_eq!(ProposalModule::council_vote_tickets(), vec![0, 1]);
+		assert_eq!(council_polls(), vec![vec![P1.to_vec(), C1.to_vec()], vec![P2.to_vec()]]);
+		assert_eq!(ProposalModule::concern_count(), 0);
+		assert_eq!(ProposalModule::vote_count_concern(), 0);
+		assert_eq!(Concerns::<Test>::iter().count(), 0);
+		assert_eq!(ConcernVotes::<Test>::iter().count(), 0);
+
+		// VoteCouncil: P1 is denied, P2 is accepted
+		council_vote(P1, &[(100, false), (101, false)]);
+		council_vote(P2, &[(100, true), (101, true), (102, false)]);
+		run_to_next_transit();
+
+		assert_eq!(ProposalModule::get_phase(), (States::Propose, 1, 60));
+		assert!(has_event(RawEvent::NewRound(1)));
+		let projects = spawned_projects();
+		assert_eq!(projects.len(), 1);
+		assert_eq!(projects[0].proposal.proposal, P2.to_vec());
+		assert_eq!(projects[0].proposer_reward, 50);
+		assert!(has_event(RawEvent::ProposerReward(0, 2, 50)));
+
+		// Rewards: proposal votes + concern votes, concern submissions and the proposer reward
+		assert_eq!(Balances::free_balance(4), 200 + 83);
+		assert_eq!(Balances::free_balance(5), 100 + 83);
+		assert_eq!(Balances::free_balance(6), 100);
+		assert_eq!(Balances::free_balance(7), 100 + 83);
+		assert_eq!(Balances::free_balance(8), 250);
+		assert_eq!(Balances::free_balance(9), 0);
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 50);
+		assert_eq!(Balances::free_balance(3), 0);
+
+		let summary = ProposalModule::round_summary(0).unwrap();
+		assert_eq!(summary.winners.len(), 2);
+		assert_eq!(summary.concern_winners, vec![(C1.to_vec(), P1.to_vec())]);
+		assert_eq!(summary.council_result, vec![(P1.to_vec(), false), (P2.to_vec(), true)]);
+		assert_eq!(summary.projects, vec![0]);
+		assert_eq!(summary.total_reward, 500 + 250 + 3 * 83 + 50);
+		assert!(ProposalModule::proposal_winners(0).is_empty());
+	});
+}
+
+#[test]
+fn council_denial_spawns_no_project() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		run_to_next_transit();
+		council_vote(P1, &[(100, true), (101, false)]);
+		run_to_next_transit();
+		assert!(spawned_projects().is_empty());
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(ProposalModule::round_summary(0).unwrap().council_result, vec![(P1.to_vec(), false)]);
+	});
+}
+
+#[test]
+fn council_votes_share_reward() {
+	new_test_ext().execute_with(|| {
+		let split = vec![(RewardCategory::CouncilVote, Permill::from_percent(30))];
+		assert_ok!(CommunityTreasury::set_reward_split(Origin::root(), split));
+		CommunityTreasury::new_era();
+		to_concern_round();
+		run_to_next_transit();
+		council_vote(P1, &[(100, true), (101, false), (102, true)]);
+		run_to_next_transit();
+
+		// Every vote receives an equal share of the CouncilVote budget (300), no matter how it was cast
+		assert!(has_event(RawEvent::CouncilVoteReward(101, P1.to_vec(), 100)));
+		assert_eq!(Balances::free_balance(100), 100);
+		assert_eq!(Balances::free_balance(101), 100);
+		assert_eq!(Balances::free_balance(102), 100);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::CouncilVote), ERA_BUDGET * 3 / 10);
+	});
+}
+
+#[test]
+fn failed_spawn_pays_no_proposer_reward() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		run_to_next_transit();
+		set_spawn_fails(true);
+		run_to_next_transit();
+		assert_eq!(ProposalModule::round_summary(0).unwrap().council_result, vec![(P1.to_vec(), true)]);
+		assert!(ProposalModule::round_summary(0).unwrap().projects.is_empty());
+		assert_eq!(Balances::free_balance(1), 0);
+	});
+}
+
+#[test]
+fn no_concerns_skip_vote_concern() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		run_to_next_transit();
+		assert_eq!(ProposalModule::get_phase(), (States::VoteCouncil, 0, 40));
+		assert_eq!(council_polls(), vec![vec![P1.to_vec()]]);
+	});
+}
+
+#[test]
+fn proposals_without_winner_start_new_round() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		run_to_next_transit();
+		assert_eq!(ProposalModule::get_phase(), (States::Propose, 1, 30));
+		assert!(ProposalModule::round_summary(0).unwrap().winners.is_empty());
+		assert_eq!(Proposals::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn every_round_starts_treasury_era() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(CommunityTreasury::era(), 1);
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		vote(4, P1);
+		run_to_next_transit();
+		// The budget of the round was shared among the voters
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ProposalVote), 0);
+
+		run_to_next_transit();
+		run_to_next_transit();
+		assert_eq!(ProposalModule::get_phase().0, States::Propose);
+		assert_eq!(CommunityTreasury::era(), 2);
+		assert_eq!(CommunityTreasury::reward_budget(RewardCategory::ProposalVote), ERA_BUDGET / 2);
+	});
+}
+
+#[test]
+fn winners_exceeding_round_budget_are_deferred() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1500);
+		propose(2, P2, 1000);
+		run_to_next_transit();
+		vote(4, P1);
+		vote(5, P1);
+		vote(6, P2);
+		run_to_next_transit();
+		assert!(has_event(RawEvent::ProposalDeferred(0, P2.to_vec(), 1000, 500)));
+		assert_eq!(ProposalModule::proposal_winners(0).len(), 1);
+		assert_eq!(ProposalModule::deferred_proposals().len(), 1);
+
+		// The deferred proposal is resubmitted in the next round
+		run_to_next_transit();
+		run_to_next_transit();
+		assert_eq!(ProposalModule::get_phase().0, States::Propose);
+		assert!(has_event(RawEvent::ProposalCarriedOver(1, P2.to_vec())));
+		assert_eq!(ProposalModule::proposal_count(), 1);
+		assert_eq!(ProposalModule::proposals(2)[0].proposal, P2.to_vec());
+		assert!(ProposalModule::deferred_proposals().is_empty());
+	});
+}
+
+#[test]
+fn deferred_proposals_exceeding_new_config_are_dropped() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 2000);
+		propose(2, P2, 1000);
+		propose(3, P3, 1500);
+		run_to_next_transit();
+		vote(4, P1);
+		vote(5, P2);
+		vote(6, P3);
+		run_to_next_transit();
+		assert_eq!(ProposalModule::deferred_proposals().len(), 2);
+
+		// The configuration of the next round only admits one proposal with a budget up to 1000
+		set_config(|config| {
+			config.propose_cap = 1;
+			config.propose_round_budget = 1000;
+		});
+		run_to_next_transit();
+		run_to_next_transit();
+		assert!(has_event(RawEvent::ProposalDropped(1, P3.to_vec())));
+		assert!(has_event(RawEvent::ProposalCarriedOver(1, P2.to_vec())));
+		assert_eq!(ProposalModule::proposal_count(), 1);
+		assert!(ProposalModule::deferred_proposals().is_empty());
+	});
+}
+
+#[test]
+fn unavailable_proposal_is_excluded_from_vote() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(2, P2, 500);
+		run_to_next_transit();
+		vote(4, P1);
+		vote(5, P2);
+
+		// Votes cast before the report are discarded
+		set_unavailable(P1);
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(6), P1.to_vec()),
+			Error::<Test>::ProposalUnavailable);
+		assert_noop!(ProposalModule::move_vote(Origin::signed(5), P2.to_vec(), P1.to_vec()),
+			Error::<Test>::ProposalUnavailable);
+
+		run_to_next_transit();
+		assert!(has_event(RawEvent::ProposalExcluded(0, P1.to_vec())));
+		let winners = ProposalModule::proposal_winners(0);
+		assert_eq!(winners.len(), 1);
+		assert_eq!(winners[0].proposal, P2.to_vec());
+		assert!(has_event(RawEvent::ProposalPassed(0, P2.to_vec(), Permill::one())));
+		// Documents are forgotten after the vote
+		assert!(registered_documents().is_empty());
+	});
+}
+
+#[test]
+fn submitted_documents_are_registered() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_ok!(ProposalModule::amend_proposal(Origin::signed(1), P1.to_vec(), P3.to_vec(), metadata(1000)));
+		propose(2, P2, 500);
+		assert_eq!(registered_documents(), vec![P2.to_vec(), P3.to_vec()]);
+		assert_ok!(ProposalModule::withdraw_proposal(Origin::signed(2), P2.to_vec()));
+		assert_eq!(registered_documents(), vec![P3.to_vec()]);
+
+		run_to_next_transit();
+		vote(4, P3);
+		run_to_next_transit();
+		concern(8, C1, P3);
+		assert_ok!(ProposalModule::amend_concern(Origin::signed(8), C1.to_vec(), P3.to_vec(), C2.to_vec()));
+		assert_eq!(registered_documents(), vec![C2.to_vec()]);
+		assert_ok!(ProposalModule::withdraw_concern(Origin::signed(8), C2.to_vec(), P3.to_vec()));
+		assert!(registered_documents().is_empty());
+	});
+}
+
+#[test]
+fn amend_and_withdraw_proposal() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_ok!(ProposalModule::amend_proposal(Origin::signed(1), P1.to_vec(), P2.to_vec(), metadata(700)));
+		let proposal = &ProposalModule::proposals(1)[0];
+		assert_eq!(proposal.proposal, P2.to_vec());
+		assert_eq!(proposal.amends, Some(P1.to_vec()));
+		assert_eq!(proposal.metadata.budget, 700);
+		assert_eq!(ProposalModule::proposal_to_identity(P2.to_vec()), 1);
+		assert_eq!(ProposalModule::proposal_to_identity(P1.to_vec()), 0);
+
+		assert_ok!(ProposalModule::withdraw_proposal(Origin::signed(1), P2.to_vec()));
+		assert!(ProposalModule::proposals(1).is_empty());
+		assert_eq!(ProposalModule::proposal_count(), 0);
+		// Without proposals the round is repeated
+		run_to_next_transit();
+		assert_eq!(ProposalModule::state(), States::Propose);
+	});
+}
+
+#[test]
+fn amend_and_withdraw_concern() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		concern(8, C1, P1);
+		assert_ok!(ProposalModule::amend_concern(Origin::signed(8), C1.to_vec(), P1.to_vec(), C2.to_vec()));
+		assert_eq!(ProposalModule::concerns(8)[0].amends, Some(C1.to_vec()));
+		assert_eq!(ProposalModule::concern_to_identity((C2.to_vec(), P1.to_vec())), 8);
+
+		assert_ok!(ProposalModule::withdraw_concern(Origin::signed(8), C2.to_vec(), P1.to_vec()));
+		assert!(ProposalModule::concerns(8).is_empty());
+		assert_eq!(ProposalModule::concern_count(), 0);
+	});
+}
+
+#[test]
+fn move_and_withdraw_proposal_vote() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(2, P2, 500);
+		run_to_next_transit();
+		vote(4, P1);
+		assert_eq!(ProposalModule::proposals(1)[0].votes, 1);
+		assert_eq!(ProposalModule::get_vote_budget(&4).proposal_votes, 1);
+
+		assert_ok!(ProposalModule::move_vote(Origin::signed(4), P1.to_vec(), P2.to_vec()));
+		assert_eq!(ProposalModule::votes(4), vec![P2.to_vec()]);
+		assert_eq!(ProposalModule::proposals(1)[0].votes, 0);
+		assert_eq!(ProposalModule::proposals(2)[0].votes, 1);
+
+		assert_ok!(ProposalModule::unvote_proposal(Origin::signed(4), P2.to_vec()));
+		assert!(ProposalModule::votes(4).is_empty());
+		assert_eq!(ProposalModule::vote_count(), 0);
+		assert_eq!(ProposalModule::get_vote_budget(&4).proposal_votes, 2);
+	});
+}
+
+#[test]
+fn withdraw_concern_vote() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		concern(8, C1, P1);
+		run_to_next_transit();
+		vote_concern(4, C1, P1);
+		assert_eq!(ProposalModule::concerns(8)[0].votes, 1);
+		assert_ok!(ProposalModule::unvote_concern(Origin::signed(4), C1.to_vec(), P1.to_vec()));
+		assert_eq!(ProposalModule::concerns(8)[0].votes, 0);
+		assert_eq!(ProposalModule::vote_count_concern(), 0);
+	});
+}
+
+#[test]
+fn commit_reveal_proposal_vote() {
+	new_test_ext().execute_with(|| {
+		enable_commit_reveal();
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		let commitment = BlakeTwo256::hash_of(&(4u64, 0 as RoundIndex, &P1.to_vec(), &SALT));
+		assert_eq!(ProposalModule::proposal_commitment(&4, 0, &P1.to_vec(), &SALT), commitment);
+		assert_ok!(ProposalModule::commit_vote_proposal(Origin::signed(4), commitment));
+		assert!(ProposalModule::votes(4).is_empty());
+
+		run_to_next_transit();
+		assert_eq!(ProposalModule::get_phase(), (States::RevealPropose, 0, 25));
+		assert_ok!(ProposalModule::reveal_vote_proposal(Origin::signed(4), P1.to_vec(), SALT));
+		assert_eq!(ProposalModule::votes(4), vec![P1.to_vec()]);
+
+		run_to_next_transit();
+		assert_eq!(ProposalModule::state(), States::Concern);
+		assert_eq!(Balances::free_balance(4), 500);
+	});
+}
+
+#[test]
+fn commit_reveal_concern_vote() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		enable_commit_reveal();
+		concern(8, C1, P1);
+		run_to_next_transit();
+		let round = ProposalModule::round();
+		let commitment = ProposalModule::concern_commitment(&4, round, &C1.to_vec(), &P1.to_vec(), &SALT);
+		assert_ok!(ProposalModule::commit_vote_concern(Origin::signed(4), commitment));
+		let commitment = ProposalModule::concern_commitment(&5, round, &C1.to_vec(), &P1.to_vec(), &SALT);
+		assert_ok!(ProposalModule::commit_vote_concern(Origin::signed(5), commitment));
+
+		run_to_next_transit();
+		assert_eq!(ProposalModule::state(), States::RevealConcern);
+		assert_ok!(ProposalModule::reveal_vote_concern(Origin::signed(4), C1.to_vec(), P1.to_vec(), SALT));
+
+		// Unrevealed votes do not count and forfeit the reward
+		run_to_next_transit();
+		assert_eq!(ProposalModule::state(), States::VoteCouncil);
+		assert!(has_event(RawEvent::VoteCommitsForfeited(5, 1)));
+		assert_eq!(Balances::free_balance(8), 250);
+		assert_eq!(Balances::free_balance(4), 500 + 250);
+		assert_eq!(Balances::free_balance(5), 0);
+	});
+}
+
+#[test]
+fn delegated_vote_shares_reward() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_ok!(ProposalModule::delegate(Origin::signed(5), VoteTopic::Proposal, 4, false));
+		run_to_next_transit();
+		vote(4, P1);
+		run_to_next_transit();
+
+		// Two correct votes share the budget (500), the delegate keeps 20% of the delegated reward
+		assert!(has_event(RawEvent::DelegatedVoteReward(5, 4, P1.to_vec(), 200, 50)));
+		assert_eq!(Balances::free_balance(4), 250 + 50);
+		assert_eq!(Balances::free_balance(5), 200);
+	});
+}
+
+#[test]
+fn direct_vote_overrides_delegation() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(2, P2, 500);
+		assert_ok!(ProposalModule::delegate(Origin::signed(5), VoteTopic::Proposal, 4, false));
+		run_to_next_transit();
+		vote(4, P1);
+		vote(5, P2);
+		run_to_next_transit();
+		assert!(has_event(RawEvent::ProposalPassed(0, P2.to_vec(), Permill::from_percent(50))));
+		assert_eq!(Balances::free_balance(4), 250);
+		assert_eq!(Balances::free_balance(5), 250);
+	});
+}
+
+#[test]
+fn round_limited_delegation_expires() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ProposalModule::delegate(Origin::signed(5), VoteTopic::Proposal, 4, true));
+		assert_ok!(ProposalModule::delegate(Origin::signed(6), VoteTopic::Proposal, 4, false));
+		// Replacing a delegation does not count it twice
+		assert_ok!(ProposalModule::delegate(Origin::signed(6), VoteTopic::Proposal, 4, false));
+		assert_eq!(ProposalModule::delegation_count(), 2);
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		run_to_next_transit();
+		assert_eq!(ProposalModule::round(), 1);
+		assert_eq!(ProposalModule::delegation(VoteTopic::Proposal, 5), None);
+		assert!(ProposalModule::delegation(VoteTopic::Proposal, 6).is_some());
+		assert_eq!(ProposalModule::delegation_count(), 1);
+
+		assert_ok!(ProposalModule::undelegate(Origin::signed(6), VoteTopic::Proposal));
+		assert_eq!(ProposalModule::delegation(VoteTopic::Proposal, 6), None);
+		assert_eq!(ProposalModule::delegation_count(), 0);
+	});
+}
+
+#[test]
+fn set_config_applies_in_next_round() {
+	new_test_ext().execute_with(|| {
+		let mut config = ProposalModule::config();
+		config.propose_round_duration = 20;
+		assert_noop!(ProposalModule::set_config(Origin::signed(1), config.clone()), DispatchError::BadOrigin);
+		assert_ok!(ProposalModule::set_config(Origin::root(), config.clone()));
+		assert!(has_event(RawEvent::ConfigScheduled(1)));
+		assert_eq!(ProposalModule::pending_config(), Some(config.clone()));
+		assert_eq!(ProposalModule::config().propose_round_duration, 10);
+
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		run_to_next_transit();
+		assert!(has_event(RawEvent::ConfigApplied(1)));
+		assert_eq!(ProposalModule::config(), config);
+		assert_eq!(ProposalModule::pending_config(), None);
+		assert_eq!(ProposalModule::get_phase(), (States::Propose, 1, 40));
+	});
+}
+
+#[test]
+fn add_and_remove_category() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ProposalModule::add_category(Origin::signed(1), 1, b"art".to_vec()), DispatchError::BadOrigin);
+		assert_ok!(ProposalModule::add_category(Origin::root(), 1, b"art".to_vec()));
+		assert_eq!(ProposalModule::get_categories().len(), 2);
+		assert_ok!(ProposalModule::remove_category(Origin::root(), 1));
+		assert_eq!(ProposalModule::category(1), None);
+	});
+}
+
+#[test]
+fn state_transit_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ProposalModule::state_transit(Origin::signed(1)), DispatchError::BadOrigin);
+		assert_ok!(ProposalModule::state_transit(Origin::root()));
+		assert_eq!(ProposalModule::get_phase(), (States::Propose, 0, 11));
+	});
+}
+
+#[test]
+fn error_concern_already_submitted() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		concern(8, C1, P1);
+		concern(8, C2, P1);
+		assert_noop!(ProposalModule::concern(Origin::signed(9), C1.to_vec(), P1.to_vec()),
+			Error::<Test>::ConcernAlreadySubmitted);
+		assert_noop!(ProposalModule::amend_concern(Origin::signed(8), C1.to_vec(), P1.to_vec(), C2.to_vec()),
+			Error::<Test>::ConcernAlreadySubmitted);
+	});
+}
+
+#[test]
+fn error_concern_limit_reached() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		concern(8, C1, P1);
+		concern(9, C2, P1);
+		concern(10, C3, P1);
+		assert_noop!(ProposalModule::concern(Origin::signed(11), b"C4".to_vec(), P1.to_vec()),
+			Error::<Test>::ConcernLimitReached);
+	});
+}
+
+#[test]
+fn error_category_not_existant() {
+	new_test_ext().execute_with(|| {
+		let mut unknown = metadata(1000);
+		unknown.category = 9;
+		assert_noop!(ProposalModule::propose(Origin::signed(1), P1.to_vec(), unknown),
+			Error::<Test>::CategoryNotExistant);
+		assert_noop!(ProposalModule::remove_category(Origin::root(), 9), Error::<Test>::CategoryNotExistant);
+	});
+}
+
+#[test]
+fn error_concern_not_existant() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		assert_noop!(ProposalModule::withdraw_concern(Origin::signed(8), C1.to_vec(), P1.to_vec()),
+			Error::<Test>::ConcernNotExistant);
+		concern(8, C1, P1);
+		run_to_next_transit();
+		assert_noop!(ProposalModule::vote_concern(Origin::signed(4), C2.to_vec(), P1.to_vec()),
+			Error::<Test>::ConcernNotExistant);
+	});
+}
+
+#[test]
+fn error_already_voted() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		vote(4, P1);
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(4), P1.to_vec()), Error::<Test>::AlreadyVoted);
+		run_to_next_transit();
+		concern(8, C1, P1);
+		run_to_next_transit();
+		vote_concern(4, C1, P1);
+		assert_noop!(ProposalModule::vote_concern(Origin::signed(4), C1.to_vec(), P1.to_vec()),
+			Error::<Test>::AlreadyVoted);
+	});
+}
+
+#[test]
+fn error_commitment_not_existant() {
+	new_test_ext().execute_with(|| {
+		enable_commit_reveal();
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		let commitment = ProposalModule::proposal_commitment(&4, 0, &P1.to_vec(), &SALT);
+		assert_ok!(ProposalModule::commit_vote_proposal(Origin::signed(4), commitment));
+		run_to_next_transit();
+		assert_noop!(ProposalModule::reveal_vote_proposal(Origin::signed(4), P1.to_vec(), [2; 32]),
+			Error::<Test>::CommitmentNotExistant);
+		assert_noop!(ProposalModule::reveal_vote_proposal(Origin::signed(5), P1.to_vec(), SALT),
+			Error::<Test>::CommitmentNotExistant);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn error_replayed_commitment() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		enable_commit_reveal();
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		// A second identity copies the commitment of another voter
+		let commitment = ProposalModule::proposal_commitment(&4, 0, &P1.to_vec(), &SALT);
+		assert_ok!(ProposalModule::commit_vote_proposal(Origin::signed(4), commitment));
+		assert_ok!(ProposalModule::commit_vote_proposal(Origin::signed(5), commitment));
+		run_to_next_transit();
+		assert_ok!(ProposalModule::reveal_vote_proposal(Origin::signed(4), P1.to_vec(), SALT));
+		// and replays the revealed vote
+		assert_noop!(ProposalModule::reveal_vote_proposal(Origin::signed(5), P1.to_vec(), SALT),
+			Error::<Test>::CommitmentNotExistant);
+		assert!(ProposalModule::votes(5).is_empty());
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn error_delegation_chain_too_long() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
-		);
+		assert_ok!(ProposalModule::delegate(Origin::signed(2), VoteTopic::Concern, 3, false));
+		assert_ok!(ProposalModule::delegate(Origin::signed(3), VoteTopic::Concern, 4, false));
+		assert_noop!(ProposalModule::delegate(Origin::signed(1), VoteTopic::Concern, 2, false),
+			Error::<Test>::DelegationChainTooLong);
+	});
+}
+
+#[test]
+fn error_delegation_cycle() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ProposalModule::delegate(Origin::signed(1), VoteTopic::Proposal, 2, false));
+		assert_noop!(ProposalModule::delegate(Origin::signed(2), VoteTopic::Proposal, 1, false),
+			Error::<Test>::DelegationCycle);
+		// The topics are independent
+		assert_ok!(ProposalModule::delegate(Origin::signed(2), VoteTopic::Concern, 1, false));
+	});
+}
+
+#[test]
+fn error_delegation_not_existant() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ProposalModule::undelegate(Origin::signed(1), VoteTopic::Proposal),
+			Error::<Test>::DelegationNotExistant);
+	});
+}
+
+#[test]
+fn error_identity_level_too_low() {
+	new_test_ext().execute_with(|| {
+		set_identity_level(1, 1);
+		set_identity_level(4, 2);
+		assert_noop!(ProposalModule::propose(Origin::signed(1), P1.to_vec(), metadata(1000)),
+			Error::<Test>::IdentityLevelTooLow);
+		assert_noop!(ProposalModule::delegate(Origin::signed(4), VoteTopic::Proposal, 5, false),
+			Error::<Test>::IdentityLevelTooLow);
+		propose(2, P1, 1000);
+		run_to_next_transit();
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(4), P1.to_vec()),
+			Error::<Test>::IdentityLevelTooLow);
+	});
+}
+
+#[test]
+fn error_invalid_cap() {
+	new_test_ext().execute_with(|| {
+		let mut config = ProposalModule::config();
+		config.propose_identified_user_cap = 0;
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidCap);
+
+		let mut config = ProposalModule::config();
+		config.concern_identified_user_cap = 4;
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidCap);
+	});
+}
+
+#[test]
+fn error_invalid_threshold() {
+	new_test_ext().execute_with(|| {
+		let mut config = ProposalModule::config();
+		config.council_accept_concern_min_votes = Permill::zero();
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidThreshold);
+
+		let mut config = ProposalModule::config();
+		config.propose_vote_acceptance_min = Permill::zero();
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidThreshold);
+
+		let mut config = ProposalModule::config();
+		config.concern_vote_acceptance_min = Permill::zero();
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidThreshold);
+	});
+}
+
+#[test]
+fn error_invalid_reward_share() {
+	new_test_ext().execute_with(|| {
+		let mut config = ProposalModule::config();
+		config.propose_reward = Permill::zero();
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidRewardShare);
+
+		let mut config = ProposalModule::config();
+		config.delegate_reward_share = Permill::zero();
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidRewardShare);
+
+		// The delegator would not receive anything
+		let mut config = ProposalModule::config();
+		config.delegate_reward_share = Permill::one();
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::InvalidRewardShare);
+	});
+}
+
+#[test]
+fn error_not_submitter() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_noop!(ProposalModule::withdraw_proposal(Origin::signed(2), P1.to_vec()), Error::<Test>::NotSubmitter);
+		assert_noop!(ProposalModule::amend_proposal(Origin::signed(2), P1.to_vec(), P2.to_vec(), metadata(10)),
+			Error::<Test>::NotSubmitter);
+	});
+}
+
+#[test]
+fn error_proposal_already_submitted() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_noop!(ProposalModule::propose(Origin::signed(2), P1.to_vec(), metadata(1000)),
+			Error::<Test>::ProposalAlreadySubmitted);
+	});
+}
+
+#[test]
+fn error_proposal_not_existant() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ProposalModule::withdraw_proposal(Origin::signed(1), P1.to_vec()),
+			Error::<Test>::ProposalNotExistant);
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(4), P2.to_vec()),
+			Error::<Test>::ProposalNotExistant);
+	});
+}
+
+#[test]
+fn error_proposal_limit_reached() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(2, P2, 1000);
+		propose(3, P3, 1000);
+		assert_noop!(ProposalModule::propose(Origin::signed(4), b"P4".to_vec(), metadata(1000)),
+			Error::<Test>::ProposalLimitReached);
+	});
+}
+
+#[test]
+fn error_round_not_current() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_eq!(ProposalModule::get_proposals(1, 0, 10), Err(Error::<Test>::RoundNotCurrent.into()));
+		assert_eq!(ProposalModule::get_concerns(1, 0, 10), Err(Error::<Test>::RoundNotCurrent.into()));
+		assert_eq!(ProposalModule::get_proposals(0, 0, 10).map(|p| p.len()), Ok(1));
+		assert_eq!(ProposalModule::get_concerns(0, 0, 10), Ok(Vec::new()));
+	});
+}
+
+#[test]
+fn error_budget_exceeds_round_budget() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ProposalModule::propose(Origin::signed(1), P1.to_vec(), metadata(2001)),
+			Error::<Test>::BudgetExceedsRoundBudget);
+	});
+}
+
+#[test]
+fn error_title_too_long() {
+	new_test_ext().execute_with(|| {
+		let mut long = metadata(1000);
+		long.title = vec![b'a'; 17];
+		assert_noop!(ProposalModule::propose(Origin::signed(1), P1.to_vec(), long), Error::<Test>::TitleTooLong);
+	});
+}
+
+#[test]
+fn error_user_concern_limit_reached() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		concern(8, C1, P1);
+		concern(8, C2, P1);
+		assert_noop!(ProposalModule::concern(Origin::signed(8), C3.to_vec(), P1.to_vec()),
+			Error::<Test>::UserConcernLimitReached);
+	});
+}
+
+#[test]
+fn error_user_concern_vote_limit_reached() {
+	new_test_ext().execute_with(|| {
+		to_concern_round();
+		concern(8, C1, P1);
+		concern(8, C2, P1);
+		concern(9, C3, P1);
+		run_to_next_transit();
+		vote_concern(4, C1, P1);
+		vote_concern(4, C2, P1);
+		assert_noop!(ProposalModule::vote_concern(Origin::signed(4), C3.to_vec(), P1.to_vec()),
+			Error::<Test>::UserConcernVoteLimitReached);
+	});
+}
+
+#[test]
+fn error_user_proposal_limit_reached() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(1, P2, 1000);
+		assert_noop!(ProposalModule::propose(Origin::signed(1), P3.to_vec(), metadata(1000)),
+			Error::<Test>::UserProposalLimitReached);
+	});
+}
+
+#[test]
+fn error_user_proposal_vote_limit_reached() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(2, P2, 1000);
+		propose(3, P3, 1000);
+		run_to_next_transit();
+		vote(4, P1);
+		vote(4, P2);
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(4), P3.to_vec()),
+			Error::<Test>::UserProposalVoteLimitReached);
+		assert_noop!(ProposalModule::move_vote(Origin::signed(4), P1.to_vec(), P2.to_vec()),
+			Error::<Test>::AlreadyVoted);
+	});
+}
+
+#[test]
+fn error_vote_not_existant() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		propose(2, P2, 1000);
+		run_to_next_transit();
+		assert_noop!(ProposalModule::unvote_proposal(Origin::signed(4), P1.to_vec()), Error::<Test>::VoteNotExistant);
+		assert_noop!(ProposalModule::move_vote(Origin::signed(4), P1.to_vec(), P2.to_vec()),
+			Error::<Test>::VoteNotExistant);
+	});
+}
+
+#[test]
+fn error_wrong_state() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(4), P1.to_vec()), Error::<Test>::WrongState);
+		assert_noop!(ProposalModule::concern(Origin::signed(8), C1.to_vec(), P1.to_vec()), Error::<Test>::WrongState);
+		run_to_next_transit();
+		assert_noop!(ProposalModule::propose(Origin::signed(2), P2.to_vec(), metadata(1000)),
+			Error::<Test>::WrongState);
+		assert_noop!(ProposalModule::reveal_vote_proposal(Origin::signed(4), P1.to_vec(), SALT),
+			Error::<Test>::WrongState);
+	});
+}
+
+#[test]
+fn error_wrong_voting_mode() {
+	new_test_ext().execute_with(|| {
+		propose(1, P1, 1000);
+		run_to_next_transit();
+		let commitment = ProposalModule::proposal_commitment(&4, 0, &P1.to_vec(), &SALT);
+		assert_noop!(ProposalModule::commit_vote_proposal(Origin::signed(4), commitment),
+			Error::<Test>::WrongVotingMode);
+		enable_commit_reveal();
+		assert_noop!(ProposalModule::vote_proposal(Origin::signed(4), P1.to_vec()), Error::<Test>::WrongVotingMode);
+	});
+}
+
+#[test]
+fn error_zero_duration() {
+	new_test_ext().execute_with(|| {
+		let mut config = ProposalModule::config();
+		config.concern_vote_duration = 0;
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::ZeroDuration);
+
+		let mut config = ProposalModule::config();
+		config.commit_reveal_voting = true;
+		config.vote_reveal_duration = 0;
+		assert_noop!(ProposalModule::set_config(Origin::root(), config), Error::<Test>::ZeroDuration);
+	});
+}
+
+#[test]
+fn migrate_storage_without_version() {
+	new_test_ext().execute_with(|| {
+		// Layout without a version. Round: u8, ProposalWinners: u8 -> [(concerns, proposer, proposal,
+		// vote ratio)], Proposals: [(proposal, votes)], Concerns: [(proposal, concern, votes)] and
+		// ConcernVotes: [concern], Delegations: (delegate, Option<u8>)
+		StorageVersion::put(Releases::V1_0_0);
+		unhashed::put_raw(&Round::hashed_key(), &1u8.encode());
+		let winners_key = |round: u8| [&ProposalWinners::<Test>::final_prefix()[..], &round.encode()[..]].concat();
+		let winner = |proposal: &[u8]| (vec![C1.to_vec()], 1u64, proposal.to_vec(), Permill::from_percent(60));
+		unhashed::put_raw(&winners_key(0), &vec![winner(P1)].encode());
+		unhashed::put_raw(&winners_key(1), &vec![winner(P2)].encode());
+		unhashed::put_raw(&Proposals::<Test>::hashed_key_for(1), &vec![(P3.to_vec(), 2u32)].encode());
+		unhashed::put_raw(&Concerns::<Test>::hashed_key_for(2), &vec![(P3.to_vec(), C2.to_vec(), 1u32)].encode());
+		unhashed::put_raw(&ConcernVotes::<Test>::hashed_key_for(3), &vec![C2.to_vec(), C3.to_vec()].encode());
+		unhashed::put_raw(&Delegations::<Test>::hashed_key_for(VoteTopic::Proposal, 5), &(4u64, Some(1u8)).encode());
+
+		migrations::migrate::<Test>();
+		assert_eq!(ProposalModule::storage_version(), Releases::V4_0_0);
+		assert_eq!(ProposalModule::round(), 1);
+		assert_eq!(ProposalModule::delegation(VoteTopic::Proposal, 5).map(|d| (d.delegate, d.round)),
+			Some((4, Some(1))));
+		assert_eq!(ProposalModule::delegation_count(), 1);
+
+		// Winners of finished rounds are summarized, the old keys are removed
+		let summary = ProposalModule::round_summary(0).unwrap();
+		assert_eq!(summary.winners.iter().map(|w| (w.proposer, w.proposal.clone())).collect::<Vec<_>>(),
+			vec![(1, P1.to_vec())]);
+		assert_eq!(summary.concern_winners, vec![(C1.to_vec(), P1.to_vec())]);
+		assert_eq!(unhashed::get_raw(&winners_key(0)), None);
+		assert_eq!(unhashed::get_raw(&winners_key(1)), None);
+		let current = ProposalModule::proposal_winners(1);
+		assert_eq!((current.len(), current[0].proposal.clone(), current[0].vote_ratio),
+			(1, P2.to_vec(), Permill::from_percent(60)));
+
+		let proposals = ProposalModule::proposals(1);
+		assert_eq!((proposals[0].proposal.clone(), proposals[0].votes, proposals[0].amends.clone()),
+			(P3.to_vec(), 2, None));
+		let concerns = ProposalModule::concerns(2);
+		assert_eq!((concerns[0].concern.clone(), concerns[0].associated_proposal.clone(), concerns[0].votes),
+			(C2.to_vec(), P3.to_vec(), 1));
+		// Votes for unknown concerns are dropped
+		assert_eq!(ProposalModule::votes_concern(3), vec![(C2.to_vec(), P3.to_vec())]);
 	});
 }
-*/
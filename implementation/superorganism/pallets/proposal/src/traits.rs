@@ -0,0 +1,60 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Strategy that decides how proposal votes are paid for and counted.
+/// Every identified user receives budget(ProposeVoteMaxPerIdentifiedUser) voice credits
+/// per round. Votes on the same proposal require AllowVoteStacking.
+pub trait VoteTally {
+	/// Voice credits an identity can spend per round
+	fn budget(max_votes: u16) -> u32;
+	/// Voice credits required to cast votes votes on the same proposal
+	fn cost(votes: u32) -> u32;
+	/// Effective votes a proposal receives from votes votes of one identity
+	fn effective_votes(votes: u32) -> u32;
+}
+
+/// Every vote costs one credit and counts once (ProposeVoteMaxPerIdentifiedUser votes per round)
+pub struct OneVotePerSlot;
+
+impl VoteTally for OneVotePerSlot {
+	fn budget(max_votes: u16) -> u32 {
+		max_votes.into()
+	}
+
+	fn cost(votes: u32) -> u32 {
+		votes
+	}
+
+	fn effective_votes(votes: u32) -> u32 {
+		votes
+	}
+}
+
+/// Casting n votes on the same proposal costs n² credits.
+/// The budget is ProposeVoteMaxPerIdentifiedUser² credits, enough to put every vote on one proposal.
+pub struct QuadraticVoting;
+
+impl VoteTally for QuadraticVoting {
+	fn budget(max_votes: u16) -> u32 {
+		u32::from(max_votes).saturating_mul(max_votes.into())
+	}
+
+	fn cost(votes: u32) -> u32 {
+		votes.saturating_mul(votes)
+	}
+
+	fn effective_votes(votes: u32) -> u32 {
+		votes
+	}
+}
@@ -0,0 +1,400 @@
+// Copyright 2020 Harald Heckmann
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_proposal
+//!
+//! Estimated from the storage accesses of the extrinsics, the values were not measured yet.
+//! SubstrateWeight prices the storage accesses with the database weights of the runtime.
+//! Replace them with the results of the benchmarks in benchmarking.rs on reference hardware:
+//! ./target/release/node-superorganism benchmark --chain dev --execution wasm --wasm-execution compiled
+//!   --pallet pallet_proposal --extrinsic '*' --steps 50 --repeat 20
+//! The commit and reveal benchmarks only measure the extrinsics if the runtime enables commit-reveal voting.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{sp_std::marker::PhantomData, traits::Get,
+	weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+/// Weight functions needed for pallet_proposal
+pub trait WeightInfo {
+	fn state_transit_propose() -> Weight;
+	fn state_transit_vote_propose(p: u32, v: u32, d: u32, ) -> Weight;
+	fn state_transit_concern(w: u32, ) -> Weight;
+	fn state_transit_vote_concern(c: u32, v: u32, w: u32, d: u32, ) -> Weight;
+	fn state_transit_vote_council(w: u32, d: u32, f: u32, ) -> Weight;
+	fn concern() -> Weight;
+	fn add_category() -> Weight;
+	fn remove_category() -> Weight;
+	fn set_config() -> Weight;
+	fn propose() -> Weight;
+	fn withdraw_concern() -> Weight;
+	fn amend_concern() -> Weight;
+	fn withdraw_proposal() -> Weight;
+	fn amend_proposal() -> Weight;
+	fn vote_concern(v: u32, ) -> Weight;
+	fn unvote_concern(v: u32, ) -> Weight;
+	fn commit_vote_concern(v: u32, ) -> Weight;
+	fn reveal_vote_concern(v: u32, ) -> Weight;
+	fn vote_proposal(v: u32, ) -> Weight;
+	fn unvote_proposal(v: u32, ) -> Weight;
+	fn commit_vote_proposal(v: u32, ) -> Weight;
+	fn reveal_vote_proposal(v: u32, ) -> Weight;
+	fn delegate(d: u32, ) -> Weight;
+	fn undelegate() -> Weight;
+	fn move_vote(v: u32, ) -> Weight;
+}
+
+/// Weights for pallet_proposal using the database weights of the runtime
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn state_transit_propose() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn state_transit_vote_propose(p: u32, v: u32, d: u32, ) -> Weight {
+		(131_000_000 as Weight)
+			.saturating_add((61_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add((38_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((29_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(d as Weight)))
+	}
+	fn state_transit_concern(w: u32, ) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add((44_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn state_transit_vote_concern(c: u32, v: u32, w: u32, d: u32, ) -> Weight {
+		(137_000_000 as Weight)
+			.saturating_add((57_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((36_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((45_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((27_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn state_transit_vote_council(w: u32, d: u32, f: u32, ) -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add((152_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add((41_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(f as Weight)))
+	}
+	fn concern() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn add_category() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_category() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_config() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn propose() -> Weight {
+		(79_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_concern() -> Weight {
+		(54_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn amend_concern() -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_proposal() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn amend_proposal() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_concern(v: u32, ) -> Weight {
+		(64_000_000 as Weight)
+			.saturating_add((410_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn unvote_concern(v: u32, ) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((430_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn commit_vote_concern(v: u32, ) -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_vote_concern(v: u32, ) -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add((540_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn vote_proposal(v: u32, ) -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add((380_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn unvote_proposal(v: u32, ) -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add((400_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn commit_vote_proposal(v: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_vote_proposal(v: u32, ) -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add((520_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn delegate(d: u32, ) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((6_100_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn move_vote(v: u32, ) -> Weight {
+		(94_000_000 as Weight)
+			.saturating_add((760_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+/// Weights for tests and runtimes without own database weights
+impl WeightInfo for () {
+	fn state_transit_propose() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn state_transit_vote_propose(p: u32, v: u32, d: u32, ) -> Weight {
+		(131_000_000 as Weight)
+			.saturating_add((61_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add((38_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((29_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(12 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(d as Weight)))
+	}
+	fn state_transit_concern(w: u32, ) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add((44_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn state_transit_vote_concern(c: u32, v: u32, w: u32, d: u32, ) -> Weight {
+		(137_000_000 as Weight)
+			.saturating_add((57_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((36_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((45_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((27_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(11 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(10 as Weight))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn state_transit_vote_council(w: u32, d: u32, f: u32, ) -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add((152_000_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((9_000_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add((41_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().reads((6 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().writes((7 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(f as Weight)))
+	}
+	fn concern() -> Weight {
+		(68_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn add_category() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_category() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_config() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn propose() -> Weight {
+		(79_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_concern() -> Weight {
+		(54_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn amend_concern() -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_proposal() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn amend_proposal() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_concern(v: u32, ) -> Weight {
+		(64_000_000 as Weight)
+			.saturating_add((410_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn unvote_concern(v: u32, ) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((430_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn commit_vote_concern(v: u32, ) -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_vote_concern(v: u32, ) -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add((540_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn vote_proposal(v: u32, ) -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add((380_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn unvote_proposal(v: u32, ) -> Weight {
+		(57_000_000 as Weight)
+			.saturating_add((400_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn commit_vote_proposal(v: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_vote_proposal(v: u32, ) -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add((520_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn delegate(d: u32, ) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((6_100_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn move_vote(v: u32, ) -> Weight {
+		(94_000_000 as Weight)
+			.saturating_add((760_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+}
@@ -24,24 +24,70 @@ use frame_support::serde::{Deserialize, Serialize};
 // Important: Change Vec<u8> to a fixed length type (otherwise attackable)
 pub type ProposalCID = Vec<u8>;
 pub type ConcernCID = ProposalCID;
+/// Roles are identified by the CID of the job description
+pub type RoleCID = ProposalCID;
+/// Categories are managed by governance (see pallet_proposal)
+pub type CategoryId = u32;
+/// Index of a proposal round
+pub type RoundIndex = u32;
+
+/// Contains the structured part of a proposal that on-chain logic can evaluate
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalMetadata<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Bounded by the proposal pallet
+	pub title: Vec<u8>,
+	pub category: CategoryId,
+	/// Requested budget for the project
+	pub budget: Balance,
+	/// Estimated duration of the project
+	pub duration: BlockNumber,
+	/// Roles the project requires, opened as positions when the project is spawned
+	pub roles: Vec<RoleCID>,
+}
+
+impl<Balance, BlockNumber> Default for ProposalMetadata<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Default + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Default + Eq + PartialEq,
+{
+	fn default() -> Self {
+		ProposalMetadata{title: Vec::new(), category: Default::default(), budget: Default::default(),
+			duration: Default::default(), roles: Vec::new()}
+	}
+}
 
 /// Contains proposal and vote count
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct Proposal {
+pub struct Proposal<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
 	pub proposal: ProposalCID,
 	pub votes: u32,
+	/// Previous version of the proposal, if it was amended
+	pub amends: Option<ProposalCID>,
+	pub metadata: ProposalMetadata<Balance, BlockNumber>,
 }
 
-impl Proposal {
-	pub fn new(proposal: ProposalCID) -> Self {
-		Proposal{proposal, votes: 0}
+impl<Balance, BlockNumber> Proposal<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub fn new(proposal: ProposalCID, metadata: ProposalMetadata<Balance, BlockNumber>) -> Self {
+		Proposal{proposal, votes: 0, amends: None, metadata}
 	}
 }
 
-impl Default for Proposal {
+impl<Balance, BlockNumber> Default for Proposal<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Default + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Default + Eq + PartialEq,
+{
 	fn default() -> Self {
-		Proposal::new(ProposalCID::default())
+		Proposal::new(ProposalCID::default(), Default::default())
 	}
 }
 
@@ -52,11 +98,13 @@ pub struct Concern {
 	pub associated_proposal: ProposalCID,
 	pub concern: ConcernCID,
 	pub votes: u32,
+	/// Previous version of the concern, if it was amended
+	pub amends: Option<ConcernCID>,
 }
 
 impl Concern {
 	pub fn new(concern: ConcernCID, associated_proposal: ProposalCID) -> Self {
-		Concern{concern, associated_proposal, votes: 0}
+		Concern{concern, associated_proposal, votes: 0, amends: None}
 	}
 }
 
@@ -70,33 +118,76 @@ impl Default for Concern {
 /// Contains one winning proposal
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ProposalWinner<IdentityId> where
-	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+pub struct ProposalWinner<IdentityId, Balance, BlockNumber> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
 {
 	pub concerns: Vec<ConcernCID>,
 	pub proposer: IdentityId, // For later rewards
 	pub proposal: ProposalCID,
-	pub vote_ratio: Permill
+	pub vote_ratio: Permill,
+	pub metadata: ProposalMetadata<Balance, BlockNumber>,
 }
 
-impl<IdentityId> ProposalWinner<IdentityId> where
-	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+impl<IdentityId, Balance, BlockNumber> ProposalWinner<IdentityId, Balance, BlockNumber> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
 {
-	pub fn new(concerns: Vec<ConcernCID>, proposer: IdentityId,
-				proposal: ProposalCID, vote_ratio: Permill) -> Self {
-		ProposalWinner{concerns, proposer, proposal, vote_ratio}
+	pub fn new(concerns: Vec<ConcernCID>, proposer: IdentityId, proposal: ProposalCID,
+				vote_ratio: Permill, metadata: ProposalMetadata<Balance, BlockNumber>) -> Self {
+		ProposalWinner{concerns, proposer, proposal, vote_ratio, metadata}
 	}
 }
 
-impl<IdentityId> Default for ProposalWinner<IdentityId> where
-	IdentityId: Codec + Clone + Eq + EncodeLike + Debug + Default
+impl<IdentityId, Balance, BlockNumber> Default for ProposalWinner<IdentityId, Balance, BlockNumber> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug + Default,
+	Balance: Codec + Clone + Debug + Default + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Default + Eq + PartialEq,
+{
+	fn default() -> Self {
+		ProposalWinner::new(Vec::new(), Default::default(), Default::default(), Default::default(),
+			Default::default())
+	}
+}
+
+/// Contains the outcome of one round. Filled while the round progresses and never
+/// changed after the round ended.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoundSummary<IdentityId, Balance, BlockNumber, ProjectId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	ProjectId: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Proposals that passed the proposal vote, including their passed concerns
+	pub winners: Vec<ProposalWinner<IdentityId, Balance, BlockNumber>>,
+	/// Concerns that passed the concern vote and the proposal they are associated with
+	pub concern_winners: Vec<(ConcernCID, ProposalCID)>,
+	/// Decision of the council for every winner (true if accepted)
+	pub council_result: Vec<(ProposalCID, bool)>,
+	/// Projects spawned from accepted proposals
+	pub projects: Vec<ProjectId>,
+	/// Rewards paid for votes, concerns and proposals in this round
+	pub total_reward: Balance,
+}
+
+impl<IdentityId, Balance, BlockNumber, ProjectId> Default for RoundSummary<IdentityId, Balance, BlockNumber, ProjectId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug,
+	Balance: Codec + Clone + Debug + Default + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	ProjectId: Codec + Clone + Debug + Eq + PartialEq,
 {
 	fn default() -> Self {
-		ProposalWinner::new(Vec::new(), Default::default(), Default::default(), Default::default())
+		RoundSummary{winners: Vec::new(), concern_winners: Vec::new(), council_result: Vec::new(),
+			projects: Vec::new(), total_reward: Default::default()}
 	}
 }
 
-/// Contains the five different states the pallet can be in
+/// Contains the different states the pallet can be in
+// The reveal states are appended to keep the encoding of the existing states
 #[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum States {
@@ -106,6 +197,10 @@ pub enum States {
 	Concern,
 	VoteConcern,
 	VoteCouncil,
+	/// Reveal committed proposal votes (commit-reveal voting only)
+	RevealPropose,
+	/// Reveal committed concern votes (commit-reveal voting only)
+	RevealConcern,
 }
 
 impl Default for States {
@@ -113,3 +208,123 @@ impl Default for States {
         States::Uninitialized
     }
 }
+
+/// Contains the kinds of votes an identity can delegate
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VoteTopic {
+	Proposal,
+	Concern,
+}
+
+/// Contains the delegate of an identity for one vote topic
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Delegation<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	pub delegate: IdentityId,
+	/// Round the delegation is limited to. None if it is valid for every round
+	pub round: Option<RoundIndex>,
+}
+
+impl<IdentityId> Delegation<IdentityId> where
+	IdentityId: Codec + Clone + Eq + EncodeLike + Debug
+{
+	pub fn new(delegate: IdentityId, round: Option<RoundIndex>) -> Self {
+		Delegation{delegate, round}
+	}
+
+	/// Is the delegation valid in the given round?
+	pub fn is_active(&self, round: RoundIndex) -> bool {
+		self.round.map_or(true, |r| r == round)
+	}
+}
+
+/// Contains the current phase of the proposal cycle
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Phase<BlockNumber, Moment> where
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+	Moment: Codec + Clone + Debug + Eq + PartialEq,
+{
+	pub state: States,
+	pub round: RoundIndex,
+	/// Block at which the next state transit is scheduled
+	pub next_transit: BlockNumber,
+	/// Estimated unix timestamp (milliseconds) of the next state transit
+	pub next_transit_estimate: Moment,
+}
+
+/// Contains the number of votes an identity can still submit in the current round
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VoteBudget {
+	/// Remaining voice credits for proposal votes (equals the remaining votes
+	/// if every vote costs one credit)
+	pub proposal_votes: u16,
+	pub concern_votes: u16,
+}
+
+/// Contains the parameters of the proposal cycle. Changed by governance, a new configuration
+/// is applied when the next round starts.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CycleConfig<Balance, BlockNumber> where
+	Balance: Codec + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Codec + Clone + Debug + Eq + PartialEq,
+{
+	/// Can an identified user spend several votes on the same proposal or concern?
+	pub allow_vote_stacking: bool,
+	/// Must votes be committed as salted hashes and revealed in a separate reveal state?
+	pub commit_reveal_voting: bool,
+	/// How long can committed votes be revealed?
+	pub vote_reveal_duration: BlockNumber,
+	/// How many delegations can be chained?
+	pub max_delegation_depth: u32,
+	/// Which share of the reward for a correct delegated vote does the delegate receive?
+	pub delegate_reward_share: Permill,
+	/// How many proposals can be submitted per proposal round?
+	pub propose_cap: u32,
+	/// How many proposals can an identified user submit per proposal round?
+	pub propose_identified_user_cap: u8,
+	/// Which identity level is required to create a proposal?
+	pub propose_identity_level: u8,
+	/// How long can the title of a proposal be? Value in bytes.
+	pub propose_max_title_length: u32,
+	/// How high is the reward for the proposer if the proposal is converted into a project?
+	pub propose_reward: Permill,
+	/// How long can proposals be submitted?
+	pub propose_round_duration: BlockNumber,
+	/// How much budget can the winning proposals of one round request in total?
+	pub propose_round_budget: Balance,
+	/// How many votes does a proposal require to be accepted for the next round?
+	pub propose_vote_acceptance_min: Permill,
+	/// How long can votes for proposals be submitted?
+	pub propose_vote_duration: BlockNumber,
+	/// Which identity level is required to vote for proposals?
+	pub propose_vote_identity_level: u8,
+	/// How many votes for proposals can each identified user submit?
+	pub propose_vote_max_per_identified_user: u16,
+	/// How many concerns can be submitted per concern round?
+	pub concern_cap: u32,
+	/// How many concerns can an identified user submit per concern round?
+	pub concern_identified_user_cap: u8,
+	/// Which identity level is required to submit a concern?
+	pub concern_identity_level: u8,
+	/// How long can concerns be submitted?
+	pub concern_round_duration: BlockNumber,
+	/// How many votes does a concern require to be accepted for the next round?
+	pub concern_vote_acceptance_min: Permill,
+	/// How long can votes for concerns be submitted?
+	pub concern_vote_duration: BlockNumber,
+	/// Which identity level is required to vote for concerns?
+	pub concern_vote_identity_level: u8,
+	/// How many votes for concerns can each identified user submit?
+	pub concern_vote_max_per_identified_user: u16,
+	/// How much time is reserved for the council to vote?
+	pub council_vote_round_duration: BlockNumber,
+	/// How many council members must agree that a concern is too serious to launch a project
+	/// from the associated proposal?
+	pub council_accept_concern_min_votes: Permill,
+}
@@ -0,0 +1,71 @@
+//! Runtime APIs of the custom pallets. The node exposes them as `superorganism_*` RPCs.
+
+use sp_std::prelude::*;
+use sp_runtime::DispatchError;
+use pallet_community_identity::{IdentityLevel, ReviewRequest};
+use pallet_council::PollTally;
+use pallet_project::types::{Project, ProjectID, ProjectState, Worker};
+use pallet_proposal_types::{CategoryId, Concern, Phase, Proposal, ProposalWinner, RoundIndex, RoundSummary, VoteBudget};
+use crate::{AccountId, Balance, BlockNumber, Moment};
+
+sp_api::decl_runtime_apis! {
+	/// Queries for the proposal cycle (proposal pallet)
+	pub trait ProposalApi {
+		/// Get the current state, round and (estimated) time of the next state transit
+		fn phase() -> Phase<BlockNumber, Moment>;
+		/// Get up to limit proposals of the current round (including votes), skipping the first start
+		/// proposals. Proposals of earlier rounds are not stored, querying them fails.
+		fn proposals(round: RoundIndex, start: u32, limit: u32)
+			-> Result<Vec<(AccountId, Proposal<Balance, BlockNumber>)>, DispatchError>;
+		/// Get up to limit proposals of the current round in a category, skipping the first start proposals
+		fn proposals_by_category(category: CategoryId, start: u32, limit: u32)
+			-> Vec<(AccountId, Proposal<Balance, BlockNumber>)>;
+		/// Get up to limit concerns of the current round (including votes), skipping the first start
+		/// concerns. Concerns of earlier rounds are not stored, querying them fails.
+		fn concerns(round: RoundIndex, start: u32, limit: u32) -> Result<Vec<(AccountId, Concern)>, DispatchError>;
+		/// Get up to limit winners of a round, skipping the first start winners
+		fn winners(round: RoundIndex, start: u32, limit: u32) -> Vec<ProposalWinner<AccountId, Balance, BlockNumber>>;
+		/// Get up to limit winners of a round in a category, skipping the first start winners
+		fn winners_by_category(round: RoundIndex, category: CategoryId, start: u32, limit: u32)
+			-> Vec<ProposalWinner<AccountId, Balance, BlockNumber>>;
+		/// Get the summary of a round. The summary of the current round is incomplete.
+		fn round_summary(round: RoundIndex) -> Option<RoundSummary<AccountId, Balance, BlockNumber, ProjectID>>;
+		/// Get all proposal categories (id, name)
+		fn categories() -> Vec<(CategoryId, Vec<u8>)>;
+		/// Get the number of votes an identity can still submit in the current round
+		fn vote_budget(identity: AccountId) -> VoteBudget;
+	}
+
+	/// Queries for projects (project pallet)
+	pub trait ProjectApi {
+		/// Get a live or archived project
+		fn project(project: ProjectID) -> Option<Project<Balance, BlockNumber, AccountId>>;
+		/// Get the projects in a state with an id within [start, start + limit) and the id to continue
+		/// with (None after the last project). Without a state, get the live projects.
+		fn projects(state: Option<ProjectState>, start: ProjectID, limit: u32)
+			-> (Vec<Project<Balance, BlockNumber, AccountId>>, Option<ProjectID>);
+		/// Get up to limit live projects an identity works on (as worker or project leader), skipping the
+		/// first start projects
+		fn worker_projects(identity: AccountId, start: u32, limit: u32) -> Vec<Project<Balance, BlockNumber, AccountId>>;
+		/// Get up to limit workers of a project, skipping the first start workers
+		fn workers(project: ProjectID, start: u32, limit: u32) -> Vec<Worker<Balance, BlockNumber, AccountId>>;
+	}
+
+	/// Queries for the council (council pallet)
+	pub trait CouncilApi {
+		/// Get the current council members
+		fn members() -> Vec<AccountId>;
+		/// Get up to limit polls (including the tally) that still accept votes, skipping the first start polls
+		fn open_polls(start: u32, limit: u32) -> Vec<PollTally<AccountId, BlockNumber>>;
+	}
+
+	/// Queries for identities (community_identity pallet)
+	pub trait IdentityApi {
+		/// Get the identity level of an identity
+		fn identity_level(identity: AccountId) -> IdentityLevel;
+		/// Get the tickets of the peer reviews an identity requested that are still pending
+		fn pending_reviews(identity: AccountId) -> Vec<(AccountId, ReviewRequest<Moment>)>;
+		/// Get the appointments of an identity (when the identity has to participate in a review)
+		fn appointments(identity: AccountId) -> Vec<(Moment, Vec<AccountId>)>;
+	}
+}
@@ -9,11 +9,12 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	ApplyExtrinsicResult, DispatchError, generic, create_runtime_str, impl_opaque_keys, ModuleId, MultiSignature,
+	SaturatedConversion, transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	BlakeTwo256, Block as BlockT, Extrinsic as ExtrinsicT, IdentityLookup, Verify, IdentifyAccount, NumberFor,
+	Saturating,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -23,7 +24,8 @@ use sp_version::RuntimeVersion;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 
-use frame_system::EnsureRoot;
+use codec::Encode;
+use frame_system::{EnsureOneOf, EnsureRoot};
 
 // A few exports that help ease life for downstream crates.
 #[cfg(any(feature = "std", test))]
@@ -33,7 +35,7 @@ pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	traits::{Get, KeyOwnerProofSystem, Randomness},
 	weights::{
 		Weight, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -42,9 +44,17 @@ pub use frame_support::{
 
 /// Import custom pallets
 pub use pallet_community_identity;
+pub use pallet_community_treasury;
 pub use pallet_council;
+pub use pallet_document_availability;
 pub use pallet_project;
 pub use pallet_proposal;
+pub use pallet_proposal_types;
+use pallet_community_identity::traits::PeerReviewedPhysicalIdentity;
+use pallet_project::{traits::ProjectTrait, types::{Project as ProjectType, ProjectID, ProjectState, Worker}};
+
+/// Runtime APIs of the custom pallets
+pub mod api;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -100,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-superorganism"),
 	impl_name: create_runtime_str!("node-superorganism"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 2,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -115,6 +125,9 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+/// Sudo (during the bootstrapping of the network) or the council after it accepted a motion
+type EnsureRootOrCouncil = EnsureOneOf<AccountId, EnsureRoot<AccountId>, pallet_council::EnsureCouncil>;
+
 // Timestamp format
 pub type Moment = u64;
 
@@ -258,7 +271,7 @@ parameter_types! {
 
 impl pallet_transaction_payment::Trait for Runtime {
 	type Currency = Balances;
-	type OnTransactionPayment = ();
+	type OnTransactionPayment = CommunityTreasury;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
@@ -288,12 +301,22 @@ impl pallet_scheduler::Trait for Runtime {
 
 parameter_types! {
 	const TwoYears: u32 = 63_115_200;
-	// TODO: Change from test value to OneWeek
-	const OneWeek: BlockNumber = 10; // DAYS * 7 as BlockNumber;
+	const OneWeek: BlockNumber = DAYS * 7;
 
 	/// How long is an identified user locked out from submitting proposals / concerns
 	/// for bad behaviour. Value in seconds.
 	pub const IdentifiedUserPenality: u32 = TwoYears::get();
+	/// Can an identified user spend several votes on the same proposal or concern?
+	pub const AllowVoteStacking: bool = false;
+	/// Must votes be committed as salted hashes and revealed in a separate reveal state?
+	pub const CommitRevealVoting: bool = false;
+	/// How long can committed votes be revealed? Value in blocks.
+	pub const VoteRevealDuration: BlockNumber = OneWeek::get();
+	/// How many delegations can be chained?
+	pub const MaxDelegationDepth: u32 = 10;
+	/// Which share of the reward for a correct delegated vote does the delegate receive?
+	/// The delegator receives the rest.
+	pub const DelegateRewardShare: Permill = Permill::from_percent(20);
 	/// Part 1.1: Proposal state configuration
 	// How many (slashable) funds must a simple User (no identity) lock to be able to propose?
 	// pub const UserProposeFee : Balance = 100_000_000_000_000
@@ -303,6 +326,8 @@ parameter_types! {
 	pub const ProposeIdentifiedUserCap: u8 =  1;
 	/// Which identity level is required to create a proposal?
 	pub const ProposeIdentityLevel: u8 = 2;
+	/// How long can the title of a proposal be? Value in bytes.
+	pub const ProposeMaxTitleLength: u32 = 128;
 	/// How high is the reward (%) for the proposer if the proposal is converted into a project?
 	pub const ProposeReward: Permill = Permill::from_percent(5);
 	/// How long can proposals be submitted? Value in blocks.
@@ -316,8 +341,8 @@ parameter_types! {
 	pub const ProposeVoteIdentityLevel: u8 = 3;
 	/// How many votes can each identified user (with an appropriate identity level) submit?
 	pub const ProposeVoteMaxPerIdentifiedUser: u16 = 3;
-	/// How high is the reward if a proposal that the user voted for passes into next round?
-	pub const ProposeVoteCorrectReward: Balance = 100_000_000_000;
+	/// How much budget can the winning proposals of one round request in total?
+	pub const ProposeRoundBudget: Balance = 100_000_000_000_000_000;
 	/// Part 2.1: Concern state configuration
 	/// How many concerns can be submitted per concern round? (required for weight calculation)
 	pub const ConcernCap: u32 = 1_000;
@@ -325,8 +350,6 @@ parameter_types! {
 	pub const ConcernIdentifiedUserCap: u8 = 1;
 	/// Which identity level is required to submit a concern?
 	pub const ConcernIdentityLevel: u8 = 2;
-	/// How high is the reward if the concern receives enough votes to be passed to the next state?
-	pub const ConcernReward: Balance = 10_000_000_000_000;
 	/// How long can concerns be submitted? Value in blocks.
 	pub const ConcernRoundDuration: BlockNumber = OneWeek::get();
 	// How many (slashable) funds must a simple User (no identity) lock to be able to submit a concern?
@@ -341,8 +364,6 @@ parameter_types! {
 	pub const ConcernVoteIdentityLevel: u8 = 3;
 	/// How many votes can each identified user (with an appropriate identity level) submit?
 	pub const ConcernVoteMaxPerIdentifiedUser: u16 = 3;
-	/// How high is the reward if a concern that the user voted for passes into next round?
-	pub const ConcernVoteCorrectReward: Balance = 10_000_000_000;
 	/// Part 3: Final evaluation of the winning proposals and associated concern by the council
 	/// How much time is reserved for the council to vote? Value in blocks.
 	pub const CouncilVoteRoundDuration: BlockNumber = OneWeek::get();
@@ -360,35 +381,59 @@ impl pallet_proposal::Trait for Runtime {
 	type PalletsOrigin = OriginCaller;
 	type Proposal = Call;
 	type Identity = pallet_community_identity::Module<Runtime>;
+	type Treasury = pallet_community_treasury::Module<Runtime>;
 	type Council = pallet_council::Module<Runtime>;
 	type Project = pallet_project::Module<Runtime>;
+	type Documents = pallet_document_availability::Module<Runtime>;
+	type ConfigOrigin = EnsureRootOrCouncil;
 
-	// Parameters
+	// Parameters (initial configuration of the proposal cycle)
 	type IdentifiedUserPenality = IdentifiedUserPenality;
+	type AllowVoteStacking = AllowVoteStacking;
+	type CommitRevealVoting = CommitRevealVoting;
+	type VoteRevealDuration = VoteRevealDuration;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type DelegateRewardShare = DelegateRewardShare;
 	// type UserProposeFee = Get<Balance<Self>>;
 	type ProposeCap = ProposeCap;
 	type ProposeIdentifiedUserCap = ProposeIdentifiedUserCap;
 	type ProposeIdentityLevel = ProposeIdentityLevel;
+	type ProposeMaxTitleLength = ProposeMaxTitleLength;
 	type ProposeReward = ProposeReward;
 	type ProposeRoundDuration = ProposeRoundDuration;
 	type ProposeVoteAcceptanceMin = ProposeVoteAcceptanceMin;
 	type ProposeVoteDuration = ProposeVoteDuration;
 	type ProposeVoteIdentityLevel = ProposeVoteIdentityLevel;
 	type ProposeVoteMaxPerIdentifiedUser = ProposeVoteMaxPerIdentifiedUser;
-	type ProposeVoteCorrectReward = ProposeVoteCorrectReward;
+	type ProposalTally = pallet_proposal::traits::OneVotePerSlot;
+	type ProposeRoundBudget = ProposeRoundBudget;
 	type ConcernCap = ConcernCap;
 	type ConcernIdentifiedUserCap = ConcernIdentifiedUserCap;
 	type ConcernIdentityLevel = ConcernIdentityLevel;
-	type ConcernReward = ConcernReward;
 	type ConcernRoundDuration = ConcernRoundDuration;
 	// type UserConcernFee = UserConcernFee;
 	type ConcernVoteAcceptanceMin = ConcernVoteAcceptanceMin;
 	type ConcernVoteDuration = ConcernVoteDuration;
 	type ConcernVoteIdentityLevel = ConcernVoteIdentityLevel;
 	type ConcernVoteMaxPerIdentifiedUser = ConcernVoteMaxPerIdentifiedUser;
-	type ConcernVoteCorrectReward = ConcernVoteCorrectReward;
 	type CouncilVoteRoundDuration = CouncilVoteRoundDuration;
 	type CouncilAcceptConcernMinVotes = CouncilAcceptConcernMinVotes;
+	type WeightInfo = pallet_proposal::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// How long can identified users apply and vote for a project leader? Value in blocks.
+	pub const LeaderElectionDuration: BlockNumber = OneWeek::get();
+	/// How often are the salaries of the workers paid? Value in blocks.
+	pub const PayoutInterval: BlockNumber = OneWeek::get();
+	/// How long does a dismissed worker stay in the project (and receive the salary)? Value in blocks.
+	pub const GracePeriod: BlockNumber = OneWeek::get();
+	/// How long does a resigning worker stay in the project (and receive the salary)? Value in blocks.
+	pub const NoticePeriod: BlockNumber = OneWeek::get();
+	/// How many votes (%) of the colleagues are required to dismiss a worker?
+	pub const ReplaceWorkerMinVotes: Permill = Permill::from_percent(66);
+	/// How many votes (%) of the workers are required to remove the project leader?
+	pub const ReplaceLeaderMinVotes: Permill = Permill::from_percent(75);
 }
 
 /// Configure the project pallet
@@ -396,16 +441,130 @@ impl pallet_project::Trait for Runtime {
 	type Currency = pallet_balances::Module<Runtime>;
 	type Event = Event;
 	type Identity = pallet_community_identity::Module<Runtime>;
+	type Treasury = pallet_community_treasury::Module<Runtime>;
+	type Scheduler = pallet_scheduler::Module<Runtime>;
+	type PalletsOrigin = OriginCaller;
+	type Proposal = Call;
+	type Documents = pallet_document_availability::Module<Runtime>;
+
+	// Parameters
+	type LeaderElectionDuration = LeaderElectionDuration;
+	type PayoutInterval = PayoutInterval;
+	type GracePeriod = GracePeriod;
+	type NoticePeriod = NoticePeriod;
+	type ReplaceWorkerMinVotes = ReplaceWorkerMinVotes;
+	type ReplaceLeaderMinVotes = ReplaceLeaderMinVotes;
+	type WeightInfo = pallet_project::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Id the treasury account is derived from
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"so/trsry");
+	/// How long is an era? Zero disables the timer, the proposal pallet starts an era with every round.
+	pub const EraDuration: BlockNumber = 0;
+	/// How many queued payouts are paid per block at most?
+	pub const MaxPayoutsPerBlock: u32 = 50;
+}
+
+/// Number of identities the issuance is tied to
+pub struct ActiveIdentities;
+
+impl Get<u32> for ActiveIdentities {
+	fn get() -> u32 {
+		CommunityIdentity::identity_count()
+	}
+}
+
+/// Configure the community_treasury pallet
+impl pallet_community_treasury::Trait for Runtime {
+	type Event = Event;
+	type Currency = pallet_balances::Module<Runtime>;
+
+	// Parameters
+	type ModuleId = TreasuryModuleId;
+	type EraDuration = EraDuration;
+	type ActiveIdentities = ActiveIdentities;
+	type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+	type ConfigOrigin = EnsureRootOrCouncil;
+	type WeightInfo = pallet_community_treasury::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the community_identity pallet
 impl pallet_community_identity::Trait for Runtime {
+	type Event = Event;
 	type Timestamp = Moment;
+	type WeightInfo = pallet_community_identity::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the community_identity pallet
 impl pallet_council::Trait for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type Proposal = Call;
 	type Identity = pallet_community_identity::Module<Runtime>;
+	type WeightInfo = pallet_council::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// How many documents does the off-chain worker of a node check per block at most?
+	pub const ChecksPerBlock: u32 = 2;
+	/// How long does the off-chain worker wait until it checks an unreported document again? Value in blocks.
+	pub const RecheckInterval: BlockNumber = 10;
+}
+
+/// Configure the document_availability pallet
+impl pallet_document_availability::Trait for Runtime {
+	type Event = Event;
+	type ReporterId = pallet_document_availability::crypto::ReporterId;
+	type ReporterOrigin = EnsureRootOrCouncil;
+
+	// Parameters
+	type ChecksPerBlock = ChecksPerBlock;
+	type RecheckInterval = RecheckInterval;
+	type WeightInfo = pallet_document_availability::weights::SubstrateWeight<Runtime>;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+/// Sign the transactions of the off-chain workers, like the wallets sign them
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		// The transaction is valid for the next BlockHashCount blocks at most
+		let period = BlockHashCount::get().checked_next_power_of_two().map(|p| p / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::from(nonce),
+			frame_system::CheckWeight::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::from(0),
+		);
+		let raw_payload = generic::SignedPayload::new(call, extra).map_err(|e| {
+			frame_support::debug::warn!("Unable to create signed payload: {:?}", e);
+		}).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (account, signature, extra)))
+	}
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -425,10 +584,12 @@ construct_runtime!(
 		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Custom pallets
-		CommunityIdentity: pallet_community_identity::{Module, Call},
-		Council: pallet_council::{Module, Call, Storage},
-		Project: pallet_project::{Module, Call, Storage, Event<T>},
+		CommunityIdentity: pallet_community_identity::{Module, Call, Storage, Event<T>},
+		CommunityTreasury: pallet_community_treasury::{Module, Call, Storage, Event<T>, Config<T>},
+		Council: pallet_council::{Module, Call, Storage, Config<T>, Event<T>, Origin},
+		Project: pallet_project::{Module, Call, Storage, Event<T>, Config},
 		Proposal: pallet_proposal::{Module, Call, Storage, Event<T>, Config},
+		DocumentAvailability: pallet_document_availability::{Module, Call, Storage, Event<T>, Config<T>},
 
 	}
 );
@@ -590,6 +751,110 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl api::ProposalApi<Block> for Runtime {
+		fn phase() -> pallet_proposal_types::Phase<BlockNumber, Moment> {
+			let (state, round, next_transit) = Proposal::get_phase();
+			let remaining_blocks = next_transit.saturating_sub(System::block_number()) as Moment;
+
+			pallet_proposal_types::Phase {
+				state,
+				round,
+				next_transit,
+				next_transit_estimate: Timestamp::now() + remaining_blocks * MILLISECS_PER_BLOCK,
+			}
+		}
+
+		fn proposals(round: pallet_proposal_types::RoundIndex, start: u32, limit: u32)
+			-> Result<Vec<(AccountId, pallet_proposal_types::Proposal<Balance, BlockNumber>)>, DispatchError>
+		{
+			Proposal::get_proposals(round, start, limit)
+		}
+
+		fn proposals_by_category(category: pallet_proposal_types::CategoryId, start: u32, limit: u32)
+			-> Vec<(AccountId, pallet_proposal_types::Proposal<Balance, BlockNumber>)>
+		{
+			Proposal::get_proposals_by_category(category, start, limit)
+		}
+
+		fn concerns(round: pallet_proposal_types::RoundIndex, start: u32, limit: u32)
+			-> Result<Vec<(AccountId, pallet_proposal_types::Concern)>, DispatchError>
+		{
+			Proposal::get_concerns(round, start, limit)
+		}
+
+		fn winners(round: pallet_proposal_types::RoundIndex, start: u32, limit: u32)
+			-> Vec<pallet_proposal_types::ProposalWinner<AccountId, Balance, BlockNumber>>
+		{
+			Proposal::get_winners(round, start, limit)
+		}
+
+		fn winners_by_category(round: pallet_proposal_types::RoundIndex, category: pallet_proposal_types::CategoryId, start: u32, limit: u32)
+			-> Vec<pallet_proposal_types::ProposalWinner<AccountId, Balance, BlockNumber>>
+		{
+			Proposal::get_winners_by_category(round, category, start, limit)
+		}
+
+		fn round_summary(round: pallet_proposal_types::RoundIndex)
+			-> Option<pallet_proposal_types::RoundSummary<AccountId, Balance, BlockNumber, ProjectID>>
+		{
+			Proposal::round_summary(round)
+		}
+
+		fn categories() -> Vec<(pallet_proposal_types::CategoryId, Vec<u8>)> {
+			Proposal::get_categories()
+		}
+
+		fn vote_budget(identity: AccountId) -> pallet_proposal_types::VoteBudget {
+			Proposal::get_vote_budget(&identity)
+		}
+	}
+
+	impl api::ProjectApi<Block> for Runtime {
+		fn project(project: ProjectID) -> Option<ProjectType<Balance, BlockNumber, AccountId>> {
+			<Project as ProjectTrait>::get_project(project).unwrap_or(None)
+		}
+
+		fn projects(state: Option<ProjectState>, start: ProjectID, limit: u32)
+			-> (Vec<ProjectType<Balance, BlockNumber, AccountId>>, Option<ProjectID>)
+		{
+			Project::get_projects_by_state(state, start, limit)
+		}
+
+		fn worker_projects(identity: AccountId, start: u32, limit: u32)
+			-> Vec<ProjectType<Balance, BlockNumber, AccountId>>
+		{
+			Project::get_projects_of_worker(&identity, start, limit)
+		}
+
+		fn workers(project: ProjectID, start: u32, limit: u32) -> Vec<Worker<Balance, BlockNumber, AccountId>> {
+			<Project as ProjectTrait>::get_workers(project, start, limit).unwrap_or_default()
+		}
+	}
+
+	impl api::CouncilApi<Block> for Runtime {
+		fn members() -> Vec<AccountId> {
+			Council::members()
+		}
+
+		fn open_polls(start: u32, limit: u32) -> Vec<pallet_council::PollTally<AccountId, BlockNumber>> {
+			Council::get_open_polls(start, limit)
+		}
+	}
+
+	impl api::IdentityApi<Block> for Runtime {
+		fn identity_level(identity: AccountId) -> pallet_community_identity::IdentityLevel {
+			CommunityIdentity::get_identity_level(&identity)
+		}
+
+		fn pending_reviews(identity: AccountId) -> Vec<(AccountId, pallet_community_identity::ReviewRequest<Moment>)> {
+			CommunityIdentity::get_pending_reviews(&identity)
+		}
+
+		fn appointments(identity: AccountId) -> Vec<(Moment, Vec<AccountId>)> {
+			CommunityIdentity::get_appointments(&identity)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -619,6 +884,12 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_community_identity, CommunityIdentity);
+			add_benchmark!(params, batches, pallet_community_treasury, CommunityTreasury);
+			add_benchmark!(params, batches, pallet_council, Council);
+			add_benchmark!(params, batches, pallet_document_availability, DocumentAvailability);
+			add_benchmark!(params, batches, pallet_project, Project);
+			add_benchmark!(params, batches, pallet_proposal, Proposal);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)